@@ -0,0 +1,54 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use naive_bayes::{bench::SyntheticCorpus, NaiveBayes, PredictOptions, Scratch};
+
+fn training(c: &mut Criterion) {
+    let mut group = c.benchmark_group("train");
+    for documents in [100, 1000] {
+        let corpus = SyntheticCorpus {
+            documents,
+            ..Default::default()
+        };
+        let (train, classes, vocab) = (corpus.generate(), corpus.classes(), corpus.vocab());
+        group.throughput(Throughput::Elements(documents as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(documents),
+            &train,
+            |b, train| b.iter(|| NaiveBayes::new(train, classes.clone(), vocab.clone())),
+        );
+    }
+    group.finish();
+}
+
+fn prediction(c: &mut Criterion) {
+    let corpus = SyntheticCorpus::default();
+    let documents = corpus.generate();
+    let model = NaiveBayes::new(&documents, corpus.classes(), corpus.vocab());
+    let options = PredictOptions::default();
+
+    let mut group = c.benchmark_group("predict");
+    group.throughput(Throughput::Elements(documents.len() as u64));
+    group.bench_function("predict", |b| {
+        b.iter(|| {
+            for document in documents.iter() {
+                model.predict(document);
+            }
+        })
+    });
+    group.bench_function("scores_into", |b| {
+        let mut scratch = Scratch::default();
+        b.iter(|| {
+            for document in documents.iter() {
+                model.scores_into(document, &options, &mut scratch);
+            }
+        })
+    });
+    group.bench_function("score_batch", |b| {
+        let scorer = model.token_scorer();
+        let batch: Vec<Vec<u32>> = documents.iter().map(|d| scorer.token_ids(d)).collect();
+        b.iter(|| scorer.score_batch(&batch))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, training, prediction);
+criterion_main!(benches);
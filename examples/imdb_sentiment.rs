@@ -0,0 +1,83 @@
+//! Trains a sentiment model on the IMDB reviews, evaluates it on the test
+//! reviews, saves it as JSON and writes the scores of the test reviews as
+//! CSV for analysis.
+//!
+//! ```text
+//! cargo run --release --example imdb_sentiment --features serde [aclImdb]
+//! ```
+//!
+//! The folder is the extracted dataset from
+//! <https://ai.stanford.edu/~amaas/data/sentiment/>: `imdb.vocab` and the
+//! `train` and `test` folders with one subfolder of reviews per label. It
+//! defaults to `dataset`. The unlabelled `unsup` reviews are skipped.
+
+use std::{env, error::Error, fs::File, io::BufWriter, path::Path, process::ExitCode};
+
+use naive_bayes::{
+    datasets::{read_labelled_folders, read_vocabulary},
+    evaluation::EvaluationCache,
+    write_scores_csv, Document, NaiveBayes, NaiveBayesBuilder, StopwordLanguage, TextNormalization,
+};
+
+fn main() -> ExitCode {
+    let root = env::args().nth(1).unwrap_or_else(|| "dataset".into());
+    if !Path::new(&root).join("imdb.vocab").is_file() {
+        eprintln!(
+            "{} is not an IMDB dataset folder, see the example docs",
+            root
+        );
+        return ExitCode::FAILURE;
+    }
+    match run(Path::new(&root)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("imdb_sentiment: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(root: &Path) -> Result<(), Box<dyn Error>> {
+    let labelled = |documents: Vec<Document>| -> Vec<Document> {
+        documents
+            .into_iter()
+            .filter(|d| d.class == "pos" || d.class == "neg")
+            .collect()
+    };
+    let vocab = read_vocabulary(root.join("imdb.vocab"))?;
+    let train = labelled(read_labelled_folders(root.join("train"))?);
+    let test = labelled(read_labelled_folders(root.join("test"))?);
+    println!(
+        "{} training and {} test reviews, {} words",
+        train.len(),
+        test.len(),
+        vocab.len()
+    );
+
+    let (fitted, _) = NaiveBayesBuilder::new()
+        .documents(&train)
+        .vocabulary(vocab)
+        .normalization(TextNormalization::lowercase())
+        .stopwords(StopwordLanguage::English.stopwords())
+        .fit()?;
+    let model = fitted.into_model();
+
+    let cache = EvaluationCache::new(test);
+    let metrics = cache.evaluate(&model);
+    println!(
+        "test accuracy {:.4}, macro F1 {:.4}",
+        metrics.accuracy(),
+        metrics.macro_f1()
+    );
+
+    let model_path = env::temp_dir().join("imdb-sentiment.json");
+    model.save_json(&model_path)?;
+    let model = NaiveBayes::load_json(&model_path)?;
+    println!("saved and reloaded {}", model_path.display());
+
+    let scores_path = env::temp_dir().join("imdb-scores.csv");
+    let rows = cache.documents().iter().enumerate();
+    let written = write_scores_csv(&model, rows, BufWriter::new(File::create(&scores_path)?))?;
+    println!("wrote {} scores to {}", written, scores_path.display());
+    Ok(())
+}
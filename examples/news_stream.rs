@@ -0,0 +1,122 @@
+//! Classifies a stream of news headlines by topic while learning from them:
+//! every batch is first served from a snapshot of a live model, scored
+//! against its labels as they arrive, and then learned from, while another
+//! thread keeps answering queries without waiting for the updates.
+//!
+//! ```text
+//! cargo run --example news_stream
+//! ```
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use naive_bayes::{
+    live::LiveModel,
+    monitor::{AccuracyMonitor, MonitorThresholds},
+    Document, NaiveBayes,
+};
+
+const TOPICS: [(&str, &[&str]); 3] = [
+    (
+        "sports",
+        &[
+            "match", "goal", "league", "coach", "season", "win", "team", "final",
+        ],
+    ),
+    (
+        "business",
+        &[
+            "market", "shares", "profit", "bank", "rates", "deal", "growth", "win",
+        ],
+    ),
+    (
+        "science",
+        &[
+            "study",
+            "space",
+            "cells",
+            "climate",
+            "data",
+            "telescope",
+            "team",
+            "energy",
+        ],
+    ),
+];
+
+/// A synthetic headline of topic `topic`, built from its keywords.
+fn headline(i: usize, topic: usize) -> Document {
+    let (class, words) = TOPICS[topic];
+    let text: Vec<&str> = (0..4)
+        .map(|k| words[(i * 7 + k * 3) % words.len()])
+        .collect();
+    Document {
+        class: class.into(),
+        text: text.join(" "),
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // bootstrap on one headline per keyword and topic, which also fixes
+    // the vocabulary; later words outside it are ignored
+    let seed: Vec<Document> = (0..8)
+        .flat_map(|i| (0..TOPICS.len()).map(move |topic| headline(i, topic)))
+        .collect();
+    let live = Arc::new(LiveModel::new(NaiveBayes::fit_corpus(&seed)?));
+
+    let done = Arc::new(AtomicBool::new(false));
+    let server = {
+        let (live, done) = (live.clone(), done.clone());
+        thread::spawn(move || {
+            let query = Document {
+                class: String::new(),
+                text: "telescope data".into(),
+            };
+            let mut served = 0;
+            while !done.load(Ordering::Relaxed) {
+                let snapshot = live.snapshot();
+                assert!(snapshot.predict(&query).is_some());
+                served += 1;
+                thread::yield_now();
+            }
+            served
+        })
+    };
+
+    let mut monitor = AccuracyMonitor::new(
+        30,
+        MonitorThresholds {
+            min_accuracy: Some(0.8),
+            ..Default::default()
+        },
+    );
+    for batch in 0..10 {
+        let headlines: Vec<Document> = (0..12)
+            .map(|i| headline(batch * 12 + i, (batch + i) % TOPICS.len()))
+            .collect();
+        let snapshot = live.snapshot();
+        for document in &headlines {
+            let predicted = snapshot.predict(document).unwrap_or_default();
+            for alert in monitor.record(&predicted, &document.class) {
+                println!("batch {}: {:?}", batch, alert);
+            }
+        }
+        let epoch = live.partial_fit(&headlines);
+        println!(
+            "batch {}: window accuracy {:.2}, published epoch {}",
+            batch,
+            monitor.accuracy(),
+            epoch
+        );
+    }
+
+    done.store(true, Ordering::Relaxed);
+    let served = server.join().expect("the serving thread panicked");
+    println!("served {} queries during training", served);
+    Ok(())
+}
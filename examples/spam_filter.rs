@@ -0,0 +1,71 @@
+//! Trains a spam filter on a CSV of labelled text messages with character
+//! n-grams, evaluates it on a held-out split, saves it, and classifies new
+//! messages with the reloaded model.
+//!
+//! ```text
+//! cargo run --example spam_filter --features serde [messages.csv]
+//! ```
+//!
+//! The CSV has a header and the label and the text in its first two
+//! columns. Without an argument, the sample `examples/data/sms.csv` is used.
+
+use std::{env, error::Error, fs::File};
+
+use naive_bayes::{
+    datasets::{read_csv, CsvFormat},
+    evaluation::evaluate,
+    pipeline::train_test_split,
+    rng::SplitMix64,
+    Document, NaiveBayes, NaiveBayesBuilder, TextNormalization,
+};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = env::args()
+        .nth(1)
+        .unwrap_or_else(|| concat!(env!("CARGO_MANIFEST_DIR"), "/examples/data/sms.csv").into());
+    let messages = read_csv(File::open(&path)?, &CsvFormat::default())?;
+    println!("read {} messages from {}", messages.len(), path);
+
+    let (train, test) = train_test_split(&messages, 0.25, &mut SplitMix64::new(7));
+    let (fitted, warnings) = NaiveBayesBuilder::new()
+        .documents(&train)
+        .normalization(TextNormalization::lowercase())
+        .char_ngrams(3, 5)
+        .fit()?;
+    for warning in warnings {
+        println!("warning: {}", warning);
+    }
+    let model = fitted.into_model();
+
+    let metrics = evaluate(&model, &test);
+    println!(
+        "held-out accuracy {:.3}, macro F1 {:.3} on {} messages",
+        metrics.accuracy(),
+        metrics.macro_f1(),
+        metrics.documents
+    );
+
+    // the tokenizer and its normalization are saved with the model
+    let bytes = model.to_bytes();
+    println!("saved model: {} bytes", bytes.len());
+    let model = NaiveBayes::from_bytes(&bytes)?;
+
+    for text in [
+        "Congratulations, you won a free cruise! Call now",
+        "running late, see you at the cafe",
+        "FR33 PRIZE!!! txt WIN",
+    ] {
+        let message = Document {
+            class: String::new(),
+            text: text.into(),
+        };
+        let probabilities = model.predict_proba(&message);
+        println!(
+            "{:>5} ({:.2} spam)  {}",
+            model.predict(&message).unwrap_or_default(),
+            probabilities.get("spam").copied().unwrap_or(0.0),
+            text
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,59 @@
+"""Exports the scikit-learn fixtures checked by the `conformance` module.
+
+    python3 fixtures/conformance/export.py
+
+Words are split at whitespace and kept as they are, like the default
+tokenizer of the crate.
+"""
+
+import json
+import pathlib
+
+from sklearn.feature_extraction.text import CountVectorizer
+from sklearn.naive_bayes import BernoulliNB, MultinomialNB
+
+TRAIN = [
+    ("pos", "good movie good plot"),
+    ("pos", "great acting good fun"),
+    ("pos", "fun plot great movie"),
+    ("neg", "bad movie bad acting"),
+    ("neg", "boring plot bad"),
+    ("neg", "awful boring movie"),
+    ("neutral", "movie plot acting"),
+    ("neutral", "long movie"),
+]
+TEST = [
+    "good fun movie",
+    "bad boring acting",
+    "long plot",
+    "great unknown words",
+    "",
+]
+
+
+def export(estimator, name, alpha):
+    vectorizer = CountVectorizer(token_pattern=r"\S+", lowercase=False)
+    counts = vectorizer.fit_transform([text for _, text in TRAIN])
+    model = estimator(alpha=alpha).fit(counts, [label for label, _ in TRAIN])
+    test = vectorizer.transform(TEST)
+    fixture = {
+        "estimator": name,
+        "alpha": alpha,
+        "classes": model.classes_.tolist(),
+        "features": vectorizer.get_feature_names_out().tolist(),
+        "train": [{"class": label, "text": text} for label, text in TRAIN],
+        "class_log_prior": model.class_log_prior_.tolist(),
+        "feature_log_prob": model.feature_log_prob_.tolist(),
+        "test": [
+            {"text": text, "joint_log_likelihood": jll.tolist(), "prediction": prediction}
+            for text, jll, prediction in zip(
+                TEST, model.predict_joint_log_proba(test), model.predict(test)
+            )
+        ],
+    }
+    path = pathlib.Path(__file__).with_name(f"{name.lower()}.json")
+    path.write_text(json.dumps(fixture, indent=2) + "\n")
+
+
+export(MultinomialNB, "MultinomialNB", 0.5)
+export(BernoulliNB, "BernoulliNB", 1.0)
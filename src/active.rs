@@ -0,0 +1,135 @@
+use crate::{classifier::softmax, selection::jaccard, Classifier, Document};
+
+/// How uncertain a classifier is about a document.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Uncertainty {
+    /// Entropy of the posterior distribution.
+    #[default]
+    Entropy,
+    /// One minus the gap between the two most probable classes.
+    Margin,
+}
+
+#[derive(Clone, Debug)]
+pub struct QuerySelection {
+    /// Number of documents to pick.
+    pub k: usize,
+    pub uncertainty: Uncertainty,
+    /// Skip candidates whose word sets have at least this Jaccard similarity
+    /// to an already picked document.
+    pub max_similarity: Option<f64>,
+}
+
+impl Default for QuerySelection {
+    fn default() -> Self {
+        Self {
+            k: 10,
+            uncertainty: Uncertainty::default(),
+            max_similarity: None,
+        }
+    }
+}
+
+/// Indices into `pool` of the documents `classifier` is least sure about,
+/// most uncertain first, for annotation.
+pub fn select_queries(
+    classifier: &dyn Classifier,
+    pool: &[&str],
+    selection: &QuerySelection,
+) -> Vec<usize> {
+    let mut ranked: Vec<(usize, f64)> = pool
+        .iter()
+        .enumerate()
+        .map(|(i, text)| {
+            let document = Document {
+                class: String::new(),
+                text: text.to_string(),
+            };
+            let posterior = softmax(&classifier.scores(&document));
+            (i, uncertainty(&posterior, selection.uncertainty))
+        })
+        .collect();
+    ranked.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.cmp(b)));
+
+    let words = |i: usize| {
+        let mut words: Vec<&str> = pool[i].split_whitespace().collect();
+        words.sort_unstable();
+        words.dedup();
+        words
+    };
+    let mut picked: Vec<(usize, Vec<&str>)> = Vec::with_capacity(selection.k);
+    for (i, _) in ranked {
+        if picked.len() == selection.k {
+            break;
+        }
+        let candidate = words(i);
+        let duplicate = selection.max_similarity.is_some_and(|max| {
+            picked
+                .iter()
+                .any(|(_, other)| jaccard(&candidate, other) >= max)
+        });
+        if !duplicate {
+            picked.push((i, candidate));
+        }
+    }
+    picked.into_iter().map(|(i, _)| i).collect()
+}
+
+fn uncertainty(posterior: &[f64], measure: Uncertainty) -> f64 {
+    match measure {
+        Uncertainty::Entropy => -posterior
+            .iter()
+            .filter(|p| **p > 0.0)
+            .map(|p| p * p.ln())
+            .sum::<f64>(),
+        Uncertainty::Margin => {
+            let (mut first, mut second) = (0.0f64, 0.0f64);
+            for p in posterior {
+                if *p > first {
+                    (first, second) = (*p, first);
+                } else if *p > second {
+                    second = *p;
+                }
+            }
+            1.0 - (first - second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    #[test]
+    fn test_select_most_uncertain_documents() {
+        let model = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good", "fine"],
+            vec![-0.7, -0.7],
+            vec![
+                vec![(0, -0.5), (1, -3.0), (2, -1.0)],
+                vec![(0, -3.0), (1, -0.5), (2, -1.0)],
+            ],
+            vec![-5.0, -5.0],
+        );
+        let pool = ["good good", "fine", "bad", "good bad", "fine fine"];
+
+        for uncertainty in [Uncertainty::Entropy, Uncertainty::Margin] {
+            let selection = QuerySelection {
+                k: 3,
+                uncertainty,
+                max_similarity: None,
+            };
+            assert_eq!(select_queries(&model, &pool, &selection), [1, 3, 4]);
+        }
+
+        // "fine fine" has the same words as "fine"
+        let selection = QuerySelection {
+            k: 3,
+            max_similarity: Some(0.9),
+            ..Default::default()
+        };
+        assert_eq!(select_queries(&model, &pool, &selection), [1, 3, 2]);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    rng::{RandomSource, SplitMix64},
+    Class, Document, Tokenizer,
+};
+
+#[derive(Clone, Debug)]
+pub struct AuditOptions {
+    /// Training documents kept per class.
+    pub samples_per_class: usize,
+    pub seed: u64,
+}
+
+impl Default for AuditOptions {
+    fn default() -> Self {
+        Self {
+            samples_per_class: 10,
+            seed: 0,
+        }
+    }
+}
+
+/// What a model saw of one class during training.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassSummary {
+    pub documents: usize,
+    pub words: usize,
+    pub min_words: usize,
+    pub max_words: usize,
+    /// A uniform sample of the class's training documents.
+    pub samples: Vec<Document>,
+}
+
+impl ClassSummary {
+    pub fn mean_words(&self) -> f64 {
+        self.words as f64 / self.documents.max(1) as f64
+    }
+}
+
+/// Per-class statistics and reservoir samples of the training documents,
+/// kept up to date by [`crate::NaiveBayes::partial_fit`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrainingSummary {
+    classes: Vec<Class>,
+    summaries: Vec<ClassSummary>,
+    samples_per_class: usize,
+    rng: SplitMix64,
+}
+
+impl TrainingSummary {
+    pub(crate) fn new(classes: &[Class], options: &AuditOptions) -> Self {
+        Self {
+            classes: classes.to_vec(),
+            summaries: vec![ClassSummary::default(); classes.len()],
+            samples_per_class: options.samples_per_class,
+            rng: SplitMix64::new(options.seed),
+        }
+    }
+
+    /// Adds `documents`, split into words by `tokenizer`, to the statistics,
+    /// sampling them with Algorithm R. Documents of classes outside the
+    /// model are ignored.
+    pub(crate) fn record(&mut self, documents: &[Document], tokenizer: &dyn Tokenizer) {
+        let mut rng = self.rng.clone();
+        self.record_with(documents, tokenizer, &mut rng);
+        self.rng = rng;
+    }
+
+    /// Like [`TrainingSummary::record`], sampling with `rng` instead of the
+    /// generator seeded by [`AuditOptions::seed`].
+    pub(crate) fn record_with(
+        &mut self,
+        documents: &[Document],
+        tokenizer: &dyn Tokenizer,
+        rng: &mut impl RandomSource,
+    ) {
+        for document in documents {
+            let Ok(class) = self.classes.binary_search(&document.class) else {
+                continue;
+            };
+            let summary = &mut self.summaries[class];
+            let mut words = 0;
+            tokenizer.for_each_token(&document.text, &mut |_| words += 1);
+            summary.min_words = match summary.documents {
+                0 => words,
+                _ => summary.min_words.min(words),
+            };
+            summary.max_words = summary.max_words.max(words);
+            summary.words += words;
+            summary.documents += 1;
+
+            if summary.samples.len() < self.samples_per_class {
+                summary.samples.push(document.clone());
+            } else {
+                let slot = rng.below(summary.documents);
+                if slot < self.samples_per_class {
+                    summary.samples[slot] = document.clone();
+                }
+            }
+        }
+    }
+
+    pub(crate) fn insert_class(&mut self, index: usize, class: &str) {
+        self.classes.insert(index, class.to_owned());
+        self.summaries.insert(index, ClassSummary::default());
+    }
+
+    pub fn class(&self, class: &str) -> Option<&ClassSummary> {
+        let index = self
+            .classes
+            .binary_search_by(|other| other.as_str().cmp(class))
+            .ok()?;
+        Some(&self.summaries[index])
+    }
+
+    /// Every class and its summary, in the order of the model's classes.
+    pub fn iter(&self) -> impl Iterator<Item = (&Class, &ClassSummary)> {
+        self.classes.iter().zip(&self.summaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reservoir_keeps_a_bounded_sample() {
+        let classes = vec!["a".to_string(), "b".to_string()];
+        let options = AuditOptions {
+            samples_per_class: 3,
+            seed: 7,
+        };
+        let mut summary = TrainingSummary::new(&classes, &options);
+        let documents: Vec<Document> = (0..100)
+            .map(|i| Document {
+                class: if i % 4 == 0 { "b" } else { "a" }.into(),
+                text: format!("d{} {}", i, "word ".repeat(i % 5)),
+            })
+            .chain([Document {
+                class: "c".into(),
+                text: "ignored".into(),
+            }])
+            .collect();
+        summary.record(&documents[..50], &crate::WhitespaceTokenizer);
+        summary.record(&documents[50..], &crate::WhitespaceTokenizer);
+
+        let a = summary.class("a").unwrap();
+        assert_eq!(a.documents, 75);
+        assert_eq!((a.min_words, a.max_words), (1, 5));
+        assert_eq!(a.words, 75 + 150);
+        assert_eq!(a.samples.len(), 3);
+        assert!(a.samples.iter().all(|d| d.class == "a"));
+        // not just the first documents seen
+        assert!(a
+            .samples
+            .iter()
+            .any(|s| !documents[..4].iter().any(|d| d.text == s.text)));
+        assert_eq!(summary.class("b").unwrap().documents, 25);
+        assert!(summary.class("c").is_none());
+
+        // a source always drawing 0 replaces the first sample every time
+        struct Zero;
+        impl RandomSource for Zero {
+            fn next_u64(&mut self) -> u64 {
+                0
+            }
+        }
+        let mut scripted = TrainingSummary::new(&classes, &options);
+        scripted.record_with(&documents, &crate::WhitespaceTokenizer, &mut Zero);
+        let samples = &scripted.class("a").unwrap().samples;
+        assert_eq!(samples[0].text, documents[99].text);
+        assert_eq!(samples[1].text, documents[2].text);
+    }
+}
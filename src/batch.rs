@@ -0,0 +1,420 @@
+use std::{collections::HashMap, hash::Hash};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    tf_idf::document_weights, Class, Document, NaiveBayes, PredictOptions, Prediction, Scratch,
+    SharedTokenizer, Tokenizer, Word,
+};
+
+const LANES: usize = 4;
+
+/// Likelihoods laid out contiguously by token id, so a batch of documents
+/// already mapped to token ids can be scored with vectorized row additions.
+pub struct TokenScorer<L = Class> {
+    classes: Vec<L>,
+    tokens: HashMap<Word, u32>,
+    tokenizer: SharedTokenizer,
+    log_prior: Vec<f64>,
+    /// Row `token` holds the likelihood of every class, padded to `stride`.
+    rows: Vec<f64>,
+    stride: usize,
+    /// Idf of every token of a TF-IDF weighted model.
+    idf: Option<Vec<f64>>,
+}
+
+impl<L: Clone + Eq + Hash> TokenScorer<L> {
+    pub fn new(model: &NaiveBayes<L>) -> Self {
+        let classes = model.classes.clone();
+        let vocab_size = model.vocab.len();
+
+        let stride = classes.len().div_ceil(LANES) * LANES;
+        let mut log_prior = vec![0.0; stride];
+        log_prior[..classes.len()].copy_from_slice(&model.log_prior);
+        let mut rows = vec![0.0; vocab_size * stride];
+        for token in 0..vocab_size {
+            for class in 0..classes.len() {
+                rows[token * stride + class] = model.likelihood.get(class, token);
+            }
+        }
+
+        Self {
+            classes,
+            tokenizer: model.tokenizer.clone(),
+            tokens: model
+                .vocab
+                .iter()
+                .map(|(word, token)| (word.clone(), *token as u32))
+                .collect(),
+            log_prior,
+            rows,
+            stride,
+            idf: model.idf.clone(),
+        }
+    }
+
+    /// Classes in the order used by the score vectors.
+    pub fn classes(&self) -> &[L] {
+        &self.classes
+    }
+
+    /// Token ids of the in-vocabulary words of `document`.
+    pub fn token_ids(&self, document: &Document) -> Vec<u32> {
+        let mut ids = Vec::new();
+        self.tokenizer.for_each_token(&document.text, &mut |word| {
+            ids.extend(self.tokens.get(word).copied())
+        });
+        ids
+    }
+
+    /// Log prior plus summed likelihoods of every class, for each document,
+    /// TF-IDF weighted if the model is.
+    pub fn score_batch(&self, documents: &[Vec<u32>]) -> Vec<Vec<f64>> {
+        let add_row = row_adder();
+        documents
+            .iter()
+            .map(|tokens| {
+                let mut scores = self.log_prior.clone();
+                match &self.idf {
+                    Some(idf) => {
+                        let tokens = tokens.iter().map(|token| *token as usize);
+                        for (token, weight) in document_weights(Some(idf), tokens) {
+                            let row = &self.rows[token * self.stride..][..self.stride];
+                            for (score, likelihood) in scores.iter_mut().zip(row) {
+                                *score += weight * likelihood;
+                            }
+                        }
+                    }
+                    None => {
+                        for token in tokens {
+                            let start = *token as usize * self.stride;
+                            add_row(&mut scores, &self.rows[start..start + self.stride]);
+                        }
+                    }
+                }
+                scores.truncate(self.classes.len());
+                scores
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<L> TokenScorer<L> {
+    /// Same scores as [`TokenScorer::score_batch`], computed as the product of
+    /// the batch's term-count matrix and the likelihood matrix restricted to
+    /// the tokens occurring in the batch. Rows are documents, columns classes.
+    ///
+    /// Enabling ndarray's `blas` feature in the final binary makes the product
+    /// go through BLAS. Likelihoods must be finite, as an absent token's zero
+    /// count times an infinite likelihood is NaN.
+    pub fn score_batch_matrix(&self, documents: &[Vec<u32>]) -> ndarray::Array2<f64> {
+        use ndarray::{Array2, ArrayView1};
+
+        let mut columns: HashMap<u32, usize> = HashMap::new();
+        for token in documents.iter().flatten() {
+            let next = columns.len();
+            columns.entry(*token).or_insert(next);
+        }
+
+        let mut counts = Array2::<f64>::zeros((documents.len(), columns.len()));
+        for (row, tokens) in documents.iter().enumerate() {
+            for token in tokens {
+                counts[[row, columns[token]]] += 1.0;
+            }
+        }
+        if let Some(idf) = &self.idf {
+            for (token, column) in columns.iter() {
+                let idf = idf[*token as usize];
+                counts.column_mut(*column).mapv_inplace(|count| count * idf);
+            }
+            for mut row in counts.rows_mut() {
+                let norm = row.dot(&row).sqrt();
+                if norm > 0.0 {
+                    row.mapv_inplace(|weight| weight / norm);
+                }
+            }
+        }
+
+        let classes = self.classes.len();
+        let mut likelihoods = Array2::<f64>::zeros((columns.len(), classes));
+        for (token, column) in columns.iter() {
+            let start = *token as usize * self.stride;
+            likelihoods
+                .row_mut(*column)
+                .assign(&ArrayView1::from(&self.rows[start..start + classes]));
+        }
+
+        counts.dot(&likelihoods) + ArrayView1::from(&self.log_prior[..classes])
+    }
+}
+
+impl<L: Clone + Eq + Hash> NaiveBayes<L> {
+    pub fn token_scorer(&self) -> TokenScorer<L> {
+        TokenScorer::new(self)
+    }
+
+    /// Every document's log-score vector, columns ordered as
+    /// [`NaiveBayes::classes`], as features for a downstream meta-learner.
+    pub fn transform(&self, documents: &[Document]) -> Vec<Vec<f64>> {
+        let scorer = self.token_scorer();
+        let batch: Vec<Vec<u32>> = documents.iter().map(|d| scorer.token_ids(d)).collect();
+        scorer.score_batch(&batch)
+    }
+}
+
+impl<L: Clone + Eq + Hash + Send + Sync> NaiveBayes<L> {
+    /// [`NaiveBayes::predict_detailed`] of every document, with the default
+    /// options.
+    pub fn predict_batch(&self, documents: &[Document]) -> Vec<Prediction<L>> {
+        self.predict_batch_with(documents, &PredictOptions::default())
+    }
+
+    /// [`NaiveBayes::predict_detailed`] of every document, reusing scoring
+    /// buffers across documents. With the `parallel` feature documents are
+    /// predicted in parallel, each thread with its own buffers.
+    pub fn predict_batch_with(
+        &self,
+        documents: &[Document],
+        options: &PredictOptions,
+    ) -> Vec<Prediction<L>> {
+        #[cfg(feature = "parallel")]
+        return documents
+            .par_iter()
+            .map_init(Scratch::default, |scratch, document| {
+                self.predict_detailed_into(document, options, scratch)
+            })
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            let mut scratch = Scratch::default();
+            documents
+                .iter()
+                .map(|document| self.predict_detailed_into(document, options, &mut scratch))
+                .collect()
+        }
+    }
+}
+
+fn row_adder() -> fn(&mut [f64], &[f64]) {
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx") {
+        // SAFETY: AVX support was just detected, and rows are always scored
+        // into a buffer of the same padded stride.
+        return |scores, row| unsafe { add_row_avx(scores, row) };
+    }
+    add_row
+}
+
+fn add_row(scores: &mut [f64], row: &[f64]) {
+    for (score, likelihood) in scores.chunks_exact_mut(LANES).zip(row.chunks_exact(LANES)) {
+        for lane in 0..LANES {
+            score[lane] += likelihood[lane];
+        }
+    }
+}
+
+/// # Safety
+///
+/// The caller must ensure AVX is available. Both slices have the same length,
+/// a multiple of [`LANES`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx")]
+unsafe fn add_row_avx(scores: &mut [f64], row: &[f64]) {
+    use std::arch::x86_64::{_mm256_add_pd, _mm256_loadu_pd, _mm256_storeu_pd};
+
+    debug_assert_eq!(scores.len(), row.len());
+    for offset in (0..scores.len()).step_by(LANES) {
+        let score = scores.as_mut_ptr().add(offset);
+        let sum = _mm256_add_pd(
+            _mm256_loadu_pd(score),
+            _mm256_loadu_pd(row.as_ptr().add(offset)),
+        );
+        _mm256_storeu_pd(score, sum);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_score_batch_matches_model() {
+        let documents: Vec<Document> = [
+            ("a", "one two two"),
+            ("b", "three four"),
+            ("c", "one four five"),
+            ("d", "two five five"),
+            ("e", "six"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab: HashSet<Word> = ["one", "two", "four", "five"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let model = NaiveBayes::new(&documents, classes, vocab);
+        let scorer = model.token_scorer();
+
+        let batch: Vec<Vec<u32>> = documents.iter().map(|d| scorer.token_ids(d)).collect();
+        let scores = scorer.score_batch(&batch);
+
+        for (document, scores) in documents.iter().zip(scores) {
+            assert_eq!(scores.len(), 5);
+            for (class, score) in scorer.classes().iter().zip(scores) {
+                let class_id = model.class_id(class).unwrap();
+                let expected = document
+                    .words()
+                    .into_iter()
+                    .filter(|word| model.vocab.contains_key(*word))
+                    .fold(model.log_prior[class_id], |sum, word| {
+                        sum + model.likelihood.get(class_id, model.vocab[word])
+                    });
+                assert_eq!(score.to_bits(), expected.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn test_transform_orders_columns_by_class() {
+        let model = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap", "meeting"],
+            vec![-0.3, -1.4],
+            vec![vec![(0, -6.0), (1, -1.2)], vec![(0, -1.1), (1, -5.5)]],
+            vec![-8.0, -8.0],
+        );
+        let documents = [
+            Document {
+                class: String::new(),
+                text: "cheap cheap unknown".into(),
+            },
+            Document {
+                class: String::new(),
+                text: String::new(),
+            },
+        ];
+        let mut scratch = crate::Scratch::default();
+        let features = model.transform(&documents);
+        assert_eq!(features.len(), 2);
+        for (document, features) in documents.iter().zip(&features) {
+            let expected = model.scores_into(document, &Default::default(), &mut scratch);
+            assert_eq!(features.as_slice(), expected);
+        }
+        assert_eq!(features[1], vec![-0.3, -1.4]);
+    }
+
+    #[test]
+    fn test_transform_weights_tf_idf_models() {
+        let documents: Vec<Document> = [
+            ("spam", "click click click prize"),
+            ("spam", "click money money"),
+            ("ham", "click lunch"),
+            ("ham", "lunch meeting meeting"),
+            ("", "click click unknown lunch"),
+            ("", ""),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let model = crate::NaiveBayesBuilder::new()
+            .documents(&documents[..4])
+            .tf_idf(true)
+            .fit()
+            .unwrap()
+            .0
+            .into_model();
+        assert!(model.is_tf_idf());
+
+        let mut scratch = Scratch::default();
+        for (document, features) in documents.iter().zip(model.transform(&documents)) {
+            let expected = model.scores_into(document, &Default::default(), &mut scratch);
+            for (feature, expected) in features.iter().zip(expected) {
+                assert!((feature - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_predict_batch_matches_single_predictions() {
+        let documents: Vec<Document> = (0..50)
+            .map(|i| Document {
+                class: ["sports", "politics", "weather"][i % 3].into(),
+                text: format!("w{} w{} common", i % 7, i % 3),
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let model = NaiveBayes::new(&documents, classes, crate::vocabulary(&documents));
+        let options = PredictOptions {
+            min_evidence: Some(crate::MinEvidence {
+                tokens: 2,
+                fallback: crate::EvidenceFallback::Abstain,
+            }),
+            ..Default::default()
+        };
+        let mut documents = documents;
+        documents.push(Document {
+            class: String::new(),
+            text: "unknown common".into(),
+        });
+
+        let batch = model.predict_batch_with(&documents, &options);
+        assert_eq!(batch.len(), documents.len());
+        for (document, prediction) in documents.iter().zip(&batch) {
+            assert_eq!(*prediction, model.predict_detailed(document, &options));
+        }
+        assert_eq!(batch.last().unwrap().class, None);
+        assert_eq!(
+            model.predict_batch(&documents[..1])[0].class,
+            model.predict(&documents[0])
+        );
+
+        // class names of different lengths stand for typed labels
+        let typed = model.clone().with_labels(|class| class.len()).unwrap();
+        for (typed, prediction) in typed
+            .predict_batch_with(&documents, &options)
+            .iter()
+            .zip(&batch)
+        {
+            assert_eq!(typed.class, prediction.class.as_ref().map(|c| c.len()));
+            assert_eq!(typed.probability, prediction.probability);
+        }
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_score_batch_matrix_matches_rows() {
+        let scorer: TokenScorer = TokenScorer {
+            classes: vec!["a".into(), "b".into()],
+            tokens: HashMap::new(),
+            tokenizer: SharedTokenizer::default(),
+            log_prior: vec![-0.5, -1.0, 0.0, 0.0],
+            rows: vec![
+                -1.0, -2.0, 0.0, 0.0, //
+                -3.0, -0.5, 0.0, 0.0, //
+                -2.5, -2.5, 0.0, 0.0,
+            ],
+            stride: 4,
+            idf: None,
+        };
+        let batch = vec![vec![0, 1, 1], vec![2], vec![]];
+
+        let matrix = scorer.score_batch_matrix(&batch);
+        for (row, scores) in scorer.score_batch(&batch).into_iter().enumerate() {
+            for (class, score) in scores.into_iter().enumerate() {
+                assert!((matrix[[row, class]] - score).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Harness types shared by the criterion benchmarks, public so downstream
+//! experiments can measure the same workloads.
+
+use std::{
+    collections::HashSet,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    rng::{RandomSource, SplitMix64},
+    Class, Document, Word,
+};
+
+/// Parameters of a random labelled corpus. Every class draws half of its
+/// words from its own band of the vocabulary, so the classes are separable.
+#[derive(Clone, Debug)]
+pub struct SyntheticCorpus {
+    pub documents: usize,
+    pub classes: usize,
+    pub vocab_size: usize,
+    pub words_per_document: usize,
+    pub seed: u64,
+}
+
+impl Default for SyntheticCorpus {
+    fn default() -> Self {
+        Self {
+            documents: 1000,
+            classes: 4,
+            vocab_size: 5000,
+            words_per_document: 50,
+            seed: 0,
+        }
+    }
+}
+
+impl SyntheticCorpus {
+    pub fn generate(&self) -> Vec<Document> {
+        self.generate_with(&mut SplitMix64::new(self.seed))
+    }
+
+    /// Like [`SyntheticCorpus::generate`], drawing from `rng` instead of
+    /// `seed`.
+    pub fn generate_with(&self, rng: &mut impl RandomSource) -> Vec<Document> {
+        let band = (self.vocab_size / self.classes.max(1)).max(1);
+        (0..self.documents)
+            .map(|i| {
+                let class = i % self.classes.max(1);
+                let words: Vec<String> = (0..self.words_per_document)
+                    .map(|_| {
+                        let token = if rng.below(2) == 0 {
+                            (class * band + rng.below(band)) % self.vocab_size.max(1)
+                        } else {
+                            rng.below(self.vocab_size.max(1))
+                        };
+                        format!("w{}", token)
+                    })
+                    .collect();
+                Document {
+                    class: format!("c{}", class),
+                    text: words.join(" "),
+                }
+            })
+            .collect()
+    }
+
+    pub fn classes(&self) -> HashSet<Class> {
+        (0..self.classes)
+            .map(|class| format!("c{}", class))
+            .collect()
+    }
+
+    pub fn vocab(&self) -> HashSet<Word> {
+        (0..self.vocab_size)
+            .map(|token| format!("w{}", token))
+            .collect()
+    }
+}
+
+/// Wall-clock time of repeated runs of a closure.
+#[derive(Clone, Copy, Debug)]
+pub struct Timing {
+    pub iterations: u32,
+    pub total: Duration,
+}
+
+impl Timing {
+    pub fn measure(iterations: u32, mut f: impl FnMut()) -> Self {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            f();
+        }
+        Self {
+            iterations,
+            total: start.elapsed(),
+        }
+    }
+
+    pub fn per_iteration(&self) -> Duration {
+        self.total / self.iterations.max(1)
+    }
+
+    /// Throughput when every iteration processes `items` items.
+    pub fn throughput(&self, items: usize) -> Throughput {
+        Throughput {
+            items: items * self.iterations as usize,
+            elapsed: self.total,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Throughput {
+    pub items: usize,
+    pub elapsed: Duration,
+}
+
+impl Throughput {
+    pub fn per_second(&self) -> f64 {
+        self.items as f64 / self.elapsed.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+impl fmt::Display for Throughput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} items in {:.3?} ({:.0}/s)",
+            self.items,
+            self.elapsed,
+            self.per_second()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_corpus_is_reproducible() {
+        let corpus = SyntheticCorpus {
+            documents: 10,
+            classes: 3,
+            vocab_size: 30,
+            words_per_document: 5,
+            seed: 1,
+        };
+        let documents = corpus.generate();
+        assert_eq!(documents.len(), 10);
+        assert!(documents.iter().all(|d| d.words().len() == 5));
+        assert!(documents
+            .iter()
+            .flat_map(|d| d.words())
+            .all(|word| corpus.vocab().contains(word)));
+        assert_eq!(
+            documents.iter().map(|d| &d.text).collect::<Vec<_>>(),
+            corpus
+                .generate()
+                .iter()
+                .map(|d| &d.text)
+                .collect::<Vec<_>>()
+        );
+
+        let timing = Timing::measure(4, || {});
+        assert_eq!(timing.throughput(10).items, 40);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Class, Classifier, Document, Word};
+
+/// Naive Bayes over word presence: every vocabulary word is a binary feature
+/// of a document, and words that are absent count as evidence too.
+#[derive(Clone)]
+pub struct BernoulliNaiveBayes {
+    vocab: HashMap<Word, usize>,
+    classes: Vec<Class>,
+    log_prior: Vec<f64>,
+    /// Training documents of every class.
+    class_documents: Vec<usize>,
+    /// Row `token` holds, for every class, the training documents containing
+    /// the token.
+    document_frequency: Vec<usize>,
+    alpha: f64,
+    /// Score of a document containing no vocabulary word: the log prior plus
+    /// the log-probability of every word being absent.
+    absent: Vec<f64>,
+    /// Row `token` holds, for every class, how much the presence of the token
+    /// changes the score: `ln p - ln (1 - p)`.
+    present: Vec<f64>,
+}
+
+impl BernoulliNaiveBayes {
+    pub fn new(documents: &[Document], classes: HashSet<Class>, vocab: HashSet<Word>) -> Self {
+        let mut words: Vec<Word> = vocab.into_iter().collect();
+        words.sort_unstable();
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+        let class_count = classes.len();
+
+        // documents of every class containing every token
+        let mut class_documents = vec![0usize; class_count];
+        let mut document_frequency = vec![0usize; vocab.len() * class_count];
+        for document in documents {
+            let Ok(class) = classes.binary_search(&document.class) else {
+                continue;
+            };
+            class_documents[class] += 1;
+            let tokens: HashSet<usize> = document
+                .words()
+                .into_iter()
+                .filter_map(|word| vocab.get(word).copied())
+                .collect();
+            for token in tokens {
+                document_frequency[token * class_count + class] += 1;
+            }
+        }
+
+        let total: usize = class_documents.iter().sum();
+        let log_prior: Vec<f64> = class_documents
+            .iter()
+            .map(|n| (*n as f64 / total as f64).ln())
+            .collect();
+        let mut model = Self {
+            vocab,
+            classes,
+            log_prior,
+            class_documents,
+            document_frequency,
+            alpha: 1.0,
+            absent: Vec::new(),
+            present: Vec::new(),
+        };
+        model.estimate();
+        model
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Switches to Lidstone smoothing of presence and absence with
+    /// pseudo-count `alpha`, 1 being add-one smoothing, and re-estimates the
+    /// model.
+    ///
+    /// # Panics
+    /// If `alpha` is negative or not finite.
+    pub fn set_alpha(&mut self, alpha: f64) {
+        assert!(
+            alpha.is_finite() && alpha >= 0.0,
+            "invalid smoothing alpha {}",
+            alpha
+        );
+        self.alpha = alpha;
+        self.estimate();
+    }
+
+    /// Smoothed probability that a document of `class` contains the token.
+    fn presence(&self, class: usize, token: usize) -> f64 {
+        let df = self.document_frequency[token * self.classes.len() + class];
+        (df as f64 + self.alpha) / (self.class_documents[class] as f64 + 2.0 * self.alpha)
+    }
+
+    fn estimate(&mut self) {
+        let class_count = self.classes.len();
+        self.absent = self.log_prior.clone();
+        self.present = vec![0.0; self.document_frequency.len()];
+        for cell in 0..self.document_frequency.len() {
+            let (token, class) = (cell / class_count, cell % class_count);
+            let p = self.presence(class, token);
+            self.absent[class] += (1.0 - p).ln();
+            self.present[cell] = p.ln() - (1.0 - p).ln();
+        }
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    pub fn log_prior(&self) -> &[f64] {
+        &self.log_prior
+    }
+
+    /// Log-probability that a document of the class at `class` contains
+    /// `word`, if it is in the vocabulary.
+    pub fn log_presence(&self, class: usize, word: &str) -> Option<f64> {
+        let token = *self.vocab.get(word)?;
+        Some(self.presence(class, token).ln())
+    }
+
+    /// Log joint probability of every class and the presence pattern of
+    /// `document`, in the order of [`BernoulliNaiveBayes::classes`].
+    pub fn scores(&self, document: &Document) -> Vec<f64> {
+        let classes = self.classes.len();
+        let tokens: HashSet<usize> = document
+            .words()
+            .into_iter()
+            .filter_map(|word| self.vocab.get(word).copied())
+            .collect();
+        let mut scores = self.absent.clone();
+        for token in tokens {
+            for (score, delta) in scores.iter_mut().zip(&self.present[token * classes..]) {
+                *score += delta;
+            }
+        }
+        scores
+    }
+}
+
+impl Classifier for BernoulliNaiveBayes {
+    fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        BernoulliNaiveBayes::scores(self, document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_absent_words_are_evidence() {
+        let documents: Vec<Document> = [("a", "x y"), ("a", "x"), ("b", "y"), ("b", "z")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes = ["a", "b"].iter().map(|c| c.to_string()).collect();
+        let vocab = ["x", "y", "z"].iter().map(|w| w.to_string()).collect();
+        let model = BernoulliNaiveBayes::new(&documents, classes, vocab);
+
+        // class a: p(x) = 3/4, p(y) = 2/4, p(z) = 1/4
+        let expected = 0.5f64.ln() + (0.75f64 * 0.5 * 0.75).ln();
+        let scores = model.scores(&Document {
+            class: String::new(),
+            text: "x x".into(),
+        });
+        assert!((scores[0] - expected).abs() < 1e-12);
+        // the absence of "x" alone is evidence against a
+        let empty = Document {
+            class: String::new(),
+            text: String::new(),
+        };
+        assert_eq!(model.predict(&empty), Some("b".into()));
+    }
+
+    #[test]
+    fn test_lidstone_smoothing() {
+        let documents: Vec<Document> = [("a", "x y"), ("a", "x"), ("b", "y")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes = ["a", "b"].iter().map(|c| c.to_string()).collect();
+        let vocab = ["x", "y"].iter().map(|w| w.to_string()).collect();
+        let mut model = BernoulliNaiveBayes::new(&documents, classes, vocab);
+        assert_eq!(model.log_presence(0, "x"), Some((3.0f64 / 4.0).ln()));
+
+        model.set_alpha(0.5);
+        assert_eq!(model.alpha(), 0.5);
+        // (documents with the word + alpha) / (documents + 2 alpha)
+        assert_eq!(model.log_presence(0, "x"), Some((2.5f64 / 3.0).ln()));
+        assert_eq!(model.log_presence(1, "x"), Some((0.5f64 / 2.0).ln()));
+        assert_eq!(model.log_presence(0, "z"), None);
+        let empty = Document {
+            class: String::new(),
+            text: String::new(),
+        };
+        let expected = (2.0f64 / 3.0).ln() + (0.5f64 / 3.0).ln() + (1.5f64 / 3.0).ln();
+        assert!((model.scores(&empty)[0] - expected).abs() < 1e-12);
+    }
+}
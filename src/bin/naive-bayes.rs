@@ -0,0 +1,74 @@
+//! Command line tools for saved models.
+//!
+//! ```text
+//! naive-bayes score <model> <documents> [output.csv]
+//! ```
+//!
+//! `score` writes the scores of every document as CSV, see
+//! [`naive_bayes::write_scores_csv`]. The model is read as JSON if its file
+//! name ends in `.json`, and in the binary format otherwise. Documents are
+//! read one per line, as `label<TAB>text` or as the text alone, and are
+//! identified by their line number.
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter},
+    process::ExitCode,
+};
+
+use naive_bayes::{write_scores_csv, Document, NaiveBayes};
+
+const USAGE: &str = "usage: naive-bayes score <model> <documents> [output.csv]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["score", model, documents] => score(model, documents, None),
+        ["score", model, documents, output] => score(model, documents, Some(output)),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("naive-bayes: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn score(model: &str, documents: &str, output: Option<&str>) -> Result<(), String> {
+    let model = if model.ends_with(".json") {
+        NaiveBayes::load_json(model).map_err(|e| format!("{}: {}", model, e))?
+    } else {
+        let bytes = fs::read(model).map_err(|e| format!("{}: {}", model, e))?;
+        NaiveBayes::from_bytes(&bytes).map_err(|e| format!("{}: {}", model, e))?
+    };
+    let text = fs::read_to_string(documents).map_err(|e| format!("{}: {}", documents, e))?;
+    let documents: Vec<Document> = text
+        .lines()
+        .map(|line| match line.split_once('\t') {
+            Some((class, text)) => Document {
+                class: class.to_owned(),
+                text: text.to_owned(),
+            },
+            None => Document {
+                class: String::new(),
+                text: line.to_owned(),
+            },
+        })
+        .collect();
+    let rows = documents.iter().enumerate().map(|(i, d)| (i + 1, d));
+
+    let written = match output {
+        Some(path) => {
+            let file = File::create(path).map_err(|e| format!("{}: {}", path, e))?;
+            write_scores_csv(&model, rows, BufWriter::new(file))
+        }
+        None => write_scores_csv(&model, rows, io::stdout().lock()),
+    };
+    written.map(|_| ()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,146 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    pipeline::{Stage, Transform},
+    Document, Word,
+};
+
+/// A pipeline stage removing boilerplate such as signatures, disclaimers and
+/// mailing list footers before counting. A run of [`Boilerplate::span`]
+/// words that recurs in many training documents is boilerplate, and so is
+/// every word covered by such runs.
+#[derive(Clone, Debug)]
+pub struct Boilerplate {
+    /// Length of the word runs compared across documents. Shorter spans also
+    /// catch common phrases.
+    pub span: usize,
+    /// Fraction of the training documents a run must occur in.
+    pub min_document_fraction: f64,
+    /// Also drop quoted reply lines, those starting with `>`.
+    pub strip_quoted: bool,
+}
+
+impl Default for Boilerplate {
+    fn default() -> Self {
+        Self {
+            span: 8,
+            min_document_fraction: 0.05,
+            strip_quoted: true,
+        }
+    }
+}
+
+impl Boilerplate {
+    fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        text.lines()
+            .filter(|line| !(self.strip_quoted && line.trim_start().starts_with('>')))
+            .flat_map(str::split_whitespace)
+            .collect()
+    }
+}
+
+impl Stage for Boilerplate {
+    fn fit(&self, documents: &[Document], _: &HashSet<Word>) -> Box<dyn Transform> {
+        let span = self.span.max(1);
+        let mut frequency: HashMap<&[&str], usize> = HashMap::new();
+        let words: Vec<Vec<&str>> = documents.iter().map(|d| self.words(&d.text)).collect();
+        for words in &words {
+            let runs: HashSet<&[&str]> = words.windows(span).collect();
+            for run in runs {
+                *frequency.entry(run).or_default() += 1;
+            }
+        }
+        // a run shared by two documents is a coincidence, not boilerplate
+        let min_documents =
+            ((self.min_document_fraction * documents.len() as f64).ceil() as usize).max(2);
+        let runs = frequency
+            .into_iter()
+            .filter(|(_, documents)| *documents >= min_documents)
+            .map(|(run, _)| run.join(" "))
+            .collect();
+        Box::new(BoilerplateFilter {
+            settings: Boilerplate {
+                span,
+                ..self.clone()
+            },
+            runs,
+        })
+    }
+}
+
+/// The boilerplate runs found in a training corpus, joined by spaces.
+struct BoilerplateFilter {
+    settings: Boilerplate,
+    runs: HashSet<String>,
+}
+
+impl BoilerplateFilter {
+    fn strip(&self, text: &str) -> String {
+        let words = self.settings.words(text);
+        let span = self.settings.span;
+        let mut boilerplate = vec![false; words.len()];
+        for start in 0..words.len().saturating_sub(span - 1) {
+            let run = &words[start..start + span];
+            if self.runs.contains(&run.join(" ")) {
+                boilerplate[start..start + span].fill(true);
+            }
+        }
+        words
+            .into_iter()
+            .zip(boilerplate)
+            .filter(|(_, boilerplate)| !boilerplate)
+            .map(|(word, _)| word)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Transform for BoilerplateFilter {
+    fn transform(&self, document: &Document) -> Document {
+        Document {
+            class: document.class.clone(),
+            text: self.strip(&document.text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boilerplate_is_removed() {
+        let footer = "Sent from my phone -- Confidential: intended for the addressee only";
+        let documents: Vec<Document> = [
+            "meeting moved to noon",
+            "cheap pills now",
+            "lunch on friday",
+            "quarterly report attached",
+        ]
+        .iter()
+        .map(|text| Document {
+            class: String::new(),
+            text: format!("{}\n> earlier message\n{}", text, footer),
+        })
+        .collect();
+        let stage = Boilerplate {
+            span: 4,
+            min_document_fraction: 0.5,
+            ..Default::default()
+        };
+        let filter = stage.fit(&documents, &HashSet::new());
+
+        assert_eq!(filter.transform(&documents[1]).text, "cheap pills now");
+        // a new document keeps everything but the footer
+        let unseen = Document {
+            class: String::new(),
+            text: format!("only for the addressee\n{}", footer),
+        };
+        assert_eq!(filter.transform(&unseen).text, "only for the addressee");
+        let kept = Document {
+            class: String::new(),
+            text: "Sent from my laptop".into(),
+        };
+        assert_eq!(filter.transform(&kept).text, "Sent from my laptop");
+    }
+}
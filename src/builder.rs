@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use crate::{
+    audit::AuditOptions,
+    counts::vocabulary_with,
+    locale::{CaseFolding, LocaleProfile},
+    pipeline::{FittedPipeline, Pipeline, Stage},
+    Class, ConflictPolicy, Document, LabelMap, SharedTokenizer, TextNormalization, Tokenizer,
+    TrainError, TrainOptions, TrainWarning, VocabularySelection, Word,
+};
+
+/// Collects training data and configuration, then fits a model with
+/// [`NaiveBayesBuilder::fit`]. Preprocessing is kept with the fitted model,
+/// so unseen documents are prepared exactly like the training documents.
+#[derive(Default)]
+pub struct NaiveBayesBuilder {
+    documents: Vec<Document>,
+    classes: Option<HashSet<Class>>,
+    vocab: Option<HashSet<Word>>,
+    options: TrainOptions,
+    pipeline: Pipeline,
+}
+
+impl NaiveBayesBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn documents(mut self, documents: &[Document]) -> Self {
+        self.documents.extend_from_slice(documents);
+        self
+    }
+
+    /// The classes to train, by default every label of the documents.
+    pub fn classes(mut self, classes: HashSet<Class>) -> Self {
+        self.classes = Some(classes);
+        self
+    }
+
+    /// The vocabulary, by default every word of the documents. It is
+    /// preprocessed like the documents.
+    pub fn vocabulary(mut self, vocab: HashSet<Word>) -> Self {
+        self.vocab = Some(vocab);
+        self
+    }
+
+    /// Prunes the vocabulary by document frequency, after preprocessing.
+    pub fn vocabulary_selection(mut self, selection: VocabularySelection) -> Self {
+        self.options.vocabulary_selection = selection;
+        self
+    }
+
+    /// Drops words occurring in fewer than `documents` documents.
+    pub fn min_df(mut self, documents: usize) -> Self {
+        self.options.vocabulary_selection.min_df = documents;
+        self
+    }
+
+    /// Drops words occurring in more than `fraction` of the documents.
+    pub fn max_df(mut self, fraction: f64) -> Self {
+        self.options.vocabulary_selection.max_df = Some(fraction);
+        self
+    }
+
+    /// Keeps at most the `words` occurring in the most documents.
+    pub fn max_vocabulary(mut self, words: usize) -> Self {
+        self.options.vocabulary_selection.max_size = Some(words);
+        self
+    }
+
+    /// Lidstone smoothing pseudo-count, 1 by default.
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.options.alpha = Some(alpha);
+        self
+    }
+
+    pub fn drop_empty_classes(mut self, drop: bool) -> Self {
+        self.options.drop_empty_classes = drop;
+        self
+    }
+
+    pub fn audit(mut self, audit: AuditOptions) -> Self {
+        self.options.audit = Some(audit);
+        self
+    }
+
+    pub fn conflicts(mut self, policy: ConflictPolicy) -> Self {
+        self.options.conflicts = policy;
+        self
+    }
+
+    /// Renames and merges the labels of the documents and the classes.
+    pub fn labels(mut self, labels: LabelMap) -> Self {
+        self.options.labels = labels;
+        self
+    }
+
+    /// Splits documents into words with `tokenizer` instead of at
+    /// whitespace, after every preprocessing stage. The fitted model keeps
+    /// it for prediction.
+    pub fn tokenizer(mut self, tokenizer: impl Tokenizer + 'static) -> Self {
+        self.options.tokenizer = SharedTokenizer::new(tokenizer);
+        self
+    }
+
+    /// Splits documents at Unicode word boundaries, the runs of scripts
+    /// written without spaces into character bigrams if `bigrams` is set.
+    #[cfg(feature = "unicode")]
+    pub fn unicode_words(self, bigrams: bool) -> Self {
+        self.tokenizer(crate::UnicodeTokenizer { bigrams })
+    }
+
+    /// Splits documents into runs of `min` to `max` characters instead of
+    /// words, such as 3 to 5 for short and noisy text.
+    pub fn char_ngrams(self, min: usize, max: usize) -> Self {
+        self.tokenizer(crate::CharNgramTokenizer { min, max })
+    }
+
+    /// Normalizes every token, at training and prediction time alike.
+    pub fn normalization(mut self, normalization: TextNormalization) -> Self {
+        self.options.normalization = normalization;
+        self
+    }
+
+    /// Drops `stopwords` from the documents and the vocabulary, after
+    /// normalization.
+    pub fn stopwords(mut self, stopwords: HashSet<Word>) -> Self {
+        self.options.stopwords = stopwords;
+        self
+    }
+
+    /// Reduces every token and vocabulary word to its stem in `language`,
+    /// after the stopwords are dropped.
+    #[cfg(feature = "stemming")]
+    pub fn stemming(mut self, language: crate::StemmerLanguage) -> Self {
+        self.options.stemming = Some(language);
+        self
+    }
+
+    /// Trains in `pool` instead of the global rayon pool.
+    #[cfg(feature = "parallel")]
+    pub fn thread_pool(mut self, pool: std::sync::Arc<rayon::ThreadPool>) -> Self {
+        self.options.concurrency = crate::Concurrency::Pool(pool);
+        self
+    }
+
+    /// Trains on at most `threads` threads.
+    #[cfg(feature = "parallel")]
+    pub fn max_threads(mut self, threads: usize) -> Self {
+        self.options.concurrency = crate::Concurrency::MaxThreads(threads);
+        self
+    }
+
+    /// Counts the runs of `min` to `max` consecutive words as words of their
+    /// own, unigrams only by default. N-grams of the vocabulary words are
+    /// added to the vocabulary.
+    pub fn ngram_range(mut self, min: usize, max: usize) -> Self {
+        self.options.ngram_range = Some((min, max));
+        self
+    }
+
+    /// Weights term occurrences by TF-IDF instead of raw counts, so that
+    /// words common to every document weigh less.
+    pub fn tf_idf(mut self, enabled: bool) -> Self {
+        self.options.tf_idf = enabled;
+        self
+    }
+
+    /// Lowercases every word, through the [`TextNormalization`] the model
+    /// keeps.
+    pub fn lowercase(mut self) -> Self {
+        self.options.normalization.case_folding = Some(CaseFolding::Root);
+        self
+    }
+
+    /// Folds case and tokenizes by the conventions of a locale, wrapping the
+    /// tokenizer set so far in [`crate::locale::Localized`]. The vocabulary is
+    /// folded the same way.
+    pub fn locale(mut self, profile: LocaleProfile) -> Self {
+        self.options.normalization.case_folding = Some(profile.folding);
+        self.options.tokenizer = SharedTokenizer::new(crate::locale::Localized {
+            tokenizer: self.options.tokenizer.clone(),
+            profile,
+        });
+        self
+    }
+
+    /// Adds a preprocessing stage, applied after the ones added before.
+    pub fn stage(mut self, stage: impl Stage + 'static) -> Self {
+        self.pipeline = self.pipeline.stage(stage);
+        self
+    }
+
+    pub fn fit(&self) -> Result<(FittedPipeline, Vec<TrainWarning>), TrainError> {
+        #[cfg(feature = "parallel")]
+        return self.options.concurrency.install(|| self.fit_here());
+        #[cfg(not(feature = "parallel"))]
+        self.fit_here()
+    }
+
+    /// [`NaiveBayesBuilder::fit`] in the current pool.
+    fn fit_here(&self) -> Result<(FittedPipeline, Vec<TrainWarning>), TrainError> {
+        let classes = match &self.classes {
+            Some(classes) => classes.clone(),
+            None => self.documents.iter().map(|d| d.class.clone()).collect(),
+        };
+        if classes.is_empty() {
+            return Err(TrainError::NoClasses);
+        }
+        let vocab = match &self.vocab {
+            Some(vocab) => vocab.clone(),
+            // preprocessed by training like a given vocabulary
+            None => vocabulary_with(&self.documents, &self.options.tokenizer),
+        };
+        self.pipeline
+            .try_fit(&self.documents, classes, vocab, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(class: &str, text: &str) -> Document {
+        Document {
+            class: class.into(),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn test_builder_fits_with_preprocessing() {
+        let documents = [
+            document("pos", "Good,movie"),
+            document("pos", "GOOD,plot"),
+            document("neg", "bad,Movie"),
+        ];
+        let (fitted, warnings) = NaiveBayesBuilder::new()
+            .documents(&documents)
+            .tokenizer(|text: &str| -> Vec<String> {
+                text.split(',').map(|w| w.to_owned()).collect()
+            })
+            .lowercase()
+            .alpha(0.5)
+            .fit()
+            .unwrap();
+
+        // "bad" and "plot" occur once
+        assert_eq!(warnings.last(), Some(&TrainWarning::SingletonTokens(2)));
+        assert_eq!(fitted.model().alpha(), 0.5);
+        assert_eq!(fitted.model().classes(), ["neg", "pos"]);
+        // the vocabulary went through the same stages as the documents
+        let mut vocab: Vec<&Word> = fitted.model().vocab.keys().collect();
+        vocab.sort_unstable();
+        assert_eq!(vocab, ["bad", "good", "movie", "plot"]);
+        assert_eq!(
+            fitted.predict(&document("", "GOOD,Plot")),
+            Some("pos".into())
+        );
+        // lowercasing is part of the model, not of a stage it drops
+        let model = fitted.into_model();
+        assert_eq!(
+            model.predict(&document("", "GOOD,Plot")),
+            Some("pos".into())
+        );
+
+        let (fitted, _) = NaiveBayesBuilder::new()
+            .documents(&[document("hot", "SICAK ILIK"), document("cold", "soğuk")])
+            .locale(LocaleProfile::turkish())
+            .fit()
+            .unwrap();
+        let model = fitted.into_model();
+        let rebuilt = model.tokenizer().spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("ILIK, Soğuk!"), ["ılık", "soğuk"]);
+        assert_eq!(model.predict(&document("", "Ilık")), Some("hot".into()));
+
+        assert_eq!(
+            NaiveBayesBuilder::new().fit().err(),
+            Some(TrainError::NoClasses)
+        );
+        let empty = NaiveBayesBuilder::new()
+            .documents(&documents)
+            .vocabulary(HashSet::new())
+            .fit();
+        assert_eq!(empty.err(), Some(TrainError::EmptyVocabulary));
+    }
+}
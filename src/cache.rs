@@ -0,0 +1,149 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use crate::{Class, Classifier, Document};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    pub fn hit_rate(&self) -> f64 {
+        self.hits as f64 / (self.hits + self.misses).max(1) as f64
+    }
+}
+
+/// A classifier whose scores are memoized in a least-recently-used cache,
+/// keyed by the exact document text, since the inner classifier may split
+/// it in any way. Like every [`Classifier`], it names its classes, so a
+/// model with typed labels is cached before [`crate::NaiveBayes::with_labels`].
+pub struct CachedClassifier<C> {
+    inner: C,
+    capacity: usize,
+    state: Mutex<Lru>,
+}
+
+#[derive(Default)]
+struct Lru {
+    /// Scores and last use of every cached text.
+    entries: HashMap<String, (Vec<f64>, u64)>,
+    /// Cached texts by last use, oldest first.
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl<C: Classifier> CachedClassifier<C> {
+    pub fn new(inner: C, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            state: Mutex::new(Lru::default()),
+        }
+    }
+
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.state.lock().unwrap().stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        *self.state.lock().unwrap() = Lru::default();
+    }
+}
+
+impl<C: Classifier> Classifier for CachedClassifier<C> {
+    fn classes(&self) -> &[Class] {
+        self.inner.classes()
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        let key = document.text.clone();
+        {
+            let mut state = self.state.lock().unwrap();
+            state.clock += 1;
+            let now = state.clock;
+            if let Some((scores, used)) = state.entries.get_mut(&key) {
+                let (scores, previous) = (scores.clone(), std::mem::replace(used, now));
+                state.recency.remove(&previous);
+                state.recency.insert(now, key);
+                state.stats.hits += 1;
+                return scores;
+            }
+            state.stats.misses += 1;
+        }
+
+        // score without holding the lock, so misses are computed concurrently
+        let scores = self.inner.scores(document);
+        if self.capacity == 0 {
+            return scores;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let now = state.clock;
+        if let Some((_, previous)) = state.entries.insert(key.clone(), (scores.clone(), now)) {
+            state.recency.remove(&previous);
+        }
+        state.recency.insert(now, key);
+        while state.entries.len() > self.capacity {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    #[test]
+    fn test_cache_hits_and_evicts_least_recent() {
+        let model = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap", "meeting"],
+            vec![-0.3, -1.4],
+            vec![vec![(0, -6.0), (1, -1.2)], vec![(0, -1.1), (1, -5.5)]],
+            vec![-8.0, -8.0],
+        );
+        let document = |text: &str| Document {
+            class: String::new(),
+            text: text.into(),
+        };
+        let cached = CachedClassifier::new(model, 2);
+
+        let expected = Classifier::scores(cached.inner(), &document("cheap meeting"));
+        assert_eq!(cached.scores(&document("cheap meeting")), expected);
+        assert_eq!(cached.scores(&document("cheap meeting")), expected);
+        // texts that only differ in whitespace are still cached apart
+        assert_eq!(cached.scores(&document(" cheap\tmeeting ")), expected);
+        assert_eq!(cached.predict(&document("cheap")), Some("spam".into()));
+        assert_eq!(cached.stats(), CacheStats { hits: 1, misses: 3 });
+
+        // after the hit, "cheap" is the least recently used entry
+        cached.scores(&document(" cheap\tmeeting "));
+        cached.scores(&document("meeting"));
+        cached.scores(&document("cheap"));
+        assert_eq!(cached.len(), 2);
+        assert_eq!(cached.stats(), CacheStats { hits: 2, misses: 5 });
+        assert!((cached.stats().hit_rate() - 2.0 / 7.0).abs() < 1e-12);
+    }
+}
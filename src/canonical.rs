@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use crate::{
+    pipeline::{Stage, Transform},
+    Document, Word,
+};
+
+/// Replaces every token matched by `matches` with `replacement`.
+#[derive(Clone)]
+pub struct Rule {
+    pub matches: fn(&str) -> bool,
+    pub replacement: String,
+}
+
+impl Rule {
+    pub fn new(matches: fn(&str) -> bool, replacement: &str) -> Self {
+        Self {
+            matches,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    pub fn url() -> Self {
+        Self::new(is_url, "<URL>")
+    }
+
+    pub fn email() -> Self {
+        Self::new(is_email, "<EMAIL>")
+    }
+
+    pub fn number() -> Self {
+        Self::new(is_number, "<NUM>")
+    }
+
+    pub fn mention() -> Self {
+        Self::new(is_mention, "<USER>")
+    }
+
+    pub fn hashtag() -> Self {
+        Self::new(is_hashtag, "<HASHTAG>")
+    }
+}
+
+/// Maps high-cardinality token families such as URLs or numbers to one
+/// placeholder token each. Rules are tried in order and the first match
+/// wins; tokens no rule matches are kept as they are.
+#[derive(Clone)]
+pub struct Canonicalize {
+    pub rules: Vec<Rule>,
+}
+
+impl Default for Canonicalize {
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                Rule::url(),
+                Rule::email(),
+                Rule::mention(),
+                Rule::hashtag(),
+                Rule::number(),
+            ],
+        }
+    }
+}
+
+impl Canonicalize {
+    pub fn token<'a>(&'a self, token: &'a str) -> &'a str {
+        self.rules
+            .iter()
+            .find(|rule| (rule.matches)(token))
+            .map_or(token, |rule| rule.replacement.as_str())
+    }
+
+    pub fn text(&self, text: &str) -> String {
+        text.split_whitespace()
+            .map(|token| self.token(token))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl Stage for Canonicalize {
+    fn fit(&self, _: &[Document], _: &HashSet<Word>) -> Box<dyn Transform> {
+        Box::new(self.clone())
+    }
+}
+
+impl Transform for Canonicalize {
+    fn transform(&self, document: &Document) -> Document {
+        Document {
+            class: document.class.clone(),
+            text: self.text(&document.text),
+        }
+    }
+
+    fn transform_vocab(&self, vocab: HashSet<Word>) -> HashSet<Word> {
+        vocab
+            .iter()
+            .map(|word| self.token(word).to_owned())
+            .collect()
+    }
+}
+
+fn trim_punctuation(token: &str) -> &str {
+    token.trim_matches(|c: char| {
+        matches!(
+            c,
+            '.' | ',' | ';' | ':' | '!' | '?' | '(' | ')' | '"' | '\''
+        )
+    })
+}
+
+pub fn is_url(token: &str) -> bool {
+    let token = trim_punctuation(token).to_ascii_lowercase();
+    token.contains("://") || token.starts_with("www.")
+}
+
+pub fn is_email(token: &str) -> bool {
+    let token = trim_punctuation(token);
+    match token.split_once('@') {
+        Some((user, domain)) => !user.is_empty() && domain.contains('.') && !domain.ends_with('.'),
+        None => false,
+    }
+}
+
+pub fn is_number(token: &str) -> bool {
+    let token = trim_punctuation(token);
+    let token = token.strip_prefix(['-', '+']).unwrap_or(token);
+    token.chars().any(|c| c.is_ascii_digit())
+        && token
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+}
+
+pub fn is_mention(token: &str) -> bool {
+    trim_punctuation(token)
+        .strip_prefix('@')
+        .is_some_and(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+}
+
+pub fn is_hashtag(token: &str) -> bool {
+    trim_punctuation(token)
+        .strip_prefix('#')
+        .is_some_and(|tag| !tag.is_empty() && tag.chars().all(|c| c.is_alphanumeric() || c == '_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_rules() {
+        let canonicalize = Canonicalize::default();
+        assert_eq!(
+            canonicalize
+                .text("@bob see https://x.io/a?b=1, mail me@x.org about 3,000 #deals at 9.30!"),
+            "<USER> see <URL> mail <EMAIL> about <NUM> <HASHTAG> at <NUM>"
+        );
+
+        let numbers_only = Canonicalize {
+            rules: vec![Rule::new(is_number, "N")],
+        };
+        assert_eq!(numbers_only.text("@bob owes 42"), "@bob owes N");
+
+        let vocab = ["10", "20", "cat"].iter().map(|w| w.to_string()).collect();
+        let mut vocab: Vec<Word> = numbers_only.transform_vocab(vocab).into_iter().collect();
+        vocab.sort_unstable();
+        assert_eq!(vocab, ["N", "cat"]);
+    }
+}
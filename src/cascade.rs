@@ -0,0 +1,157 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{
+    classifier::{argmax, softmax},
+    Class, Classifier, Document,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CascadeStats {
+    /// Documents answered by the first stage.
+    pub first_stage: u64,
+    /// Documents escalated to the second stage.
+    pub escalated: u64,
+}
+
+impl CascadeStats {
+    pub fn escalation_rate(&self) -> f64 {
+        self.escalated as f64 / (self.first_stage + self.escalated).max(1) as f64
+    }
+}
+
+/// Two classifiers in sequence: a cheap first stage, such as a
+/// [`crate::bernoulli::BernoulliNaiveBayes`] over a small vocabulary,
+/// answers the documents it is confident about and escalates the rest to a
+/// larger second stage. The cascade knows the classes of the second stage;
+/// the first stage should know a subset of them.
+pub struct Cascade<F, S> {
+    first: F,
+    second: S,
+    /// Posterior probability of its best class the first stage needs to
+    /// answer.
+    pub threshold: f64,
+    /// Position of every first stage class among the second stage classes.
+    positions: Vec<Option<usize>>,
+    first_stage: AtomicU64,
+    escalated: AtomicU64,
+}
+
+impl<F: Classifier, S: Classifier> Cascade<F, S> {
+    pub fn new(first: F, second: S, threshold: f64) -> Self {
+        let positions = first
+            .classes()
+            .iter()
+            .map(|class| second.classes().iter().position(|other| other == class))
+            .collect();
+        Self {
+            first,
+            second,
+            threshold,
+            positions,
+            first_stage: AtomicU64::new(0),
+            escalated: AtomicU64::new(0),
+        }
+    }
+
+    pub fn first(&self) -> &F {
+        &self.first
+    }
+
+    pub fn second(&self) -> &S {
+        &self.second
+    }
+
+    pub fn stats(&self) -> CascadeStats {
+        CascadeStats {
+            first_stage: self.first_stage.load(Ordering::Relaxed),
+            escalated: self.escalated.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset_stats(&self) {
+        self.first_stage.store(0, Ordering::Relaxed);
+        self.escalated.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<F: Classifier, S: Classifier> Classifier for Cascade<F, S> {
+    fn classes(&self) -> &[Class] {
+        self.second.classes()
+    }
+
+    /// Scores of the first stage when it is confident, mapped to the second
+    /// stage classes with `-inf` for the ones it does not know; otherwise
+    /// scores of the second stage.
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        let scores = self.first.scores(document);
+        let posterior = softmax(&scores);
+        if let Some(best) = argmax(&posterior) {
+            if posterior[best] >= self.threshold && self.positions[best].is_some() {
+                self.first_stage.fetch_add(1, Ordering::Relaxed);
+                let mut mapped = vec![f64::NEG_INFINITY; self.second.classes().len()];
+                for (class, position) in self.positions.iter().enumerate() {
+                    if let Some(position) = position {
+                        mapped[*position] = scores[class];
+                    }
+                }
+                return mapped;
+            }
+        }
+        self.escalated.fetch_add(1, Ordering::Relaxed);
+        self.second.scores(document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    #[test]
+    fn test_cascade_escalates_uncertain_documents() {
+        // the first stage only knows "cheap", and is confident about it
+        let first = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap"],
+            vec![-0.7, -0.7],
+            vec![vec![(0, -9.0)], vec![(0, -0.1)]],
+            vec![-3.0, -3.0],
+        );
+        let second = NaiveBayes::from_parts(
+            &["ham", "other", "spam"],
+            &["cheap", "meeting"],
+            vec![-1.1, -1.1, -1.1],
+            vec![
+                vec![(0, -6.0), (1, -0.2)],
+                vec![(0, -3.0), (1, -3.0)],
+                vec![(0, -0.2), (1, -6.0)],
+            ],
+            vec![-9.0, -9.0, -9.0],
+        );
+        let document = |text: &str| Document {
+            class: String::new(),
+            text: text.into(),
+        };
+        let cascade = Cascade::new(first, second, 0.9);
+        assert_eq!(cascade.classes(), ["ham", "other", "spam"]);
+
+        let scores = cascade.scores(&document("cheap cheap"));
+        assert_eq!(scores[1], f64::NEG_INFINITY);
+        assert_eq!(
+            scores[2],
+            Classifier::scores(cascade.first(), &document("cheap cheap"))[1]
+        );
+        // the first stage cannot tell a meeting from anything else
+        assert_eq!(cascade.predict(&document("meeting")), Some("ham".into()));
+        assert_eq!(
+            cascade.stats(),
+            CascadeStats {
+                first_stage: 1,
+                escalated: 1
+            }
+        );
+        assert_eq!(cascade.stats().escalation_rate(), 0.5);
+        cascade.reset_stats();
+        assert_eq!(cascade.stats(), CascadeStats::default());
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    classifier::{argmax, softmax},
+    Class,
+};
+
+/// A labelled row of categorical values, one per column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub class: Class,
+    pub values: Vec<String>,
+}
+
+/// Why a categorical model could not be fitted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CategoricalError {
+    /// No training records at all.
+    Empty,
+    /// Record `record` has a different number of values than the first.
+    DimensionMismatch {
+        record: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// A smoothing alpha that is negative or not finite, or a number of
+    /// alphas other than one per column.
+    InvalidAlpha,
+}
+
+impl fmt::Display for CategoricalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoricalError::Empty => write!(f, "there are no training records"),
+            CategoricalError::DimensionMismatch {
+                record,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "record {} has {} values instead of {}",
+                record, actual, expected
+            ),
+            CategoricalError::InvalidAlpha => write!(
+                f,
+                "every column needs a smoothing alpha that is finite and at least 0"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CategoricalError {}
+
+/// Counts and smoothed likelihoods of the categories of one column.
+#[derive(Clone, Debug)]
+struct Column {
+    categories: HashMap<String, usize>,
+    /// Records of every category and class, by `category * classes + class`.
+    counts: Vec<usize>,
+    alpha: f64,
+    log_likelihood: Vec<f64>,
+}
+
+/// Naive Bayes over columns of discrete values, such as the outlook and
+/// wind of the classic play-tennis data, each column with its own Lidstone
+/// smoothing.
+#[derive(Clone, Debug)]
+pub struct CategoricalNaiveBayes {
+    classes: Vec<Class>,
+    class_records: Vec<usize>,
+    log_prior: Vec<f64>,
+    columns: Vec<Column>,
+}
+
+impl CategoricalNaiveBayes {
+    /// Fits with add-one smoothing of every column.
+    pub fn fit(records: &[Record]) -> Result<Self, CategoricalError> {
+        let columns = records.first().ok_or(CategoricalError::Empty)?.values.len();
+        Self::fit_with(records, &vec![1.0; columns])
+    }
+
+    /// Fits every class of `records`, smoothing column `i` with pseudo-count
+    /// `alphas[i]`.
+    pub fn fit_with(records: &[Record], alphas: &[f64]) -> Result<Self, CategoricalError> {
+        let dimensions = records.first().ok_or(CategoricalError::Empty)?.values.len();
+        for (i, record) in records.iter().enumerate() {
+            if record.values.len() != dimensions {
+                return Err(CategoricalError::DimensionMismatch {
+                    record: i,
+                    expected: dimensions,
+                    actual: record.values.len(),
+                });
+            }
+        }
+        if alphas.len() != dimensions || !alphas.iter().all(|a| a.is_finite() && *a >= 0.0) {
+            return Err(CategoricalError::InvalidAlpha);
+        }
+
+        let mut classes: Vec<Class> = records.iter().map(|r| r.class.clone()).collect();
+        classes.sort_unstable();
+        classes.dedup();
+        let class_count = classes.len();
+        let mut class_records = vec![0usize; class_count];
+        let mut columns: Vec<Column> = alphas
+            .iter()
+            .map(|alpha| Column {
+                categories: HashMap::new(),
+                counts: Vec::new(),
+                alpha: *alpha,
+                log_likelihood: Vec::new(),
+            })
+            .collect();
+        for record in records {
+            let class = classes.binary_search(&record.class).unwrap();
+            class_records[class] += 1;
+            for (column, value) in columns.iter_mut().zip(&record.values) {
+                let next = column.categories.len();
+                let category = *column.categories.entry(value.clone()).or_insert(next);
+                if category == next {
+                    column.counts.resize((next + 1) * class_count, 0);
+                }
+                column.counts[category * class_count + class] += 1;
+            }
+        }
+
+        let log_prior = class_records
+            .iter()
+            .map(|n| (*n as f64 / records.len() as f64).ln())
+            .collect();
+        let mut model = Self {
+            classes,
+            class_records,
+            log_prior,
+            columns,
+        };
+        for column in 0..dimensions {
+            model.estimate(column);
+        }
+        Ok(model)
+    }
+
+    fn estimate(&mut self, column: usize) {
+        let class_count = self.classes.len();
+        let class_records = &self.class_records;
+        let column = &mut self.columns[column];
+        let categories = column.categories.len() as f64;
+        let alpha = column.alpha;
+        column.log_likelihood = column
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(cell, count)| {
+                let records = class_records[cell % class_count] as f64;
+                ((*count as f64 + alpha) / (records + alpha * categories)).ln()
+            })
+            .collect();
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    pub fn log_prior(&self) -> &[f64] {
+        &self.log_prior
+    }
+
+    pub fn alpha(&self, column: usize) -> f64 {
+        self.columns[column].alpha
+    }
+
+    /// Switches column `column` to Lidstone smoothing with pseudo-count
+    /// `alpha` and re-estimates it.
+    ///
+    /// # Panics
+    /// If `alpha` is negative or not finite.
+    pub fn set_alpha(&mut self, column: usize, alpha: f64) {
+        assert!(
+            alpha.is_finite() && alpha >= 0.0,
+            "invalid smoothing alpha {}",
+            alpha
+        );
+        self.columns[column].alpha = alpha;
+        self.estimate(column);
+    }
+
+    /// Log-probability that a record of the class at `class` has `value` in
+    /// column `column`, if the value was seen in training.
+    pub fn log_likelihood(&self, column: usize, value: &str, class: usize) -> Option<f64> {
+        let column = &self.columns[column];
+        let category = *column.categories.get(value)?;
+        Some(column.log_likelihood[category * self.classes.len() + class])
+    }
+
+    /// Log joint probability of every class and `values`, in the order of
+    /// [`CategoricalNaiveBayes::classes`]. Values not seen in training are
+    /// ignored.
+    ///
+    /// # Panics
+    /// If `values` has a different length than the training records.
+    pub fn scores<S: AsRef<str>>(&self, values: &[S]) -> Vec<f64> {
+        assert_eq!(values.len(), self.columns.len(), "wrong number of values");
+        let classes = self.classes.len();
+        let mut scores = self.log_prior.clone();
+        for (column, value) in self.columns.iter().zip(values) {
+            let Some(&category) = column.categories.get(value.as_ref()) else {
+                continue;
+            };
+            for (score, log_likelihood) in scores
+                .iter_mut()
+                .zip(&column.log_likelihood[category * classes..])
+            {
+                *score += log_likelihood;
+            }
+        }
+        scores
+    }
+
+    /// Posterior probability of every class given `values`.
+    pub fn probabilities<S: AsRef<str>>(&self, values: &[S]) -> Vec<f64> {
+        softmax(&self.scores(values))
+    }
+
+    /// The most probable class of `values`, ties going to the one listed
+    /// first.
+    pub fn predict<S: AsRef<str>>(&self, values: &[S]) -> Option<Class> {
+        let best = argmax(&self.scores(values))?;
+        Some(self.classes[best].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_tennis() {
+        let records: Vec<Record> = [
+            "sunny hot high weak no",
+            "sunny hot high strong no",
+            "overcast hot high weak yes",
+            "rain mild high weak yes",
+            "rain cool normal weak yes",
+            "rain cool normal strong no",
+            "overcast cool normal strong yes",
+            "sunny mild high weak no",
+            "sunny cool normal weak yes",
+            "rain mild normal weak yes",
+            "sunny mild normal strong yes",
+            "overcast mild high strong yes",
+            "overcast hot normal weak yes",
+            "rain mild high strong no",
+        ]
+        .iter()
+        .map(|row| {
+            let mut values: Vec<String> = row.split(' ').map(String::from).collect();
+            let class = values.pop().unwrap();
+            Record { class, values }
+        })
+        .collect();
+        let mut model = CategoricalNaiveBayes::fit(&records).unwrap();
+        assert_eq!(model.classes(), ["no", "yes"]);
+        // no overcast day among the 5 without tennis, of 3 outlooks
+        assert_eq!(model.log_likelihood(0, "overcast", 0), Some(0.125f64.ln()));
+        assert_eq!(
+            model.predict(&["sunny", "cool", "high", "strong"]),
+            Some("no".into())
+        );
+        assert_eq!(
+            model.predict(&["overcast", "mild", "normal", "weak"]),
+            Some("yes".into())
+        );
+        // unseen values carry no evidence
+        assert_eq!(model.scores(&["fog", "", "", ""]), model.log_prior());
+
+        model.set_alpha(0, 0.0);
+        assert_eq!(
+            model.log_likelihood(0, "overcast", 0),
+            Some(f64::NEG_INFINITY)
+        );
+        assert_eq!(model.alpha(1), 1.0);
+        assert_eq!(
+            CategoricalNaiveBayes::fit_with(&records, &[1.0]).err(),
+            Some(CategoricalError::InvalidAlpha)
+        );
+    }
+}
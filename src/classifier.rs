@@ -0,0 +1,50 @@
+use crate::{Class, Document, NaiveBayes, PredictOptions, Scratch};
+
+/// Anything that scores documents against a fixed set of classes.
+pub trait Classifier: Send + Sync {
+    /// Classes in the order used by [`Classifier::scores`].
+    fn classes(&self) -> &[Class];
+
+    /// Log score of every class; higher is more likely.
+    fn scores(&self, document: &Document) -> Vec<f64>;
+
+    /// The best scoring class, ties going to the one listed first.
+    fn predict(&self, document: &Document) -> Option<Class> {
+        let best = argmax(&self.scores(document))?;
+        Some(self.classes()[best].clone())
+    }
+}
+
+/// Index of the highest score, the first on ties.
+pub(crate) fn argmax(scores: &[f64]) -> Option<usize> {
+    (0..scores.len()).reduce(|best, class| {
+        if scores[class].total_cmp(&scores[best]).is_gt() {
+            class
+        } else {
+            best
+        }
+    })
+}
+
+impl Classifier for NaiveBayes {
+    fn classes(&self) -> &[Class] {
+        NaiveBayes::classes(self)
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        let mut scratch = Scratch::default();
+        self.scores_into(document, &PredictOptions::default(), &mut scratch)
+            .to_vec()
+    }
+}
+
+/// Posterior probabilities from log scores. Uniform when no score is finite.
+pub(crate) fn softmax(scores: &[f64]) -> Vec<f64> {
+    let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if !max.is_finite() {
+        return vec![1.0 / scores.len() as f64; scores.len()];
+    }
+    let exp: Vec<f64> = scores.iter().map(|s| (s - max).exp()).collect();
+    let sum: f64 = exp.iter().sum();
+    exp.into_iter().map(|e| e / sum).collect()
+}
@@ -0,0 +1,132 @@
+use std::hash::Hash;
+
+use crate::{likelihood::Row, NaiveBayes};
+
+/// Bounds on the influence of single tokens, applied whenever the model
+/// parameters are estimated.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clipping {
+    /// Lowest probability any token may have under any class.
+    pub min_probability: Option<f64>,
+    /// Largest log-ratio by which one token may favour a class over another,
+    /// so a single rare token cannot decide a classification on its own.
+    pub max_log_ratio: Option<f64>,
+}
+
+impl Clipping {
+    /// Clipped copies of the estimated rows and defaults of every class.
+    pub(crate) fn apply(
+        &self,
+        rows: Vec<Row>,
+        mut defaults: Vec<f64>,
+        vocab_size: usize,
+    ) -> (Vec<Row>, Vec<f64>) {
+        if *self == Self::default() {
+            return (rows, defaults);
+        }
+        let floor = self.min_probability.map_or(f64::NEG_INFINITY, f64::ln);
+        for default in defaults.iter_mut() {
+            *default = default.max(floor);
+        }
+
+        // dense view, token-major like the model's own tables
+        let classes = rows.len();
+        let mut values: Vec<f64> = (0..vocab_size * classes)
+            .map(|cell| defaults[cell % classes])
+            .collect();
+        for (class, row) in rows.iter().enumerate() {
+            for (token, value) in row {
+                values[token * classes + class] = value.max(floor);
+            }
+        }
+        if let Some(max_log_ratio) = self.max_log_ratio {
+            for token_values in values.chunks_mut(classes.max(1)) {
+                let best = token_values
+                    .iter()
+                    .copied()
+                    .fold(f64::NEG_INFINITY, f64::max);
+                for value in token_values {
+                    *value = value.max(best - max_log_ratio);
+                }
+            }
+        }
+
+        let rows = (0..classes)
+            .map(|class| {
+                (0..vocab_size)
+                    .map(|token| (token, values[token * classes + class]))
+                    .filter(|(token, value)| {
+                        rows[class].binary_search_by_key(token, |(t, _)| *t).is_ok()
+                            || *value != defaults[class]
+                    })
+                    .collect()
+            })
+            .collect();
+        (rows, defaults)
+    }
+}
+
+impl<L: Clone + Eq + Hash, F: Eq + Hash> NaiveBayes<L, F> {
+    pub fn clipping(&self) -> &Clipping {
+        &self.clipping
+    }
+
+    /// Sets the bounds kept by every later estimation and re-estimates the
+    /// model with them.
+    pub fn set_clipping(&mut self, clipping: Clipping) {
+        self.clipping = clipping;
+        self.estimate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Classifier, Document};
+
+    #[test]
+    fn test_rare_token_influence_is_bounded() {
+        let mut documents: Vec<Document> = (0..20)
+            .map(|i| Document {
+                class: if i % 2 == 0 { "ham" } else { "spam" }.into(),
+                text: if i % 2 == 0 {
+                    "hello friend"
+                } else {
+                    "buy now"
+                }
+                .into(),
+            })
+            .collect();
+        documents.push(Document {
+            class: "ham".into(),
+            text: "zzyzx zzyzx zzyzx zzyzx zzyzx zzyzx".into(),
+        });
+        let classes = ["ham", "spam"].iter().map(|c| c.to_string()).collect();
+        let vocab = crate::vocabulary(&documents);
+        let mut model = NaiveBayes::new(&documents, classes, vocab);
+        let rare = Document {
+            class: String::new(),
+            text: "zzyzx".into(),
+        };
+        let evidence = |model: &NaiveBayes| {
+            let scores = Classifier::scores(model, &rare);
+            (scores[0] - model.log_prior[0]) - (scores[1] - model.log_prior[1])
+        };
+        assert!(evidence(&model) > 1.5);
+
+        model.set_clipping(Clipping {
+            min_probability: Some(0.01),
+            max_log_ratio: Some(0.5),
+        });
+        assert!(evidence(&model) <= 0.5 + 1e-12);
+        for token in 0..model.vocab.len() {
+            for class in 0..2 {
+                assert!(model.likelihood.get(class, token) >= 0.01f64.ln());
+            }
+        }
+        // the bounds survive re-estimation
+        model.partial_fit(&documents[..2]);
+        assert_eq!(model.clipping().max_log_ratio, Some(0.5));
+    }
+}
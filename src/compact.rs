@@ -0,0 +1,81 @@
+use crate::NaiveBayes;
+
+/// Heap usage of a model before and after [`NaiveBayes::compact`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Compaction {
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+}
+
+impl Compaction {
+    pub fn reclaimed(&self) -> usize {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+impl NaiveBayes {
+    /// Rebuilds the internal tables into their smallest form, dropping
+    /// stored likelihoods equal to the class default, zero counts and spare
+    /// capacity left behind by incremental updates. Scores are unchanged.
+    pub fn compact(&mut self) -> Compaction {
+        let bytes_before = self.heap_bytes();
+        self.likelihood.compact();
+        self.counts.compact();
+        self.vocab.shrink_to_fit();
+        self.classes.shrink_to_fit();
+        self.log_prior.shrink_to_fit();
+        self.sorted_document_frequency.shrink_to_fit();
+        Compaction {
+            bytes_before,
+            bytes_after: self.heap_bytes(),
+        }
+    }
+
+    /// Approximate bytes the model allocates on the heap, training summary
+    /// excluded.
+    pub fn heap_bytes(&self) -> usize {
+        let vocab: usize = self.vocab.keys().map(|word| word.capacity()).sum::<usize>()
+            + self.vocab.capacity() * (size_of::<(String, usize)>() + 1);
+        let classes: usize = self
+            .classes
+            .iter()
+            .map(|class| class.capacity())
+            .sum::<usize>()
+            + self.classes.capacity() * size_of::<String>();
+        vocab
+            + classes
+            + self.counts.heap_bytes()
+            + self.likelihood.heap_bytes()
+            + self.log_prior.capacity() * size_of::<f64>()
+            + self.sorted_document_frequency.capacity() * size_of::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Classifier, Document};
+
+    #[test]
+    fn test_compact_keeps_scores() {
+        // every word occurs in a single class, so the table is sparse
+        let documents: Vec<Document> = (0..300)
+            .map(|i| Document {
+                class: format!("c{}", i % 30),
+                text: format!("w{} x{}", i, i % 60),
+            })
+            .collect();
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let mut model = NaiveBayes::new(&documents, classes, crate::vocabulary(&documents));
+        // snaps the stored likelihoods of the words seen once to the default
+        model.quantize_likelihoods(0.4);
+        let expected: Vec<Vec<f64>> = documents[..20].iter().map(|d| model.scores(d)).collect();
+
+        let compaction = model.compact();
+        assert!(compaction.reclaimed() > 0);
+        assert_eq!(compaction.bytes_after, model.heap_bytes());
+        let scores: Vec<Vec<f64>> = documents[..20].iter().map(|d| model.scores(d)).collect();
+        assert_eq!(scores, expected);
+        assert_eq!(model.compact().reclaimed(), 0);
+    }
+}
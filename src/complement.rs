@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    counts::Counts, tf_idf::document_weights, Class, Classifier, Document, NaiveBayes,
+    SharedTokenizer, Tokenizer, Word,
+};
+
+/// Complement naive Bayes (Rennie et al., 2003): every class is scored by
+/// how unlike the documents of all other classes a document is, which are
+/// many even for a rare class. The prior is ignored, so skewed corpora do
+/// not bias predictions toward the majority class.
+#[derive(Clone)]
+pub struct ComplementNaiveBayes {
+    vocab: HashMap<Word, usize>,
+    classes: Vec<Class>,
+    counts: Counts,
+    tokenizer: SharedTokenizer,
+    alpha: f64,
+    weight_normalized: bool,
+    /// Row `token` holds the weight of one occurrence of the token for every
+    /// class.
+    weights: Vec<f64>,
+    /// Idf of every token of a TF-IDF weighted model.
+    idf: Option<Vec<f64>>,
+}
+
+impl ComplementNaiveBayes {
+    pub fn new(documents: &[Document], classes: HashSet<Class>, vocab: HashSet<Word>) -> Self {
+        let mut words: Vec<Word> = vocab.into_iter().collect();
+        words.sort_unstable();
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+        let tokenizer = SharedTokenizer::default();
+        let counts = Counts::new(documents, &classes, &vocab, &tokenizer);
+        Self::from_counts(vocab, classes, counts, tokenizer, None)
+    }
+
+    /// The complement model of the counts of `model`, with its vocabulary,
+    /// tokenizer and smoothing, so that it is preprocessed and weighted like
+    /// any model of [`crate::NaiveBayesBuilder`]. The documents of TF-IDF
+    /// weighted models are weighted by the idf of `model` too.
+    pub fn from_model(model: &NaiveBayes) -> Self {
+        let mut complement = Self::from_counts(
+            model.vocab.clone(),
+            model.classes.clone(),
+            model.counts.clone(),
+            model.tokenizer.clone(),
+            model.idf.clone(),
+        );
+        complement.set_alpha(model.alpha);
+        complement
+    }
+
+    fn from_counts(
+        vocab: HashMap<Word, usize>,
+        classes: Vec<Class>,
+        counts: Counts,
+        tokenizer: SharedTokenizer,
+        idf: Option<Vec<f64>>,
+    ) -> Self {
+        let mut model = Self {
+            vocab,
+            classes,
+            counts,
+            tokenizer,
+            alpha: 1.0,
+            weight_normalized: false,
+            weights: Vec::new(),
+            idf,
+        };
+        model.estimate();
+        model
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Switches to Lidstone smoothing of the complement counts with
+    /// pseudo-count `alpha`, 1 being add-one smoothing, and re-estimates the
+    /// model.
+    ///
+    /// # Panics
+    /// If `alpha` is negative or not finite.
+    pub fn set_alpha(&mut self, alpha: f64) {
+        assert!(
+            alpha.is_finite() && alpha >= 0.0,
+            "invalid smoothing alpha {}",
+            alpha
+        );
+        self.alpha = alpha;
+        self.estimate();
+    }
+
+    /// Scales the weights of every class to sum to one, so that classes
+    /// with long documents do not dominate.
+    pub fn set_weight_normalization(&mut self, enabled: bool) {
+        self.weight_normalized = enabled;
+        self.estimate();
+    }
+
+    /// Adds `documents` to the counts of the model and re-estimates it. The
+    /// idf of a TF-IDF weighted model is kept.
+    pub fn partial_fit(&mut self, documents: &[Document]) {
+        let counts = match &self.idf {
+            Some(idf) => {
+                Counts::new_tf_idf(documents, &self.classes, &self.vocab, &self.tokenizer, idf)
+            }
+            None => Counts::new(documents, &self.classes, &self.vocab, &self.tokenizer),
+        };
+        self.counts = std::mem::take(&mut self.counts).merge(counts);
+        self.estimate();
+    }
+
+    fn estimate(&mut self) {
+        let classes = self.classes.len();
+        let vocab_size = self.vocab.len();
+        let mut totals = vec![0.0; vocab_size];
+        for words in &self.counts.words {
+            for (token, count) in words {
+                totals[*token] += count;
+            }
+        }
+        // complement counts, smoothed, of every token and class
+        let mut complement: Vec<f64> = totals
+            .iter()
+            .flat_map(|total| std::iter::repeat_n(total + self.alpha, classes))
+            .collect();
+        for (class, words) in self.counts.words.iter().enumerate() {
+            for (token, count) in words {
+                complement[token * classes + class] -= count;
+            }
+        }
+        let mut class_totals = vec![0.0; classes];
+        for (cell, count) in complement.iter().enumerate() {
+            class_totals[cell % classes] += count;
+        }
+        let mut logged: Vec<f64> = complement
+            .iter()
+            .enumerate()
+            .map(|(cell, count)| (count / class_totals[cell % classes]).ln())
+            .collect();
+        if self.weight_normalized {
+            let mut sums = vec![0.0; classes];
+            for (cell, weight) in logged.iter().enumerate() {
+                sums[cell % classes] += weight;
+            }
+            for (cell, weight) in logged.iter_mut().enumerate() {
+                *weight /= sums[cell % classes];
+            }
+        } else {
+            // words frequent in the other classes count against a class
+            for weight in &mut logged {
+                *weight = -*weight;
+            }
+        }
+        self.weights = logged;
+    }
+
+    /// Weight of one occurrence of `word` for the class at `class`, if it is
+    /// in the vocabulary.
+    pub fn weight(&self, class: usize, word: &str) -> Option<f64> {
+        let token = *self.vocab.get(word)?;
+        Some(self.weights[token * self.classes.len() + class])
+    }
+
+    /// Summed weights of the tokens of `document` for every class, in the
+    /// order of [`ComplementNaiveBayes::classes`], TF-IDF weighted if the
+    /// model is. Words outside the vocabulary are ignored.
+    pub fn scores(&self, document: &Document) -> Vec<f64> {
+        let classes = self.classes.len();
+        let mut tokens = Vec::new();
+        self.tokenizer.for_each_token(&document.text, &mut |word| {
+            tokens.extend(self.vocab.get(word).copied())
+        });
+        let mut scores = vec![0.0; classes];
+        for (token, occurrences) in document_weights(self.idf.as_deref(), tokens) {
+            for (score, weight) in scores.iter_mut().zip(&self.weights[token * classes..]) {
+                *score += occurrences * weight;
+            }
+        }
+        scores
+    }
+}
+
+impl Classifier for ComplementNaiveBayes {
+    fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        ComplementNaiveBayes::scores(self, document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary;
+
+    #[test]
+    fn test_complement_resists_skewed_priors() {
+        let mut documents: Vec<Document> = (0..9)
+            .map(|i| Document {
+                class: "common".into(),
+                text: format!("news report topic{}", i),
+            })
+            .collect();
+        documents.push(Document {
+            class: "rare".into(),
+            text: "alert urgent".into(),
+        });
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = vocabulary(&documents);
+        let unseen = Document {
+            class: String::new(),
+            text: "alert report".into(),
+        };
+        let multinomial = NaiveBayes::new(&documents, classes.clone(), vocab.clone());
+        assert_eq!(multinomial.predict(&unseen), Some("common".into()));
+        let mut complement = ComplementNaiveBayes::new(&documents, classes, vocab);
+        assert_eq!(complement.predict(&unseen), Some("rare".into()));
+
+        // "alert" occurs once outside "common", among 2 words; add-one
+        // smoothing over the 13 words of the vocabulary
+        let expected = -(2.0f64 / 15.0).ln();
+        assert!((complement.weight(0, "alert").unwrap() - expected).abs() < 1e-12);
+        assert_eq!(
+            ComplementNaiveBayes::from_model(&multinomial).scores(&unseen),
+            complement.scores(&unseen)
+        );
+
+        complement.set_weight_normalization(true);
+        let sum: f64 = (0..complement.vocab.len())
+            .map(|token| complement.weights[token * 2])
+            .sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_complement_of_tf_idf_model_weights_documents() {
+        let documents: Vec<Document> = [
+            ("pos", "good good movie"),
+            ("neg", "bad movie"),
+            ("pos", "good plot"),
+            ("neg", "bad bad plot"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let model = crate::NaiveBayesBuilder::new()
+            .documents(&documents)
+            .tf_idf(true)
+            .fit()
+            .unwrap()
+            .0
+            .into_model();
+        let mut complement = ComplementNaiveBayes::from_model(&model);
+        let document = Document {
+            class: String::new(),
+            text: "good good movie".into(),
+        };
+        let good = model.idf("good").unwrap() * 2.0;
+        let movie = model.idf("movie").unwrap();
+        let norm = (good * good + movie * movie).sqrt();
+        for (class, score) in complement.scores(&document).into_iter().enumerate() {
+            let expected = (good * complement.weight(class, "good").unwrap()
+                + movie * complement.weight(class, "movie").unwrap())
+                / norm;
+            assert!((score - expected).abs() < 1e-12);
+        }
+
+        // added documents are weighted by the same idf
+        let counts = complement.counts.clone();
+        complement.partial_fit(&documents[..1]);
+        let added = Counts::new_tf_idf(
+            &documents[..1],
+            &complement.classes,
+            &complement.vocab,
+            &complement.tokenizer,
+            complement.idf.as_deref().unwrap(),
+        );
+        assert_eq!(complement.counts, counts.merge(added));
+    }
+}
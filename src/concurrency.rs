@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// Where the parallel work of training runs, so that a service can keep it
+/// off the global rayon pool.
+#[derive(Clone, Debug, Default)]
+pub enum Concurrency {
+    /// The global rayon pool, or the pool of the caller.
+    #[default]
+    Global,
+    /// A pool of at most this many threads, built for each operation unless
+    /// the pool of the caller is small enough already. Zero counts as one.
+    MaxThreads(usize),
+    /// A pool provided and kept by the caller.
+    Pool(Arc<ThreadPool>),
+}
+
+impl Concurrency {
+    /// Runs `op`, and every parallel iterator within it, in the configured
+    /// pool. Wrap operations outside training, such as cross-validation or
+    /// [`crate::NaiveBayes::partial_fit`], in it to limit them too.
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match self {
+            Concurrency::Global => op(),
+            // rayon reads zero threads as one per CPU
+            Concurrency::MaxThreads(threads)
+                if rayon::current_num_threads() <= (*threads).max(1) =>
+            {
+                op()
+            }
+            Concurrency::MaxThreads(threads) => {
+                match ThreadPoolBuilder::new()
+                    .num_threads((*threads).max(1))
+                    .build()
+                {
+                    Ok(pool) => pool.install(op),
+                    // not being able to spawn threads leaves the current pool
+                    Err(_) => op(),
+                }
+            }
+            Concurrency::Pool(pool) => pool.install(op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{Document, NaiveBayesBuilder};
+
+    #[test]
+    fn test_training_runs_in_the_configured_pool() {
+        let documents: Vec<Document> = (0..40)
+            .map(|i| Document {
+                class: format!("c{}", i % 2),
+                text: format!("w{} x{}", i, i % 5),
+            })
+            .collect();
+        let sizes = Arc::new(Mutex::new(Vec::new()));
+        let tokenizer = {
+            let sizes = sizes.clone();
+            move |text: &str| -> Vec<String> {
+                sizes.lock().unwrap().push(rayon::current_num_threads());
+                text.split_whitespace().map(str::to_owned).collect()
+            }
+        };
+        let pool = Arc::new(ThreadPoolBuilder::new().num_threads(3).build().unwrap());
+        NaiveBayesBuilder::new()
+            .documents(&documents)
+            .tokenizer(tokenizer.clone())
+            .thread_pool(pool)
+            .fit()
+            .unwrap();
+        assert!(sizes.lock().unwrap().iter().all(|&size| size == 3));
+
+        sizes.lock().unwrap().clear();
+        NaiveBayesBuilder::new()
+            .documents(&documents)
+            .tokenizer(tokenizer)
+            .max_threads(1)
+            .fit()
+            .unwrap();
+        assert!(!sizes.lock().unwrap().is_empty());
+        assert!(sizes.lock().unwrap().iter().all(|&size| size == 1));
+    }
+
+    #[test]
+    fn test_zero_max_threads_runs_on_one_thread() {
+        let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let threads =
+            pool.install(|| Concurrency::MaxThreads(0).install(rayon::current_num_threads));
+        assert_eq!(threads, 1);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{Class, Document};
+
+/// What to do with texts that occur in the training data with different
+/// labels. Texts are compared with whitespace normalized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Count every copy under its own label.
+    #[default]
+    KeepAll,
+    /// Relabel every copy with the most frequent label, dropping the text
+    /// when the top labels tie.
+    Majority,
+    /// Drop every copy of a conflicting text.
+    DropConflicts,
+    /// Count a conflicting text once per distinct label, so repeated copies
+    /// do not add up.
+    DownWeight,
+}
+
+/// Applies `policy` to `documents`, returning the documents to train on and
+/// the number of distinct conflicting texts.
+pub fn resolve_conflicts(documents: &[Document], policy: ConflictPolicy) -> (Vec<Document>, usize) {
+    let mut labels: HashMap<String, BTreeMap<&Class, usize>> = HashMap::new();
+    for document in documents {
+        *labels
+            .entry(document.words().join(" "))
+            .or_default()
+            .entry(&document.class)
+            .or_default() += 1;
+    }
+    let conflicts = labels.values().filter(|labels| labels.len() > 1).count();
+    if policy == ConflictPolicy::KeepAll || conflicts == 0 {
+        return (documents.to_vec(), conflicts);
+    }
+
+    let mut resolved = Vec::with_capacity(documents.len());
+    let mut emitted: HashMap<String, Vec<Class>> = HashMap::new();
+    for document in documents {
+        let key = document.words().join(" ");
+        let votes = &labels[&key];
+        if votes.len() == 1 {
+            resolved.push(document.clone());
+            continue;
+        }
+        match policy {
+            ConflictPolicy::KeepAll | ConflictPolicy::DropConflicts => {}
+            ConflictPolicy::Majority => {
+                let top = votes.values().max().copied().unwrap_or(0);
+                let mut winners = votes.iter().filter(|(_, count)| **count == top);
+                if let (Some((class, _)), None) = (winners.next(), winners.next()) {
+                    resolved.push(Document {
+                        class: (*class).clone(),
+                        text: document.text.clone(),
+                    });
+                }
+            }
+            ConflictPolicy::DownWeight => {
+                let seen = emitted.entry(key).or_default();
+                if !seen.contains(&document.class) {
+                    seen.push(document.class.clone());
+                    resolved.push(document.clone());
+                }
+            }
+        }
+    }
+    (resolved, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{vocabulary, NaiveBayes, TrainOptions, TrainWarning};
+
+    #[test]
+    fn test_conflict_policies() {
+        let documents: Vec<Document> = [
+            ("spam", "win now"),
+            ("ham", "win  now"),
+            ("spam", "win now"),
+            ("ham", "lunch"),
+            ("spam", "tie"),
+            ("ham", "tie"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let labels = |policy| {
+            let (documents, conflicts) = resolve_conflicts(&documents, policy);
+            assert_eq!(conflicts, 2);
+            documents
+                .into_iter()
+                .map(|d| format!("{}:{}", d.class, d.words().join(" ")))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(labels(ConflictPolicy::KeepAll).len(), 6);
+        assert_eq!(
+            labels(ConflictPolicy::Majority),
+            ["spam:win now", "spam:win now", "spam:win now", "ham:lunch"]
+        );
+        assert_eq!(labels(ConflictPolicy::DropConflicts), ["ham:lunch"]);
+        assert_eq!(
+            labels(ConflictPolicy::DownWeight),
+            [
+                "spam:win now",
+                "ham:win now",
+                "ham:lunch",
+                "spam:tie",
+                "ham:tie"
+            ]
+        );
+
+        let options = TrainOptions {
+            conflicts: ConflictPolicy::Majority,
+            ..Default::default()
+        };
+        let classes = ["ham".into(), "spam".into()].into();
+        let (model, warnings) =
+            NaiveBayes::try_new(&documents, classes, vocabulary(&documents), &options).unwrap();
+        assert_eq!(warnings[0], TrainWarning::ConflictingLabels(2));
+        assert_eq!(model.counts.documents, [1.0, 3.0]);
+    }
+}
@@ -0,0 +1,225 @@
+//! Checks this crate against parameters and scores exported from the
+//! naive Bayes estimators of scikit-learn, see
+//! `fixtures/conformance/export.py`. A fixture holds the training corpus,
+//! the fitted parameters and the joint log-likelihood and prediction of
+//! every test document.
+
+use std::fmt;
+
+use crate::{bernoulli::BernoulliNaiveBayes, Class, Classifier, Document, NaiveBayes, Word};
+
+/// The scikit-learn estimator a fixture was exported from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum Estimator {
+    MultinomialNB,
+    BernoulliNB,
+}
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Fixture {
+    pub estimator: Estimator,
+    pub alpha: f64,
+    pub classes: Vec<Class>,
+    /// Vocabulary words in the column order of `feature_log_prob`.
+    pub features: Vec<Word>,
+    pub train: Vec<Document>,
+    pub class_log_prior: Vec<f64>,
+    /// Row per class, column per feature.
+    pub feature_log_prob: Vec<Vec<f64>>,
+    pub test: Vec<Expected>,
+}
+
+/// The expected scores of a test document.
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Expected {
+    pub text: String,
+    pub joint_log_likelihood: Vec<f64>,
+    pub prediction: Class,
+}
+
+/// The first difference found between a model and its fixture.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mismatch {
+    Classes {
+        expected: Vec<Class>,
+        actual: Vec<Class>,
+    },
+    /// A parameter or score off by more than the tolerance.
+    Value {
+        name: String,
+        expected: f64,
+        actual: f64,
+    },
+    Prediction {
+        document: usize,
+        expected: Class,
+        actual: Option<Class>,
+    },
+    /// The estimator and smoothing have no counterpart in this crate.
+    Unsupported,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Classes { expected, actual } => {
+                write!(f, "expected classes {:?}, got {:?}", expected, actual)
+            }
+            Mismatch::Value {
+                name,
+                expected,
+                actual,
+            } => write!(f, "expected {} = {}, got {}", name, expected, actual),
+            Mismatch::Prediction {
+                document,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected test document {} to be {:?}, got {:?}",
+                document, expected, actual
+            ),
+            Mismatch::Unsupported => write!(f, "the fixture has no counterpart in this crate"),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+impl Fixture {
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Trains the counterpart of the fixture and compares it, parameters
+    /// first, with values equal up to `tolerance` relative to their size.
+    pub fn check(&self, tolerance: f64) -> Result<(), Mismatch> {
+        let classes = self.classes.iter().cloned().collect();
+        let vocab = self.features.iter().cloned().collect();
+        let model: Box<dyn Classifier> = match self.estimator {
+            Estimator::MultinomialNB => {
+                let mut model = NaiveBayes::new(&self.train, classes, vocab);
+                model.set_alpha(self.alpha);
+                self.check_parameters(&model, tolerance)?;
+                Box::new(model)
+            }
+            Estimator::BernoulliNB => {
+                let mut model = BernoulliNaiveBayes::new(&self.train, classes, vocab);
+                model.set_alpha(self.alpha);
+                compare(
+                    "class_log_prior",
+                    &self.class_log_prior,
+                    model.log_prior(),
+                    tolerance,
+                )?;
+                for (class, row) in self.feature_log_prob.iter().enumerate() {
+                    let actual: Vec<f64> = self
+                        .features
+                        .iter()
+                        .filter_map(|word| model.log_presence(class, word))
+                        .collect();
+                    compare(
+                        &format!("feature_log_prob[{}]", class),
+                        row,
+                        &actual,
+                        tolerance,
+                    )?;
+                }
+                Box::new(model)
+            }
+        };
+        if model.classes() != self.classes {
+            return Err(Mismatch::Classes {
+                expected: self.classes.clone(),
+                actual: model.classes().to_vec(),
+            });
+        }
+
+        for (i, expected) in self.test.iter().enumerate() {
+            let document = Document {
+                class: String::new(),
+                text: expected.text.clone(),
+            };
+            compare(
+                &format!("joint_log_likelihood[{}]", i),
+                &expected.joint_log_likelihood,
+                &model.scores(&document),
+                tolerance,
+            )?;
+            let actual = model.predict(&document);
+            if actual.as_ref() != Some(&expected.prediction) {
+                return Err(Mismatch::Prediction {
+                    document: i,
+                    expected: expected.prediction.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_parameters(&self, model: &NaiveBayes, tolerance: f64) -> Result<(), Mismatch> {
+        compare(
+            "class_log_prior",
+            &self.class_log_prior,
+            &model.log_prior,
+            tolerance,
+        )?;
+        for (class, row) in self.feature_log_prob.iter().enumerate() {
+            let actual: Vec<f64> = self
+                .features
+                .iter()
+                .map(|word| model.likelihood.get(class, model.vocab[word]))
+                .collect();
+            compare(
+                &format!("feature_log_prob[{}]", class),
+                row,
+                &actual,
+                tolerance,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn compare(name: &str, expected: &[f64], actual: &[f64], tolerance: f64) -> Result<(), Mismatch> {
+    for (i, (e, a)) in expected.iter().zip(actual).enumerate() {
+        if (e - a).abs() > tolerance * e.abs().max(1.0) {
+            return Err(Mismatch::Value {
+                name: format!("{}[{}]", name, i),
+                expected: *e,
+                actual: *a,
+            });
+        }
+    }
+    if expected.len() != actual.len() {
+        return Err(Mismatch::Value {
+            name: format!("{} length", name),
+            expected: expected.len() as f64,
+            actual: actual.len() as f64,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scikit_learn_fixtures() {
+        for json in [
+            include_str!("../fixtures/conformance/multinomialnb.json"),
+            include_str!("../fixtures/conformance/bernoullinb.json"),
+        ] {
+            let fixture = Fixture::from_json(json).unwrap();
+            assert_eq!(fixture.check(1e-12), Ok(()), "{:?}", fixture.estimator);
+        }
+
+        // a model smoothed differently than exported is caught
+        let mut fixture =
+            Fixture::from_json(include_str!("../fixtures/conformance/multinomialnb.json")).unwrap();
+        fixture.alpha = 1.0;
+        assert!(matches!(fixture.check(1e-12), Err(Mismatch::Value { .. })));
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{Class, Document, FeatureDocument, SoftDocument, Tokenizer, WhitespaceTokenizer, Word};
+
+/// Raw corpus statistics the model parameters are estimated from.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Counts {
+    /// Training documents of every class. Documents with soft labels count
+    /// fractionally towards each of their classes.
+    pub(crate) documents: Vec<f64>,
+    /// Occurrences of every token in the documents of every class.
+    pub(crate) words: Vec<HashMap<usize, f64>>,
+    /// Number of documents every token occurs in.
+    pub(crate) document_frequency: Vec<usize>,
+}
+
+impl Counts {
+    pub(crate) fn empty(classes: usize, vocab_size: usize) -> Self {
+        Self {
+            documents: vec![0.0; classes],
+            words: vec![HashMap::new(); classes],
+            document_frequency: vec![0; vocab_size],
+        }
+    }
+
+    /// Counts `documents` in one pass. With the `parallel` feature every
+    /// worker thread fills its own counts, which are merged at the end.
+    pub(crate) fn new(
+        documents: &[Document],
+        classes: &[Class],
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+    ) -> Self {
+        let class_index = Self::class_index(classes);
+        Self::fold(documents, classes.len(), vocab.len(), |counts, document| {
+            counts.add(document, &class_index, vocab, tokenizer)
+        })
+    }
+
+    /// Counts soft-labelled `documents` like [`Counts::new`]. Labels of
+    /// classes outside `classes` are ignored.
+    pub(crate) fn new_soft(
+        documents: &[SoftDocument],
+        classes: &[Class],
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+    ) -> Self {
+        let class_index = Self::class_index(classes);
+        Self::fold(documents, classes.len(), vocab.len(), |counts, document| {
+            let labels: Vec<(usize, f64)> = document
+                .labels
+                .iter()
+                .filter_map(|(class, weight)| Some((*class_index.get(class.as_str())?, *weight)))
+                .collect();
+            counts.add_weighted(&document.text, &labels, vocab, tokenizer)
+        })
+    }
+
+    /// Counts `documents` like [`Counts::new`], every occurrence weighted by
+    /// the `idf` of its token and every document scaled to unit length, as
+    /// the TF-IDF features of scikit-learn's `TfidfTransformer`.
+    pub(crate) fn new_tf_idf(
+        documents: &[Document],
+        classes: &[Class],
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+        idf: &[f64],
+    ) -> Self {
+        let class_index = Self::class_index(classes);
+        Self::new_labeled(
+            documents,
+            classes.len(),
+            vocab,
+            tokenizer,
+            Some(idf),
+            |document| Some((*class_index.get(document.class.as_str())?, &document.text)),
+        )
+    }
+
+    /// Counts the texts of `items` under the class ids `label` gives them,
+    /// TF-IDF weighted with `idf` if given, like [`Counts::new`] and
+    /// [`Counts::new_tf_idf`]. Items without a class id are ignored.
+    pub(crate) fn new_labeled<T: Sync>(
+        items: &[T],
+        classes: usize,
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+        idf: Option<&[f64]>,
+        label: impl Fn(&T) -> Option<(usize, &str)> + Sync + Send,
+    ) -> Self {
+        Self::fold(items, classes, vocab.len(), |counts, item| {
+            let Some((class, text)) = label(item) else {
+                return counts;
+            };
+            match idf {
+                Some(idf) => counts.add_tf_idf(text, class, vocab, tokenizer, idf),
+                None => counts.add_weighted(text, &[(class, 1.0)], vocab, tokenizer),
+            }
+        })
+    }
+
+    /// Counts `text` as a document of `class` with TF-IDF weights.
+    fn add_tf_idf(
+        mut self,
+        text: &str,
+        class: usize,
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+        idf: &[f64],
+    ) -> Self {
+        self.documents[class] += 1.0;
+        let mut frequencies: HashMap<usize, f64> = HashMap::new();
+        tokenizer.for_each_token(text, &mut |word| {
+            if let Some(&token) = vocab.get(word) {
+                *frequencies.entry(token).or_default() += 1.0;
+            }
+        });
+        let norm = frequencies
+            .iter()
+            .map(|(token, tf)| (tf * idf[*token]).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        for (token, tf) in frequencies {
+            self.document_frequency[token] += 1;
+            *self.words[class].entry(token).or_default() += tf * idf[token] / norm;
+        }
+        self
+    }
+
+    fn fold<T: Sync>(
+        items: &[T],
+        classes: usize,
+        vocab_size: usize,
+        add: impl Fn(Self, &T) -> Self + Sync + Send,
+    ) -> Self {
+        let empty = || Self::empty(classes, vocab_size);
+
+        #[cfg(feature = "parallel")]
+        return items
+            .par_iter()
+            .fold(empty, &add)
+            .reduce(empty, Self::merge);
+
+        #[cfg(not(feature = "parallel"))]
+        items.iter().fold(empty(), add)
+    }
+
+    pub(crate) fn class_index(classes: &[Class]) -> HashMap<&str, usize> {
+        classes
+            .iter()
+            .enumerate()
+            .map(|(index, class)| (class.as_str(), index))
+            .collect()
+    }
+
+    pub(crate) fn add(
+        self,
+        document: &Document,
+        class_index: &HashMap<&str, usize>,
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+    ) -> Self {
+        let Some(&class) = class_index.get(document.class.as_str()) else {
+            return self;
+        };
+        self.add_weighted(&document.text, &[(class, 1.0)], vocab, tokenizer)
+    }
+
+    /// Counts `text` as a document of every class in `labels`, each with its
+    /// weight. The document frequency of its words grows by one whatever the
+    /// weights.
+    pub(crate) fn add_weighted(
+        self,
+        text: &str,
+        labels: &[(usize, f64)],
+        vocab: &HashMap<Word, usize>,
+        tokenizer: &dyn Tokenizer,
+    ) -> Self {
+        self.add_tokens(labels, |emit| {
+            tokenizer.for_each_token(text, &mut |word| {
+                if let Some(&token) = vocab.get(word) {
+                    emit(token);
+                }
+            })
+        })
+    }
+
+    /// Counts the tokens emitted by `tokens` like [`Counts::add_weighted`].
+    pub(crate) fn add_tokens(
+        mut self,
+        labels: &[(usize, f64)],
+        tokens: impl FnOnce(&mut dyn FnMut(usize)),
+    ) -> Self {
+        for (class, weight) in labels {
+            self.documents[*class] += weight;
+        }
+
+        let mut seen = HashSet::new();
+        tokens(&mut |token| {
+            for (class, weight) in labels {
+                *self.words[*class].entry(token).or_default() += weight;
+            }
+            if seen.insert(token) {
+                self.document_frequency[token] += 1;
+            }
+        });
+        self
+    }
+
+    /// Counts documents given as features like [`Counts::new`].
+    pub(crate) fn new_features<F: Eq + Hash + Sync>(
+        documents: &[FeatureDocument<F>],
+        classes: &[Class],
+        vocab: &HashMap<F, usize>,
+    ) -> Self {
+        let class_index = Self::class_index(classes);
+        Self::fold(documents, classes.len(), vocab.len(), |counts, document| {
+            let Some(&class) = class_index.get(document.class.as_str()) else {
+                return counts;
+            };
+            counts.add_tokens(&[(class, 1.0)], |emit| {
+                for feature in &document.features {
+                    if let Some(&token) = vocab.get(feature) {
+                        emit(token);
+                    }
+                }
+            })
+        })
+    }
+
+    /// Makes room for a class without documents at `index`.
+    pub(crate) fn insert_class(&mut self, index: usize) {
+        self.documents.insert(index, 0.0);
+        self.words.insert(index, HashMap::new());
+    }
+
+    /// Drops zero counts and releases spare capacity.
+    pub(crate) fn compact(&mut self) {
+        for words in &mut self.words {
+            words.retain(|_, count| *count != 0.0);
+            words.shrink_to_fit();
+        }
+        self.documents.shrink_to_fit();
+        self.words.shrink_to_fit();
+        self.document_frequency.shrink_to_fit();
+    }
+
+    /// Approximate bytes allocated on the heap.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let entry = size_of::<(usize, f64)>() + 1;
+        let words: usize = self.words.iter().map(|w| w.capacity() * entry).sum();
+        self.documents.capacity() * size_of::<f64>()
+            + self.words.capacity() * size_of::<HashMap<usize, f64>>()
+            + words
+            + self.document_frequency.capacity() * size_of::<usize>()
+    }
+
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        for (total, count) in self.documents.iter_mut().zip(other.documents) {
+            *total += count;
+        }
+        for (total, counts) in self.words.iter_mut().zip(other.words) {
+            for (token, count) in counts {
+                *total.entry(token).or_default() += count;
+            }
+        }
+        for (total, count) in self
+            .document_frequency
+            .iter_mut()
+            .zip(other.document_frequency)
+        {
+            *total += count;
+        }
+        self
+    }
+}
+
+/// Every distinct word of `documents`, collected in parallel with the
+/// `parallel` feature.
+pub fn vocabulary(documents: &[Document]) -> HashSet<Word> {
+    vocabulary_with(documents, &WhitespaceTokenizer)
+}
+
+/// Every distinct word of `documents` as split by `tokenizer`.
+pub fn vocabulary_with(documents: &[Document], tokenizer: &dyn Tokenizer) -> HashSet<Word> {
+    let add = |mut vocab: HashSet<Word>, document: &Document| {
+        tokenizer.for_each_token(&document.text, &mut |word| {
+            if !vocab.contains(word) {
+                vocab.insert(word.to_owned());
+            }
+        });
+        vocab
+    };
+
+    #[cfg(feature = "parallel")]
+    return documents
+        .par_iter()
+        .fold(HashSet::new, add)
+        .reduce(HashSet::new, |mut a, b| {
+            a.extend(b);
+            a
+        });
+
+    #[cfg(not(feature = "parallel"))]
+    documents.iter().fold(HashSet::new(), add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let documents: Vec<Document> = [("a", "x y x"), ("b", "y z"), ("a", "z z w"), ("c", "x")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes = vec!["a".to_string(), "b".to_string()];
+        let vocab: HashMap<Word, usize> = ["x", "y", "z"]
+            .iter()
+            .enumerate()
+            .map(|(token, word)| (word.to_string(), token))
+            .collect();
+
+        let counts = Counts::new(&documents, &classes, &vocab, &WhitespaceTokenizer);
+        assert_eq!(counts.documents, vec![2.0, 1.0]);
+        assert_eq!(
+            counts.words[0],
+            HashMap::from([(0, 2.0), (1, 1.0), (2, 2.0)])
+        );
+        assert_eq!(counts.words[1], HashMap::from([(1, 1.0), (2, 1.0)]));
+        // documents of unknown classes are not counted
+        assert_eq!(counts.document_frequency, vec![1, 2, 2]);
+
+        assert_eq!(vocabulary(&documents).len(), 4);
+    }
+}
@@ -0,0 +1,154 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Class, Document, NaiveBayes, Tokenizer, Word};
+
+/// How much of a corpus the vocabulary of a model covers.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Coverage {
+    pub tokens: usize,
+    pub known_tokens: usize,
+    /// Distinct words.
+    pub types: usize,
+    pub known_types: usize,
+}
+
+impl Coverage {
+    /// Fraction of word occurrences in the vocabulary.
+    pub fn token_coverage(&self) -> f64 {
+        self.known_tokens as f64 / self.tokens.max(1) as f64
+    }
+
+    /// Fraction of distinct words in the vocabulary.
+    pub fn type_coverage(&self) -> f64 {
+        self.known_types as f64 / self.types.max(1) as f64
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoverageReport {
+    pub overall: Coverage,
+    /// Coverage of the documents labelled with each class, whether or not
+    /// the model knows the class.
+    pub per_class: HashMap<Class, Coverage>,
+}
+
+impl<L: Clone + Eq + Hash> NaiveBayes<L> {
+    /// Vocabulary coverage of `documents`, split by the tokenizer of the
+    /// model, to estimate how a model will fare on a new domain before
+    /// deploying it there.
+    pub fn coverage(&self, documents: &[Document]) -> CoverageReport {
+        let mut overall = Tally::default();
+        let mut per_class: HashMap<&str, Tally> = HashMap::new();
+        for document in documents {
+            let class = per_class.entry(document.class.as_str()).or_default();
+            self.tokenizer.for_each_token(&document.text, &mut |word| {
+                overall.add(word);
+                class.add(word);
+            });
+        }
+
+        let coverage = |tally: Tally| Coverage {
+            tokens: tally.tokens,
+            known_tokens: tally
+                .types
+                .iter()
+                .filter(|(word, _)| self.vocab.contains_key(*word))
+                .map(|(_, count)| count)
+                .sum(),
+            types: tally.types.len(),
+            known_types: tally
+                .types
+                .keys()
+                .filter(|word| self.vocab.contains_key(*word))
+                .count(),
+        };
+        CoverageReport {
+            overall: coverage(overall),
+            per_class: per_class
+                .into_iter()
+                .map(|(class, tally)| (class.to_owned(), coverage(tally)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Tally {
+    tokens: usize,
+    types: HashMap<Word, usize>,
+}
+
+impl Tally {
+    fn add(&mut self, word: &str) {
+        self.tokens += 1;
+        match self.types.get_mut(word) {
+            Some(count) => *count += 1,
+            None => {
+                self.types.insert(word.to_owned(), 1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_of_new_corpus() {
+        let model = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.7, -0.7],
+            vec![vec![(0, -0.5)], vec![(1, -0.5)]],
+            vec![-2.0, -2.0],
+        );
+        let documents: Vec<Document> = [
+            ("pos", "good good superb"),
+            ("neg", "bad awful"),
+            ("new", "meh"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+
+        let report = model.coverage(&documents);
+        assert_eq!(
+            report.overall,
+            Coverage {
+                tokens: 6,
+                known_tokens: 3,
+                types: 5,
+                known_types: 2,
+            }
+        );
+        assert_eq!(report.overall.token_coverage(), 0.5);
+        assert_eq!(report.per_class["pos"].token_coverage(), 2.0 / 3.0);
+        assert_eq!(report.per_class["pos"].type_coverage(), 0.5);
+        assert_eq!(report.per_class["new"].known_tokens, 0);
+        assert_eq!(report.per_class.len(), 3);
+
+        // tokens are those of the model, here comma-separated values
+        let mut model = model;
+        model.tokenizer = crate::SharedTokenizer::new(|text: &str| -> Vec<String> {
+            text.split(',').map(String::from).collect()
+        });
+        let documents = [Document {
+            class: "pos".into(),
+            text: "good,very good,good".into(),
+        }];
+        let report = model.coverage(&documents);
+        assert_eq!(
+            report.overall,
+            Coverage {
+                tokens: 3,
+                known_tokens: 2,
+                types: 2,
+                known_types: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Loaders for corpora on disk.
+
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use crate::{Document, Word};
+
+/// Reads a corpus laid out as one folder per class holding one file per
+/// document, as the IMDB reviews are. Other entries are skipped; folders
+/// and files are read in name order.
+pub fn read_labelled_folders(path: impl AsRef<Path>) -> io::Result<Vec<Document>> {
+    let mut documents = Vec::new();
+    for class_dir in sorted_entries(path.as_ref())? {
+        if !class_dir.is_dir() {
+            continue;
+        }
+        let Some(class) = class_dir.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        for file in sorted_entries(&class_dir)? {
+            if file.is_file() {
+                documents.push(Document {
+                    class: class.to_owned(),
+                    text: fs::read_to_string(&file)?,
+                });
+            }
+        }
+    }
+    Ok(documents)
+}
+
+fn sorted_entries(path: &Path) -> io::Result<Vec<std::path::PathBuf>> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort_unstable();
+    Ok(entries)
+}
+
+/// Reads a vocabulary file of whitespace-separated words, such as
+/// `imdb.vocab`.
+pub fn read_vocabulary(path: impl AsRef<Path>) -> io::Result<HashSet<Word>> {
+    Ok(fs::read_to_string(path)?
+        .split_whitespace()
+        .map(str::to_owned)
+        .collect())
+}
+
+/// Where [`read_csv`] finds the label and the text of a document.
+#[derive(Clone, Debug)]
+pub struct CsvFormat {
+    /// Column of the label, counted from 0.
+    pub label: usize,
+    pub text: usize,
+    pub delimiter: char,
+    /// Skip the first record.
+    pub header: bool,
+}
+
+impl Default for CsvFormat {
+    fn default() -> Self {
+        Self {
+            label: 0,
+            text: 1,
+            delimiter: ',',
+            header: true,
+        }
+    }
+}
+
+/// Reads one document per CSV record. Fields may be quoted with `"`, and
+/// quoted fields may hold delimiters, newlines and doubled quotes. Empty
+/// lines are skipped.
+pub fn read_csv(mut reader: impl Read, format: &CsvFormat) -> io::Result<Vec<Document>> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    let records = parse_csv(&input, format.delimiter);
+    let skip = usize::from(format.header);
+    records
+        .into_iter()
+        .enumerate()
+        .skip(skip)
+        .filter(|(_, record)| record.iter().any(|field| !field.is_empty()))
+        .map(|(line, mut record)| {
+            let missing = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("record {} has only {} fields", line + 1, record.len()),
+                )
+            };
+            if format.label.max(format.text) >= record.len() {
+                return Err(missing());
+            }
+            Ok(Document {
+                class: std::mem::take(&mut record[format.label]),
+                text: std::mem::take(&mut record[format.text]),
+            })
+        })
+        .collect()
+}
+
+fn parse_csv(input: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            c if quoted => field.push(c),
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_csv() {
+        let input = "label,text\r\nham,\"see you, later\"\n\nspam,\"say \"\"win\"\"\nnow\"\n";
+        let documents = read_csv(input.as_bytes(), &CsvFormat::default()).unwrap();
+        let documents: Vec<(&str, &str)> = documents
+            .iter()
+            .map(|d| (d.class.as_str(), d.text.as_str()))
+            .collect();
+        assert_eq!(
+            documents,
+            [("ham", "see you, later"), ("spam", "say \"win\"\nnow")]
+        );
+
+        let swapped = CsvFormat {
+            label: 1,
+            text: 0,
+            delimiter: '\t',
+            header: false,
+        };
+        let documents = read_csv("hello\tham".as_bytes(), &swapped).unwrap();
+        assert_eq!(documents[0].class, "ham");
+        let error = read_csv("only one field".as_bytes(), &swapped).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_read_labelled_folders() {
+        let root = std::env::temp_dir().join(format!("naive-bayes-folders-{}", std::process::id()));
+        for (class, file, text) in [
+            ("pos", "1.txt", "good"),
+            ("neg", "1.txt", "bad"),
+            ("neg", "2.txt", "dull"),
+        ] {
+            fs::create_dir_all(root.join(class)).unwrap();
+            fs::write(root.join(class).join(file), text).unwrap();
+        }
+        fs::write(root.join("imdb.vocab"), "bad\ngood dull\n").unwrap();
+
+        let documents = read_labelled_folders(&root).unwrap();
+        let documents: Vec<(&str, &str)> = documents
+            .iter()
+            .map(|d| (d.class.as_str(), d.text.as_str()))
+            .collect();
+        assert_eq!(
+            documents,
+            [("neg", "bad"), ("neg", "dull"), ("pos", "good")]
+        );
+        assert_eq!(read_vocabulary(root.join("imdb.vocab")).unwrap().len(), 3);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
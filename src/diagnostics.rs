@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use crate::{Class, Document, NaiveBayes, Tokenizer, Word};
+
+/// How much one batch of [`NaiveBayes::partial_fit_diagnosed`] moved the
+/// model, to spot a poisoned or mislabelled batch right after ingesting it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateDiagnostics {
+    pub documents: usize,
+    /// Change of the log prior of every class, in class order.
+    pub prior_shifts: Vec<(Class, f64)>,
+    /// The largest changes of the likelihoods of the words in the batch,
+    /// largest first.
+    pub likelihood_shifts: Vec<LikelihoodShift>,
+}
+
+impl UpdateDiagnostics {
+    pub fn max_prior_shift(&self) -> f64 {
+        self.prior_shifts
+            .iter()
+            .map(|(_, shift)| shift.abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+/// The log-likelihood of a word in a class before and after an update.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LikelihoodShift {
+    pub class: Class,
+    pub word: Word,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl LikelihoodShift {
+    pub fn shift(&self) -> f64 {
+        self.after - self.before
+    }
+}
+
+impl NaiveBayes {
+    /// [`NaiveBayes::partial_fit`], reporting the shift of every prior and
+    /// the `top` largest shifts of the likelihoods of the words in
+    /// `documents`.
+    pub fn partial_fit_diagnosed(
+        &mut self,
+        documents: &[Document],
+        top: usize,
+    ) -> UpdateDiagnostics {
+        let mut tokens = BTreeSet::new();
+        for document in documents {
+            self.tokenizer.for_each_token(&document.text, &mut |word| {
+                tokens.extend(self.vocab.get(word).copied());
+            });
+        }
+        let cells: Vec<(usize, usize)> = (0..self.classes.len())
+            .flat_map(|class| tokens.iter().map(move |token| (class, *token)))
+            .collect();
+        let before: Vec<f64> = cells
+            .iter()
+            .map(|(class, token)| self.likelihood.get(*class, *token))
+            .collect();
+        let prior = self.log_prior.clone();
+
+        self.partial_fit(documents);
+
+        let mut words = vec![""; self.vocab.len()];
+        for (word, token) in &self.vocab {
+            words[*token] = word;
+        }
+        let mut likelihood_shifts: Vec<LikelihoodShift> = cells
+            .into_iter()
+            .zip(before)
+            .map(|((class, token), before)| LikelihoodShift {
+                class: self.classes[class].clone(),
+                word: words[token].to_owned(),
+                before,
+                after: self.likelihood.get(class, token),
+            })
+            .collect();
+        likelihood_shifts.sort_by(|a, b| b.shift().abs().total_cmp(&a.shift().abs()));
+        likelihood_shifts.truncate(top);
+
+        UpdateDiagnostics {
+            documents: documents.len(),
+            prior_shifts: self
+                .classes
+                .iter()
+                .zip(prior.iter().zip(&self.log_prior))
+                .map(|(class, (before, after))| (class.clone(), after - before))
+                .collect(),
+            likelihood_shifts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_diagnostics() {
+        let document = |class: &str, text: &str| Document {
+            class: class.into(),
+            text: text.into(),
+        };
+        let documents: Vec<Document> = (0..10)
+            .flat_map(|_| {
+                [
+                    document("spam", "win cash"),
+                    document("ham", "lunch meeting"),
+                ]
+            })
+            .collect();
+        let classes = ["ham".into(), "spam".into()].into();
+        let mut model = NaiveBayes::new(&documents, classes, crate::vocabulary(&documents));
+
+        // a healthy batch barely moves the model
+        let healthy = model.partial_fit_diagnosed(&documents[..2], 3);
+        assert_eq!(healthy.documents, 2);
+        assert!(healthy.max_prior_shift() < 0.01);
+
+        // a mislabelled batch moves "cash" into ham the most
+        let poisoned: Vec<Document> = (0..5).map(|_| document("ham", "cash cash")).collect();
+        let diagnostics = model.partial_fit_diagnosed(&poisoned, 2);
+        assert!(diagnostics.max_prior_shift() > healthy.max_prior_shift());
+        let largest = &diagnostics.likelihood_shifts[0];
+        assert_eq!(
+            (largest.class.as_str(), largest.word.as_str()),
+            ("ham", "cash")
+        );
+        assert!(largest.shift() > 1.0);
+        assert_eq!(diagnostics.likelihood_shifts.len(), 2);
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use crate::{classifier::softmax, Class, Classifier, Document};
+
+/// How the members of an [`Ensemble`] are combined.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Combination {
+    /// Every member votes for its predicted class.
+    MajorityVote,
+    /// Members' posterior probabilities are averaged.
+    AverageScores,
+    /// Members' posterior probabilities are averaged with these weights, one
+    /// per member.
+    Weighted(Vec<f64>),
+}
+
+/// Several classifiers combined into one. Members may know different
+/// classes; the ensemble scores the union of them.
+pub struct Ensemble {
+    members: Vec<Box<dyn Classifier>>,
+    /// Sorted union of the members' classes.
+    classes: Vec<Class>,
+    pub combination: Combination,
+}
+
+impl Ensemble {
+    pub fn new(combination: Combination) -> Self {
+        Self {
+            members: Vec::new(),
+            classes: Vec::new(),
+            combination,
+        }
+    }
+
+    pub fn member(mut self, classifier: impl Classifier + 'static) -> Self {
+        for class in classifier.classes() {
+            if let Err(position) = self.classes.binary_search(class) {
+                self.classes.insert(position, class.clone());
+            }
+        }
+        self.members.push(Box::new(classifier));
+        self
+    }
+
+    /// Switches to [`Combination::Weighted`], weighting every member by its
+    /// accuracy on the labelled `validation` documents.
+    pub fn learn_weights(&mut self, validation: &[Document]) {
+        let weights = self
+            .members
+            .iter()
+            .map(|member| {
+                let correct = validation
+                    .iter()
+                    .filter(|d| member.predict(d).as_ref() == Some(&d.class))
+                    .count();
+                correct as f64 / validation.len().max(1) as f64
+            })
+            .collect();
+        self.combination = Combination::Weighted(weights);
+    }
+
+    fn class_index(&self) -> HashMap<&str, usize> {
+        self.classes
+            .iter()
+            .enumerate()
+            .map(|(index, class)| (class.as_str(), index))
+            .collect()
+    }
+}
+
+impl Classifier for Ensemble {
+    fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    /// Log of the combined vote share or posterior of every class.
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        let class_index = self.class_index();
+        let mut combined = vec![0.0; self.classes.len()];
+        let mut total_weight = 0.0;
+        for (i, member) in self.members.iter().enumerate() {
+            let weight = match &self.combination {
+                Combination::Weighted(weights) => weights.get(i).copied().unwrap_or(0.0),
+                _ => 1.0,
+            };
+            total_weight += weight;
+
+            if self.combination == Combination::MajorityVote {
+                if let Some(class) = member.predict(document) {
+                    combined[class_index[class.as_str()]] += weight;
+                }
+                continue;
+            }
+            let posterior = softmax(&member.scores(document));
+            for (class, p) in member.classes().iter().zip(posterior) {
+                combined[class_index[class.as_str()]] += weight * p;
+            }
+        }
+        combined
+            .into_iter()
+            .map(|share| (share / total_weight).ln())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the same scores for every document.
+    struct Fixed {
+        classes: Vec<Class>,
+        scores: Vec<f64>,
+    }
+
+    impl Classifier for Fixed {
+        fn classes(&self) -> &[Class] {
+            &self.classes
+        }
+
+        fn scores(&self, _: &Document) -> Vec<f64> {
+            self.scores.clone()
+        }
+    }
+
+    fn fixed(classes: &[&str], scores: &[f64]) -> Fixed {
+        Fixed {
+            classes: classes.iter().map(|c| c.to_string()).collect(),
+            scores: scores.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_ensemble_combinations() {
+        let document = Document {
+            class: "b".into(),
+            text: String::new(),
+        };
+        let members = || {
+            [
+                fixed(&["a", "b"], &[0.0, -0.1]),
+                fixed(&["a", "b"], &[0.0, -0.1]),
+                fixed(&["b", "c"], &[0.0, -10.0]),
+            ]
+        };
+        let ensemble = |combination| {
+            members()
+                .into_iter()
+                .fold(Ensemble::new(combination), Ensemble::member)
+        };
+
+        let vote = ensemble(Combination::MajorityVote);
+        assert_eq!(vote.classes(), ["a", "b", "c"]);
+        assert_eq!(vote.predict(&document), Some("a".into()));
+        // the third member is confident enough to carry the average
+        let average = ensemble(Combination::AverageScores);
+        assert_eq!(average.predict(&document), Some("b".into()));
+        let total: f64 = average.scores(&document).iter().map(|s| s.exp()).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+
+        let mut weighted = ensemble(Combination::MajorityVote);
+        weighted.learn_weights(std::slice::from_ref(&document));
+        assert_eq!(
+            weighted.combination,
+            Combination::Weighted(vec![0.0, 0.0, 1.0])
+        );
+        assert_eq!(weighted.predict(&document), Some("b".into()));
+    }
+}
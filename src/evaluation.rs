@@ -0,0 +1,441 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    classifier::argmax, Class, Classifier, Document, NaiveBayes, PredictOptions, Scratch,
+    SharedTokenizer, Tokenizer, TokenizerSpec,
+};
+
+/// A labelled document with metadata such as its source or language.
+#[derive(Clone, Debug)]
+pub struct TaggedDocument {
+    pub document: Document,
+    pub metadata: HashMap<String, String>,
+}
+
+impl TaggedDocument {
+    pub fn new(document: Document) -> Self {
+        Self {
+            document,
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn tag(mut self, key: &str, value: &str) -> Self {
+        self.metadata.insert(key.to_owned(), value.to_owned());
+        self
+    }
+}
+
+/// A way of grouping documents for [`evaluate_slices`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Facet {
+    /// The value of a metadata key, `(none)` for documents without it.
+    Metadata(String),
+    /// Number of words, bucketed at these increasing boundaries: with
+    /// boundaries `[10, 100]` the buckets are `<10`, `10-99` and `>=100`.
+    Length(Vec<usize>),
+}
+
+impl Facet {
+    fn name(&self) -> &str {
+        match self {
+            Facet::Metadata(key) => key,
+            Facet::Length(_) => "length",
+        }
+    }
+
+    fn value(&self, document: &TaggedDocument) -> String {
+        match self {
+            Facet::Metadata(key) => document
+                .metadata
+                .get(key)
+                .cloned()
+                .unwrap_or_else(|| "(none)".into()),
+            Facet::Length(boundaries) => {
+                let words = document.document.words().len();
+                let bucket = boundaries.iter().take_while(|b| words >= **b).count();
+                match (bucket.checked_sub(1), boundaries.get(bucket)) {
+                    (None, Some(upper)) => format!("<{}", upper),
+                    (Some(lower), Some(upper)) if boundaries[lower] + 1 == *upper => {
+                        boundaries[lower].to_string()
+                    }
+                    (Some(lower), Some(upper)) => format!("{}-{}", boundaries[lower], upper - 1),
+                    (Some(lower), None) => format!(">={}", boundaries[lower]),
+                    (None, None) => "all".into(),
+                }
+            }
+        }
+    }
+}
+
+/// Outcomes of the predictions on a set of documents.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    pub documents: usize,
+    pub correct: usize,
+    /// True positives, false positives and false negatives of every class
+    /// predicted or observed.
+    pub per_class: BTreeMap<Class, (usize, usize, usize)>,
+}
+
+impl Metrics {
+    fn record(&mut self, predicted: Option<&Class>, actual: &Class) {
+        self.documents += 1;
+        if predicted == Some(actual) {
+            self.correct += 1;
+            self.per_class.entry(actual.clone()).or_default().0 += 1;
+            return;
+        }
+        if let Some(predicted) = predicted {
+            self.per_class.entry(predicted.clone()).or_default().1 += 1;
+        }
+        self.per_class.entry(actual.clone()).or_default().2 += 1;
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        self.correct as f64 / self.documents.max(1) as f64
+    }
+
+    /// Mean F1 over every class predicted or observed.
+    pub fn macro_f1(&self) -> f64 {
+        let total: f64 = self
+            .per_class
+            .values()
+            .map(|(tp, fp, fn_)| 2.0 * *tp as f64 / (2 * tp + fp + fn_).max(1) as f64)
+            .sum();
+        total / self.per_class.len().max(1) as f64
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SliceReport {
+    pub overall: Metrics,
+    /// Metrics by facet name and value, such as `("language", "de")` or
+    /// `("length", "10-99")`.
+    pub slices: BTreeMap<(String, String), Metrics>,
+}
+
+impl SliceReport {
+    /// The slices of one facet, by value.
+    pub fn facet<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (&'a str, &'a Metrics)> {
+        self.slices
+            .iter()
+            .filter(move |((facet, _), _)| facet == name)
+            .map(|((_, value), metrics)| (value.as_str(), metrics))
+    }
+}
+
+/// Evaluates `classifier` on the labelled `documents`.
+pub fn evaluate(classifier: &impl Classifier, documents: &[Document]) -> Metrics {
+    let mut metrics = Metrics::default();
+    for document in documents {
+        metrics.record(classifier.predict(document).as_ref(), &document.class);
+    }
+    metrics
+}
+
+/// Evaluates `classifier` on `documents`, overall and broken down by every
+/// facet, so that a slice the model fails on is not hidden in the blended
+/// numbers.
+pub fn evaluate_slices(
+    classifier: &impl Classifier,
+    documents: &[TaggedDocument],
+    facets: &[Facet],
+) -> SliceReport {
+    let mut report = SliceReport::default();
+    for tagged in documents {
+        let predicted = classifier.predict(&tagged.document);
+        let actual = &tagged.document.class;
+        report.overall.record(predicted.as_ref(), actual);
+        for facet in facets {
+            report
+                .slices
+                .entry((facet.name().to_owned(), facet.value(tagged)))
+                .or_default()
+                .record(predicted.as_ref(), actual);
+        }
+    }
+    report
+}
+
+/// A fixed test set prepared for evaluating many model variants, as in a
+/// grid search: documents are tokenized once per tokenizer, and mapped to
+/// token ids once per distinct vocabulary instead of once per model.
+pub struct EvaluationCache {
+    documents: Vec<Document>,
+    /// Words of every document, by tokenizer.
+    words: Mutex<HashMap<TokenizerKey, Arc<Vec<Vec<String>>>>>,
+    /// Token ids of every document, by tokenizer and vocabulary fingerprint.
+    token_ids: Mutex<HashMap<(TokenizerKey, u64), Arc<TokenIds>>>,
+}
+
+/// In-vocabulary token ids of every document, in document order.
+type TokenIds = Vec<Vec<usize>>;
+
+/// A saved tokenizer by its spec, any other by identity. The key holds on to
+/// the tokenizer, so that no tokenizer allocated later can take its address
+/// while its words are cached.
+#[derive(Clone)]
+enum TokenizerKey {
+    Spec(TokenizerSpec),
+    Shared(SharedTokenizer),
+}
+
+impl PartialEq for TokenizerKey {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TokenizerKey::Spec(a), TokenizerKey::Spec(b)) => a == b,
+            (TokenizerKey::Shared(a), TokenizerKey::Shared(b)) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TokenizerKey {}
+
+impl Hash for TokenizerKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            TokenizerKey::Spec(spec) => spec.hash(state),
+            TokenizerKey::Shared(tokenizer) => tokenizer.address().hash(state),
+        }
+    }
+}
+
+impl EvaluationCache {
+    pub fn new(documents: Vec<Document>) -> Self {
+        Self {
+            documents,
+            words: Mutex::new(HashMap::new()),
+            token_ids: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn documents(&self) -> &[Document] {
+        &self.documents
+    }
+
+    /// Number of distinct tokenizer and vocabulary pairs whose token ids are
+    /// cached.
+    pub fn vocabularies(&self) -> usize {
+        self.token_ids.lock().unwrap().len()
+    }
+
+    /// Scores of every document under `model`, as
+    /// [`NaiveBayes::scores_into`] would compute them.
+    pub fn scores(&self, model: &NaiveBayes, options: &PredictOptions) -> Vec<Vec<f64>> {
+        let token_ids = self.token_ids(model);
+        let mut scratch = Scratch::default();
+        self.documents
+            .iter()
+            .zip(token_ids.iter())
+            .map(|(document, tokens)| {
+                model
+                    .scores_of_tokens(tokens, &document.text, options, &mut scratch)
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    pub fn evaluate(&self, model: &NaiveBayes) -> Metrics {
+        self.evaluate_with(model, &PredictOptions::default())
+    }
+
+    /// Metrics of `model` on the cached documents, ties going to the class
+    /// that sorts first as in [`NaiveBayes::predict`].
+    pub fn evaluate_with(&self, model: &NaiveBayes, options: &PredictOptions) -> Metrics {
+        let mut metrics = Metrics::default();
+        for (document, scores) in self.documents.iter().zip(self.scores(model, options)) {
+            let best = argmax(&scores).map(|class| &model.classes[class]);
+            metrics.record(best, &document.class);
+        }
+        metrics
+    }
+
+    fn token_ids(&self, model: &NaiveBayes) -> Arc<TokenIds> {
+        let tokenizer = match model.tokenizer.spec() {
+            Some(spec) => TokenizerKey::Spec(spec),
+            None => TokenizerKey::Shared(model.tokenizer.clone()),
+        };
+        let key = (tokenizer.clone(), fingerprint(model));
+        if let Some(token_ids) = self.token_ids.lock().unwrap().get(&key) {
+            return token_ids.clone();
+        }
+        let words = self
+            .words
+            .lock()
+            .unwrap()
+            .entry(tokenizer)
+            .or_insert_with(|| {
+                let words = self.documents.iter().map(|d| model.tokenize(&d.text));
+                Arc::new(words.collect())
+            })
+            .clone();
+        let token_ids: Arc<TokenIds> = Arc::new(
+            words
+                .iter()
+                .map(|words| {
+                    words
+                        .iter()
+                        .filter_map(|word| model.vocab.get(word).copied())
+                        .collect()
+                })
+                .collect(),
+        );
+        self.token_ids
+            .lock()
+            .unwrap()
+            .insert(key, token_ids.clone());
+        token_ids
+    }
+}
+
+/// Hash of the vocabulary words of `model` in token order.
+fn fingerprint(model: &NaiveBayes) -> u64 {
+    let mut words = vec![""; model.vocab.len()];
+    for (word, token) in &model.vocab {
+        words[*token] = word;
+    }
+    let mut hasher = DefaultHasher::new();
+    words.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    #[test]
+    fn test_metrics_by_facet() {
+        let model = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.7, -0.7],
+            vec![vec![(0, -0.1), (1, -5.0)], vec![(0, -5.0), (1, -0.1)]],
+            vec![-9.0, -9.0],
+        );
+        let tagged = |class: &str, text: &str, language: Option<&str>| {
+            let document = TaggedDocument::new(Document {
+                class: class.into(),
+                text: text.into(),
+            });
+            match language {
+                Some(language) => document.tag("language", language),
+                None => document,
+            }
+        };
+        let documents = [
+            tagged("pos", "good", Some("en")),
+            tagged("neg", "bad bad bad", Some("en")),
+            tagged("pos", "gut", Some("de")),
+            tagged("neg", "schlecht good", Some("de")),
+            tagged("pos", "good good", None),
+        ];
+        let facets = [
+            Facet::Metadata("language".into()),
+            Facet::Length(vec![2, 3]),
+        ];
+
+        let report = evaluate_slices(&model, &documents, &facets);
+        assert_eq!(report.overall.documents, 5);
+        assert_eq!(report.overall.accuracy(), 0.6);
+        let languages: Vec<(&str, f64)> = report
+            .facet("language")
+            .map(|(value, metrics)| (value, metrics.accuracy()))
+            .collect();
+        assert_eq!(languages, [("(none)", 1.0), ("de", 0.0), ("en", 1.0)]);
+        let lengths: Vec<(&str, usize)> = report
+            .facet("length")
+            .map(|(value, metrics)| (value, metrics.documents))
+            .collect();
+        assert_eq!(lengths, [("2", 2), ("<2", 2), (">=3", 1)]);
+
+        // "gut" ties and goes to neg, "schlecht good" is predicted pos
+        let german = &report.slices[&("language".to_owned(), "de".to_owned())];
+        assert_eq!(german.per_class["neg"], (0, 1, 1));
+        assert_eq!(german.macro_f1(), 0.0);
+    }
+
+    #[test]
+    fn test_cache_matches_direct_evaluation() {
+        let documents: Vec<Document> = [
+            ("pos", "good movie"),
+            ("neg", "bad movie"),
+            ("pos", "good good plot"),
+            ("neg", "bad plot"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: std::collections::HashSet<Class> =
+            documents.iter().map(|d| d.class.clone()).collect();
+        let model = NaiveBayes::new(&documents, classes.clone(), crate::vocabulary(&documents));
+        let mut smoothed = model.clone();
+        smoothed.set_alpha(0.1);
+        let test: Vec<Document> = [("pos", "good plot twist"), ("neg", "bad bad movie")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+
+        let cache = EvaluationCache::new(test.clone());
+        let options = PredictOptions {
+            token_budget: Some(crate::TokenBudget::First(2)),
+            ..Default::default()
+        };
+        for model in [&model, &smoothed] {
+            let mut scratch = Scratch::default();
+            let expected: Vec<Vec<f64>> = test
+                .iter()
+                .map(|d| model.scores_into(d, &options, &mut scratch).to_vec())
+                .collect();
+            assert_eq!(cache.scores(model, &options), expected);
+            assert_eq!(cache.evaluate(model).accuracy(), 1.0);
+        }
+        // both variants share a vocabulary
+        assert_eq!(cache.vocabularies(), 1);
+
+        let narrow = NaiveBayes::new(
+            &documents,
+            classes.clone(),
+            ["good".into(), "bad".into()].into(),
+        );
+        assert_eq!(cache.evaluate(&narrow).documents, 2);
+        assert_eq!(cache.vocabularies(), 2);
+
+        // tokenizers without a spec are told apart even when dropped, as in
+        // a grid search, one taking the first word only
+        for i in 0..8 {
+            let first_only = i % 2 == 1;
+            let tokenizer = move |text: &str| -> Vec<String> {
+                let words = text.split_whitespace().map(String::from);
+                words
+                    .take(if first_only { 1 } else { usize::MAX })
+                    .collect()
+            };
+            let model = NaiveBayes::new_with_tokenizer(
+                &documents,
+                classes.clone(),
+                crate::vocabulary(&documents),
+                tokenizer,
+            );
+            let mut scratch = Scratch::default();
+            let expected: Vec<Vec<f64>> = test
+                .iter()
+                .map(|d| model.scores_into(d, &options, &mut scratch).to_vec())
+                .collect();
+            assert_eq!(cache.scores(&model, &options), expected);
+        }
+        assert_eq!(cache.vocabularies(), 2 + 8);
+    }
+}
@@ -0,0 +1,123 @@
+use std::hash::Hash;
+
+use crate::{
+    best_class_ids, classifier::softmax, Class, Document, NaiveBayes, PredictOptions, Scratch,
+};
+
+/// What [`MinEvidence`] does with a document that has too few known tokens.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EvidenceFallback {
+    /// Predict from the prior alone.
+    #[default]
+    Prior,
+    /// Predict no class.
+    Abstain,
+    /// Scale the likelihoods of the known tokens by this weight in `(0, 1]`,
+    /// widening the smoothing so the posterior stays close to the prior.
+    Dampen(f64),
+}
+
+/// The number of known tokens below which a document is not classified on
+/// its words as usual.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinEvidence {
+    pub tokens: usize,
+    pub fallback: EvidenceFallback,
+}
+
+/// A prediction together with the evidence it rests on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prediction<L = Class> {
+    /// The best class, `None` when the model abstained.
+    pub class: Option<L>,
+    /// Posterior probability of `class`, 0 when abstaining.
+    pub probability: f64,
+    /// Occurrences of in-vocabulary tokens in the document.
+    pub known_tokens: usize,
+    /// The fallback applied because the document had too few known tokens.
+    pub fallback: Option<EvidenceFallback>,
+}
+
+impl<L: Clone + Eq + Hash> NaiveBayes<L> {
+    /// Like [`NaiveBayes::predict_with`], reporting the posterior of the
+    /// predicted class and how much evidence it rests on. Of tied classes,
+    /// the first chosen by `options.ties` is predicted.
+    pub fn predict_detailed(&self, document: &Document, options: &PredictOptions) -> Prediction<L> {
+        self.predict_detailed_into(document, options, &mut Scratch::default())
+    }
+
+    /// [`NaiveBayes::predict_detailed`] reusing the buffers of `scratch`.
+    pub(crate) fn predict_detailed_into(
+        &self,
+        document: &Document,
+        options: &PredictOptions,
+        scratch: &mut Scratch,
+    ) -> Prediction<L> {
+        self.scores_into(document, options, scratch);
+        let mut prediction = Prediction {
+            class: None,
+            probability: 0.0,
+            known_tokens: scratch.known_tokens,
+            fallback: scratch.fallback,
+        };
+        if scratch.fallback == Some(EvidenceFallback::Abstain) {
+            return prediction;
+        }
+        if let Some(&class) = best_class_ids(&scratch.scores, options.ties).first() {
+            prediction.class = Some(self.classes[class].clone());
+            prediction.probability = softmax(&scratch.scores)[class];
+        }
+        prediction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_evidence_fallbacks() {
+        let model = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.2, -1.7],
+            vec![vec![(0, -0.1), (1, -5.0)], vec![(0, -5.0), (1, -0.1)]],
+            vec![-9.0, -9.0],
+        );
+        let document = |text: &str| Document {
+            class: String::new(),
+            text: text.into(),
+        };
+        let with = |fallback| PredictOptions {
+            min_evidence: Some(MinEvidence {
+                tokens: 2,
+                fallback,
+            }),
+            ..Default::default()
+        };
+
+        // a single word is enough to be confident without a minimum
+        let plain = model.predict_detailed(&document("good unknown"), &Default::default());
+        assert_eq!(plain.class.as_deref(), Some("pos"));
+        assert_eq!((plain.known_tokens, plain.fallback), (1, None));
+        assert!(plain.probability > 0.95);
+
+        let prior = model.predict_detailed(&document("good"), &with(EvidenceFallback::Prior));
+        assert_eq!(prior.class.as_deref(), Some("neg"));
+        assert_eq!(prior.fallback, Some(EvidenceFallback::Prior));
+
+        let abstain = with(EvidenceFallback::Abstain);
+        let abstained = model.predict_detailed(&document("good"), &abstain);
+        assert_eq!((abstained.class, abstained.probability), (None, 0.0));
+        assert!(model.predict_with(&document("good"), &abstain).is_empty());
+        assert_eq!(
+            model.predict_with(&document("good good"), &abstain),
+            ["pos"]
+        );
+
+        let dampened =
+            model.predict_detailed(&document("good"), &with(EvidenceFallback::Dampen(0.1)));
+        assert_eq!(dampened.class.as_deref(), Some("neg"));
+        assert!(dampened.probability < 0.95);
+    }
+}
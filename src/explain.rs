@@ -0,0 +1,138 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Class, Document, NaiveBayes, Tokenizer, Word};
+
+/// What the occurrences of one vocabulary word add to the score of a class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contribution {
+    pub word: Word,
+    pub occurrences: usize,
+    /// Log-likelihood of the word times its weight in the document, its
+    /// number of occurrences or its TF-IDF weight.
+    pub log_likelihood: f64,
+    /// `log_likelihood` minus its mean over all classes: positive words
+    /// speak for the class, negative ones against it.
+    pub impact: f64,
+}
+
+/// The score of one class for a document, term by term.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassExplanation<L = Class> {
+    pub class: L,
+    pub log_prior: f64,
+    /// Every vocabulary word of the document, highest impact first.
+    pub contributions: Vec<Contribution>,
+    /// The log prior plus every contribution, as scored by
+    /// [`NaiveBayes::predict_scores`].
+    pub score: f64,
+}
+
+impl<L: Clone + Eq + Hash> NaiveBayes<L> {
+    /// Why `document` scores as it does for every class, in the order of
+    /// [`NaiveBayes::classes`], with the default prediction options. Words
+    /// outside the vocabulary contribute nothing and are left out.
+    pub fn explain(&self, document: &Document) -> Vec<ClassExplanation<L>> {
+        let mut occurrences: HashMap<usize, (&str, usize)> = HashMap::new();
+        self.tokenizer.for_each_token(&document.text, &mut |word| {
+            if let Some((word, &token)) = self.vocab.get_key_value(word) {
+                occurrences.entry(token).or_insert((word, 0)).1 += 1;
+            }
+        });
+        // TF-IDF weights are scaled to unit length, as in scoring
+        let tf_idf = self.idf.as_ref().map(|idf| {
+            let norm = occurrences
+                .iter()
+                .map(|(token, (_, count))| (*count as f64 * idf[*token]).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            (idf, norm)
+        });
+        let weight = |token: usize, count: usize| match tf_idf {
+            Some((idf, norm)) => count as f64 * idf[token] / norm,
+            None => count as f64,
+        };
+
+        let classes = self.classes.len();
+        let terms: Vec<(&str, usize, Vec<f64>)> = occurrences
+            .iter()
+            .map(|(&token, &(word, count))| {
+                let weight = weight(token, count);
+                let log_likelihoods = (0..classes)
+                    .map(|class| weight * self.likelihood.get(class, token))
+                    .collect();
+                (word, count, log_likelihoods)
+            })
+            .collect();
+        (0..classes)
+            .map(|class| {
+                let mut contributions: Vec<Contribution> = terms
+                    .iter()
+                    .map(|(word, occurrences, log_likelihoods)| {
+                        let mean = log_likelihoods.iter().sum::<f64>() / classes as f64;
+                        Contribution {
+                            word: word.to_string(),
+                            occurrences: *occurrences,
+                            log_likelihood: log_likelihoods[class],
+                            impact: log_likelihoods[class] - mean,
+                        }
+                    })
+                    .collect();
+                contributions.sort_by(|a, b| {
+                    b.impact
+                        .total_cmp(&a.impact)
+                        .then_with(|| a.word.cmp(&b.word))
+                });
+                let log_prior = self.log_prior[class];
+                ClassExplanation {
+                    class: self.classes[class].clone(),
+                    log_prior,
+                    score: log_prior + contributions.iter().map(|c| c.log_likelihood).sum::<f64>(),
+                    contributions,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Classifier;
+
+    #[test]
+    fn test_explain_adds_up_to_the_scores() {
+        let model = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap", "meeting", "now"],
+            vec![-0.3, -1.4],
+            vec![
+                vec![(0, -6.0), (1, -1.2), (2, -2.0)],
+                vec![(0, -1.1), (1, -5.5), (2, -2.0)],
+            ],
+            vec![-8.0, -8.0],
+        );
+        let document = Document {
+            class: String::new(),
+            text: "cheap now cheap unknown".into(),
+        };
+        let explanation = model.explain(&document);
+        let spam = &explanation[1];
+        assert_eq!(spam.class, "spam");
+        assert_eq!(spam.log_prior, -1.4);
+        let words: Vec<(&str, usize)> = spam
+            .contributions
+            .iter()
+            .map(|c| (c.word.as_str(), c.occurrences))
+            .collect();
+        // "meeting" is absent, "now" is as likely in both classes
+        assert_eq!(words, [("cheap", 2), ("now", 1)]);
+        assert_eq!(spam.contributions[0].log_likelihood, -2.2);
+        assert!((spam.contributions[0].impact - 4.9).abs() < 1e-12);
+        assert_eq!(spam.contributions[1].impact, 0.0);
+        assert_eq!(explanation[0].contributions[0].word, "now");
+
+        for (explained, score) in explanation.iter().zip(model.scores(&document)) {
+            assert!((explained.score - score).abs() < 1e-12);
+        }
+    }
+}
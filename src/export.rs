@@ -0,0 +1,195 @@
+use std::fmt::Write;
+
+use crate::{classifier::argmax, tf_idf::document_weights, Document, NaiveBayes, Tokenizer};
+
+/// A model compiled into the binary, as generated by
+/// [`NaiveBayes::to_rust_source`]. All tables are `'static`, so embedding one
+/// costs no parsing or allocation at startup.
+pub struct StaticModel {
+    pub classes: &'static [&'static str],
+    /// Vocabulary in sorted order; a word's index is its token id.
+    pub words: &'static [&'static str],
+    pub log_prior: &'static [f64],
+    /// Row `token` holds the log-likelihood of every class.
+    pub likelihoods: &'static [f64],
+    /// Idf by token id of a TF-IDF weighted model.
+    pub idf: Option<&'static [f64]>,
+}
+
+impl StaticModel {
+    pub fn token(&self, word: &str) -> Option<usize> {
+        self.words.binary_search(&word).ok()
+    }
+
+    /// Log prior plus summed likelihoods of every class, TF-IDF weighted if
+    /// the model is, skipping words outside the vocabulary.
+    pub fn scores<'a>(&self, words: impl IntoIterator<Item = &'a str>) -> Vec<f64> {
+        let classes = self.classes.len();
+        let mut scores = self.log_prior.to_vec();
+        let tokens = words.into_iter().filter_map(|word| self.token(word));
+        for (token, weight) in document_weights(self.idf, tokens) {
+            let row = &self.likelihoods[token * classes..][..classes];
+            for (score, likelihood) in scores.iter_mut().zip(row) {
+                *score += weight * likelihood;
+            }
+        }
+        scores
+    }
+
+    /// The best scoring class of `document` split by `tokenizer`, which must
+    /// be the tokenizer of the exported model, ties going to the one listed
+    /// first.
+    pub fn predict(&self, document: &Document, tokenizer: &dyn Tokenizer) -> Option<&'static str> {
+        let tokens = tokenizer.tokenize(&document.text);
+        let scores = self.scores(tokens.iter().map(String::as_str));
+        Some(self.classes[argmax(&scores)?])
+    }
+}
+
+impl NaiveBayes {
+    /// Rust source declaring `pub static <name>: StaticModel` with the
+    /// parameters of this model, for embedding with `include!`. The
+    /// including module must have [`StaticModel`] in scope.
+    pub fn to_rust_source(&self, name: &str) -> String {
+        let mut words: Vec<(&str, usize)> = self
+            .vocab
+            .iter()
+            .map(|(word, token)| (word.as_str(), *token))
+            .collect();
+        words.sort_unstable();
+
+        let mut source = String::new();
+        writeln!(
+            source,
+            "// generated by NaiveBayes::to_rust_source, do not edit"
+        )
+        .unwrap();
+        writeln!(source, "pub static {}: StaticModel = StaticModel {{", name).unwrap();
+        let strings = |items: &mut dyn Iterator<Item = &str>| {
+            items.map(|s| format!("{:?}", s)).collect::<Vec<_>>()
+        };
+        write_list(
+            &mut source,
+            "classes",
+            strings(&mut self.classes.iter().map(|c| c.as_str())),
+        );
+        write_list(
+            &mut source,
+            "words",
+            strings(&mut words.iter().map(|(w, _)| *w)),
+        );
+        write_list(
+            &mut source,
+            "log_prior",
+            self.log_prior.iter().map(|v| float(*v)).collect(),
+        );
+        let likelihoods = words
+            .iter()
+            .flat_map(|(_, token)| {
+                (0..self.classes.len()).map(move |class| float(self.likelihood.get(class, *token)))
+            })
+            .collect();
+        write_list(&mut source, "likelihoods", likelihoods);
+        match &self.idf {
+            Some(idf) => {
+                writeln!(source, "    idf: Some(&[").unwrap();
+                for (_, token) in &words {
+                    writeln!(source, "        {},", float(idf[*token])).unwrap();
+                }
+                writeln!(source, "    ]),").unwrap();
+            }
+            None => writeln!(source, "    idf: None,").unwrap(),
+        }
+        writeln!(source, "}};").unwrap();
+        source
+    }
+}
+
+fn write_list(source: &mut String, field: &str, items: Vec<String>) {
+    writeln!(source, "    {}: &[", field).unwrap();
+    for item in items {
+        writeln!(source, "        {},", item).unwrap();
+    }
+    writeln!(source, "    ],").unwrap();
+}
+
+/// A float literal that reads back as exactly `value`.
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "f64::NAN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 {
+            "f64::INFINITY"
+        } else {
+            "f64::NEG_INFINITY"
+        }
+        .to_string()
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_model_matches_source() {
+        let model = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["good", "bad"],
+            vec![-0.5, -1.0],
+            vec![
+                vec![(0, -2.0), (1, -0.5)],
+                vec![(0, -0.25), (1, f64::NEG_INFINITY)],
+            ],
+            vec![-5.0, -5.0],
+        );
+        let source = model.to_rust_source("MODEL");
+        assert!(source.contains("pub static MODEL: StaticModel = StaticModel {"));
+        assert!(source.contains("        \"bad\",\n        \"good\",\n"));
+        assert!(source.contains("        -0.5,\n        f64::NEG_INFINITY,\n"));
+
+        // what the generated source declares
+        static MODEL: StaticModel = StaticModel {
+            classes: &["neg", "pos"],
+            words: &["bad", "good"],
+            log_prior: &[-0.5, -1.0],
+            likelihoods: &[-0.5, f64::NEG_INFINITY, -2.0, -0.25],
+            idf: None,
+        };
+        let document = Document {
+            class: String::new(),
+            text: "good unknown good".into(),
+        };
+        assert_eq!(MODEL.scores(document.words()), [-4.5, -1.5]);
+        assert_eq!(
+            MODEL.predict(&document, &model.tokenizer),
+            model.predict(&document).as_deref()
+        );
+        assert!(source.ends_with("    idf: None,\n};\n"));
+
+        let mut tf_idf = model.clone();
+        tf_idf.idf = Some(vec![1.5, 2.0]);
+        let source = tf_idf.to_rust_source("MODEL");
+        assert!(source.contains("    idf: Some(&[\n        2.0,\n        1.5,\n    ]),\n"));
+        static TF_IDF: StaticModel = StaticModel {
+            classes: &["neg", "pos"],
+            words: &["bad", "good"],
+            log_prior: &[-0.5, -1.0],
+            likelihoods: &[-0.5, f64::NEG_INFINITY, -2.0, -0.25],
+            idf: Some(&[2.0, 1.5]),
+        };
+        let document = Document {
+            class: String::new(),
+            text: "good bad good".into(),
+        };
+        for (score, expected) in TF_IDF
+            .scores(document.words())
+            .iter()
+            .zip(crate::Classifier::scores(&tf_idf, &document))
+        {
+            assert!(score == &expected || (score - expected).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{counts::Counts, Class, NaiveBayes, PredictOptions, Scratch, TiePolicy};
+
+/// A training document given as features instead of text, such as
+/// pre-hashed tokens, ids of an external vocabulary or byte n-grams.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureDocument<F> {
+    pub class: Class,
+    pub features: Vec<F>,
+}
+
+impl<F: Eq + Hash + Sync> NaiveBayes<Class, F> {
+    /// Trains on documents given as features, which are counted as they are
+    /// without a tokenizer. Like [`NaiveBayes::new`], the input is not
+    /// validated.
+    pub fn from_features(
+        documents: &[FeatureDocument<F>],
+        classes: HashSet<Class>,
+        vocab: HashSet<F>,
+    ) -> Self {
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+        let vocab: HashMap<F, usize> = vocab
+            .into_iter()
+            .enumerate()
+            .map(|(token, feature)| (feature, token))
+            .collect();
+
+        let counts = Counts::new_features(documents, &classes, &vocab);
+        Self::from_counts(vocab, classes, counts, documents.len(), &mut ())
+    }
+}
+
+impl<L: Clone + Eq + Hash, F: Eq + Hash> NaiveBayes<L, F> {
+    /// Log prior plus summed likelihoods of every class for a document given
+    /// as `features`, in the order of [`NaiveBayes::classes`]. Features
+    /// outside the vocabulary are ignored.
+    pub fn feature_scores(&self, features: &[F]) -> Vec<f64> {
+        let mut scratch = Scratch::default();
+        scratch.tokens.extend(
+            features
+                .iter()
+                .filter_map(|feature| self.vocab.get(feature).copied()),
+        );
+        self.score_tokens(|_, _, _| {}, &PredictOptions::default(), &mut scratch)
+            .to_vec()
+    }
+
+    /// The class with the highest posterior for a document given as
+    /// `features`, ties going to the class listed first.
+    pub fn predict_features(&self, features: &[F]) -> Option<L> {
+        self.best_classes(&self.feature_scores(features), TiePolicy::First)
+            .pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{vocabulary, Document};
+
+    #[test]
+    fn test_prehashed_features_train_like_words() {
+        let texts = [
+            ("spam", "win cash now"),
+            ("ham", "lunch now"),
+            ("ham", "cash lunch"),
+        ];
+        let documents: Vec<Document> = texts
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let hash = |word: &str| {
+            word.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x100_0000_01b3)
+            })
+        };
+        let hashed: Vec<FeatureDocument<u64>> = documents
+            .iter()
+            .map(|d| FeatureDocument {
+                class: d.class.clone(),
+                features: d.words().into_iter().map(hash).collect(),
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = vocabulary(&documents);
+
+        let words = NaiveBayes::new(&documents, classes.clone(), vocab.clone());
+        let features = NaiveBayes::from_features(
+            &hashed,
+            classes,
+            vocab.iter().map(|word| hash(word)).collect(),
+        );
+        let unseen = Document {
+            class: String::new(),
+            text: "cash now unknown".into(),
+        };
+        let unseen_features: Vec<u64> = unseen.words().into_iter().map(hash).collect();
+        let expected = crate::Classifier::scores(&words, &unseen);
+        for (a, b) in features
+            .feature_scores(&unseen_features)
+            .iter()
+            .zip(&expected)
+        {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert_eq!(
+            features.predict_features(&unseen_features),
+            words.predict(&unseen)
+        );
+    }
+}
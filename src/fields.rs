@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::{tf_idf::document_weights, Class, Document, NaiveBayes, TiePolicy, Tokenizer};
+
+/// A document made of named fields such as a title, a body and tags, each
+/// tokenized on its own.
+#[derive(Clone, Debug, Default)]
+pub struct FieldedDocument {
+    pub class: Class,
+    /// Field names and their text, in order.
+    pub fields: Vec<(String, String)>,
+}
+
+impl FieldedDocument {
+    pub fn new(class: &str) -> Self {
+        Self {
+            class: class.to_owned(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn field(mut self, name: &str, text: &str) -> Self {
+        self.fields.push((name.to_owned(), text.to_owned()));
+        self
+    }
+
+    /// The words of every field in one document, for training.
+    pub fn flatten(&self) -> Document {
+        let words: Vec<&str> = self
+            .fields
+            .iter()
+            .flat_map(|(_, text)| text.split_whitespace())
+            .collect();
+        Document {
+            class: self.class.clone(),
+            text: words.join(" "),
+        }
+    }
+}
+
+/// How much every occurrence of a word counts in each field.
+#[derive(Clone, Debug)]
+pub struct FieldWeights {
+    pub weights: HashMap<String, f64>,
+    /// Weight of fields missing from `weights`.
+    pub default: f64,
+}
+
+impl Default for FieldWeights {
+    fn default() -> Self {
+        Self {
+            weights: HashMap::new(),
+            default: 1.0,
+        }
+    }
+}
+
+impl FieldWeights {
+    pub fn weight(&self, field: &str) -> f64 {
+        self.weights.get(field).copied().unwrap_or(self.default)
+    }
+}
+
+impl NaiveBayes {
+    /// Log prior plus the likelihoods of every field's words scaled by the
+    /// field's weight, in the order of [`NaiveBayes::classes`]. Every field
+    /// is split by the model's tokenizer and, in TF-IDF weighted models,
+    /// weighted as a document of its own.
+    pub fn field_scores(&self, document: &FieldedDocument, weights: &FieldWeights) -> Vec<f64> {
+        let mut scores = vec![0.0; self.classes.len()];
+        let mut tokens = Vec::new();
+        for (field, text) in &document.fields {
+            let weight = weights.weight(field);
+            if weight == 0.0 {
+                continue;
+            }
+            tokens.clear();
+            self.tokenizer.for_each_token(text, &mut |word| {
+                tokens.extend(self.vocab.get(word).copied())
+            });
+            for (token, occurrences) in document_weights(self.idf.as_deref(), tokens.drain(..)) {
+                self.likelihood
+                    .add_token(token, weight * occurrences, &mut scores);
+            }
+        }
+        for (score, prior) in scores.iter_mut().zip(&self.log_prior) {
+            *score += prior;
+        }
+        scores
+    }
+
+    /// The best scoring class of `document` under `weights`, ties going to
+    /// the first class.
+    pub fn predict_fields(
+        &self,
+        document: &FieldedDocument,
+        weights: &FieldWeights,
+    ) -> Option<Class> {
+        self.best_classes(&self.field_scores(document, weights), TiePolicy::First)
+            .pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_weights_scale_evidence() {
+        let model = NaiveBayes::from_parts(
+            &["sports", "tech"],
+            &["chip", "match", "score"],
+            vec![-0.7, -0.7],
+            vec![
+                vec![(0, -3.0), (1, -1.0), (2, -1.0)],
+                vec![(0, -0.5), (1, -3.0), (2, -2.0)],
+            ],
+            vec![-5.0, -5.0],
+        );
+        let document = FieldedDocument::new("tech")
+            .field("title", "chip")
+            .field("body", "match score");
+        assert_eq!(document.flatten().text, "chip match score");
+
+        let flat = FieldWeights::default();
+        assert_eq!(
+            model.field_scores(&document, &flat),
+            model.token_scorer().score_batch(&[vec![0, 1, 2]])[0]
+        );
+        assert_eq!(
+            model.predict_fields(&document, &flat),
+            Some("sports".into())
+        );
+
+        let title = FieldWeights {
+            weights: HashMap::from([("title".to_string(), 3.0)]),
+            ..Default::default()
+        };
+        let scores = model.field_scores(&document, &title);
+        assert_eq!(scores, vec![-0.7 - 9.0 - 2.0, -0.7 - 1.5 - 5.0]);
+        assert_eq!(model.predict_fields(&document, &title), Some("tech".into()));
+    }
+
+    #[test]
+    fn test_fields_use_the_model_tokenizer() {
+        let documents = [
+            Document {
+                class: "sports".into(),
+                text: "match,score".into(),
+            },
+            Document {
+                class: "tech".into(),
+                text: "chip,score".into(),
+            },
+        ];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let commas = |text: &str| -> Vec<String> { text.split(',').map(String::from).collect() };
+        let vocab = ["chip", "match", "score"].map(String::from).into();
+        let model = NaiveBayes::new_with_tokenizer(&documents, classes, vocab, commas);
+
+        let document = FieldedDocument::new("")
+            .field("title", "chip,score")
+            .field("tags", "match");
+        let expected = crate::Classifier::scores(
+            &model,
+            &Document {
+                class: String::new(),
+                text: "chip,score,match".into(),
+            },
+        );
+        let scores = model.field_scores(&document, &FieldWeights::default());
+        for (score, expected) in scores.iter().zip(&expected) {
+            assert!((score - expected).abs() < 1e-12);
+        }
+
+        // a lone field is weighted like a document of its own
+        let (fitted, _) = crate::NaiveBayesBuilder::new()
+            .documents(&documents)
+            .tokenizer(commas)
+            .tf_idf(true)
+            .fit()
+            .unwrap();
+        let tf_idf = fitted.into_model();
+        let field = FieldedDocument::new("").field("body", "match,match,chip");
+        let expected = crate::Classifier::scores(&tf_idf, &field.flatten());
+        let scores = tf_idf.field_scores(&field, &FieldWeights::default());
+        for (score, expected) in scores.iter().zip(&expected) {
+            assert!((score - expected).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt;
+
+use crate::{
+    classifier::{argmax, softmax},
+    Class,
+};
+
+/// Default share of the largest feature variance added to every variance,
+/// as in scikit-learn's `GaussianNB`.
+pub const DEFAULT_VAR_SMOOTHING: f64 = 1e-9;
+
+/// A labelled vector of continuous features.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    pub class: Class,
+    pub features: Vec<f64>,
+}
+
+/// Why a Gaussian model could not be fitted.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GaussianError {
+    /// No training samples at all.
+    Empty,
+    /// Sample `sample` has a different number of features than the first.
+    DimensionMismatch {
+        sample: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// Sample `sample` has a NaN or infinite feature.
+    NonFinite { sample: usize },
+    /// A variance smoothing that is negative or not finite.
+    InvalidSmoothing,
+}
+
+impl fmt::Display for GaussianError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GaussianError::Empty => write!(f, "there are no training samples"),
+            GaussianError::DimensionMismatch {
+                sample,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "sample {} has {} features instead of {}",
+                sample, actual, expected
+            ),
+            GaussianError::NonFinite { sample } => {
+                write!(f, "sample {} has a feature that is not finite", sample)
+            }
+            GaussianError::InvalidSmoothing => {
+                write!(f, "variance smoothing must be finite and at least 0")
+            }
+        }
+    }
+}
+
+impl std::error::Error for GaussianError {}
+
+/// Naive Bayes over continuous features, each normally distributed within
+/// every class with its own mean and variance.
+#[derive(Clone, Debug)]
+pub struct GaussianNaiveBayes {
+    classes: Vec<Class>,
+    log_prior: Vec<f64>,
+    /// Mean and variance of every feature, by class.
+    means: Vec<Vec<f64>>,
+    variances: Vec<Vec<f64>>,
+}
+
+impl GaussianNaiveBayes {
+    /// Fits with the [`DEFAULT_VAR_SMOOTHING`].
+    pub fn fit(samples: &[Sample]) -> Result<Self, GaussianError> {
+        Self::fit_with(samples, DEFAULT_VAR_SMOOTHING)
+    }
+
+    /// Fits every class of `samples`, adding `var_smoothing` times the
+    /// largest variance of any feature over all samples to every variance,
+    /// so that constant features do not give infinite densities.
+    pub fn fit_with(samples: &[Sample], var_smoothing: f64) -> Result<Self, GaussianError> {
+        if !(var_smoothing.is_finite() && var_smoothing >= 0.0) {
+            return Err(GaussianError::InvalidSmoothing);
+        }
+        let dimensions = samples.first().ok_or(GaussianError::Empty)?.features.len();
+        for (i, sample) in samples.iter().enumerate() {
+            if sample.features.len() != dimensions {
+                return Err(GaussianError::DimensionMismatch {
+                    sample: i,
+                    expected: dimensions,
+                    actual: sample.features.len(),
+                });
+            }
+            if !sample.features.iter().all(|x| x.is_finite()) {
+                return Err(GaussianError::NonFinite { sample: i });
+            }
+        }
+
+        let mut classes: Vec<Class> = samples.iter().map(|s| s.class.clone()).collect();
+        classes.sort_unstable();
+        classes.dedup();
+        let mut sizes = vec![0usize; classes.len()];
+        let mut means = vec![vec![0.0; dimensions]; classes.len()];
+        let mut variances = vec![vec![0.0; dimensions]; classes.len()];
+        let class_of = |sample: &Sample| classes.binary_search(&sample.class).unwrap();
+        for sample in samples {
+            let class = class_of(sample);
+            sizes[class] += 1;
+            for (mean, x) in means[class].iter_mut().zip(&sample.features) {
+                *mean += x;
+            }
+        }
+        for (mean, size) in means.iter_mut().zip(&sizes) {
+            mean.iter_mut().for_each(|m| *m /= *size as f64);
+        }
+        for sample in samples {
+            let class = class_of(sample);
+            for ((variance, mean), x) in variances[class]
+                .iter_mut()
+                .zip(&means[class])
+                .zip(&sample.features)
+            {
+                *variance += (x - mean).powi(2);
+            }
+        }
+        for (variance, size) in variances.iter_mut().zip(&sizes) {
+            variance.iter_mut().for_each(|v| *v /= *size as f64);
+        }
+
+        let epsilon = var_smoothing * Self::max_variance(samples, dimensions);
+        for variance in variances.iter_mut().flatten() {
+            *variance += epsilon;
+        }
+        let log_prior = sizes
+            .iter()
+            .map(|size| (*size as f64 / samples.len() as f64).ln())
+            .collect();
+        Ok(Self {
+            classes,
+            log_prior,
+            means,
+            variances,
+        })
+    }
+
+    /// Largest variance of any feature over all samples.
+    fn max_variance(samples: &[Sample], dimensions: usize) -> f64 {
+        let n = samples.len() as f64;
+        (0..dimensions)
+            .map(|feature| {
+                let mean = samples.iter().map(|s| s.features[feature]).sum::<f64>() / n;
+                samples
+                    .iter()
+                    .map(|s| (s.features[feature] - mean).powi(2))
+                    .sum::<f64>()
+                    / n
+            })
+            .fold(0.0, f64::max)
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    pub fn log_prior(&self) -> &[f64] {
+        &self.log_prior
+    }
+
+    /// Mean of every feature within the class at `class`.
+    pub fn means(&self, class: usize) -> &[f64] {
+        &self.means[class]
+    }
+
+    /// Smoothed variance of every feature within the class at `class`.
+    pub fn variances(&self, class: usize) -> &[f64] {
+        &self.variances[class]
+    }
+
+    /// Log joint probability of every class and `features`, in the order of
+    /// [`GaussianNaiveBayes::classes`].
+    ///
+    /// # Panics
+    /// If `features` has a different length than the training samples.
+    pub fn scores(&self, features: &[f64]) -> Vec<f64> {
+        assert_eq!(
+            features.len(),
+            self.means[0].len(),
+            "wrong number of features"
+        );
+        self.log_prior
+            .iter()
+            .zip(self.means.iter().zip(&self.variances))
+            .map(|(log_prior, (means, variances))| {
+                log_prior
+                    + features
+                        .iter()
+                        .zip(means.iter().zip(variances))
+                        .map(|(x, (mean, variance))| {
+                            -0.5 * ((2.0 * std::f64::consts::PI * variance).ln()
+                                + (x - mean).powi(2) / variance)
+                        })
+                        .sum::<f64>()
+            })
+            .collect()
+    }
+
+    /// Posterior probability of every class given `features`.
+    pub fn probabilities(&self, features: &[f64]) -> Vec<f64> {
+        softmax(&self.scores(features))
+    }
+
+    /// The most probable class of `features`, ties going to the one listed
+    /// first.
+    pub fn predict(&self, features: &[f64]) -> Option<Class> {
+        let best = argmax(&self.scores(features))?;
+        Some(self.classes[best].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gaussian_densities() {
+        let sample = |class: &str, features: &[f64]| Sample {
+            class: class.into(),
+            features: features.to_vec(),
+        };
+        let samples = [
+            sample("adult", &[180.0, 80.0]),
+            sample("adult", &[170.0, 70.0]),
+            sample("adult", &[160.0, 60.0]),
+            sample("child", &[120.0, 25.0]),
+            sample("child", &[110.0, 20.0]),
+        ];
+        let model = GaussianNaiveBayes::fit_with(&samples, 0.0).unwrap();
+        assert_eq!(model.classes(), ["adult", "child"]);
+        assert_eq!(model.means(0), [170.0, 70.0]);
+        assert_eq!(model.variances(1), [25.0, 6.25]);
+
+        let density = |x: f64, mean: f64, variance: f64| {
+            (-(x - mean).powi(2) / (2.0 * variance)).exp()
+                / (2.0 * std::f64::consts::PI * variance).sqrt()
+        };
+        let expected = 0.4f64.ln() + (density(115.0, 115.0, 25.0) * density(30.0, 22.5, 6.25)).ln();
+        assert!((model.scores(&[115.0, 30.0])[1] - expected).abs() < 1e-9);
+        assert_eq!(model.predict(&[175.0, 75.0]), Some("adult".into()));
+        assert_eq!(model.predict(&[115.0, 30.0]), Some("child".into()));
+        let probabilities = model.probabilities(&[150.0, 50.0]);
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // a constant feature is smoothed by the variance of the others
+        let constant = [sample("a", &[1.0, 0.0]), sample("b", &[1.0, 2.0])];
+        let smoothed = GaussianNaiveBayes::fit(&constant).unwrap();
+        assert_eq!(smoothed.variances(0), [1e-9, 1e-9]);
+        assert!(smoothed.scores(&[1.0, 0.0]).iter().all(|s| s.is_finite()));
+        assert_eq!(
+            GaussianNaiveBayes::fit(&[sample("a", &[1.0]), sample("b", &[1.0, 2.0])]).err(),
+            Some(GaussianError::DimensionMismatch {
+                sample: 1,
+                expected: 1,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            GaussianNaiveBayes::fit(&[]).err(),
+            Some(GaussianError::Empty)
+        );
+    }
+}
@@ -0,0 +1,309 @@
+//! Experimental batch scoring on the GPU through wgpu compute shaders.
+//!
+//! Scores are computed in `f32`, and infinite log-likelihoods are clamped to
+//! [`NEG_LIKELIHOOD_LIMIT`] since shaders are not required to handle
+//! infinities.
+
+use std::{borrow::Cow, collections::HashMap, fmt, sync::mpsc};
+
+use wgpu::util::DeviceExt;
+
+use crate::{Class, Document, NaiveBayes, SharedTokenizer, Tokenizer, Word};
+
+pub const NEG_LIKELIHOOD_LIMIT: f32 = -1e30;
+
+/// Largest number of invocations dispatched at once.
+const MAX_CELLS: usize = 65535 * WORKGROUP_SIZE;
+const WORKGROUP_SIZE: usize = 64;
+
+const SHADER: &str = r#"
+@group(0) @binding(0) var<storage, read> params: array<u32>;
+@group(0) @binding(1) var<storage, read> likelihoods: array<f32>;
+@group(0) @binding(2) var<storage, read> log_prior: array<f32>;
+@group(0) @binding(3) var<storage, read> offsets: array<u32>;
+@group(0) @binding(4) var<storage, read> tokens: array<u32>;
+@group(0) @binding(5) var<storage, read_write> scores: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let documents = params[0];
+    let classes = params[1];
+    let cell = id.x;
+    if (cell >= documents * classes) {
+        return;
+    }
+    let document = cell / classes;
+    let column = cell % classes;
+    var score = log_prior[column];
+    for (var i = offsets[document]; i < offsets[document + 1u]; i = i + 1u) {
+        score = score + likelihoods[tokens[i] * classes + column];
+    }
+    scores[cell] = score;
+}
+"#;
+
+#[derive(Debug)]
+pub enum GpuError {
+    NoAdapter,
+    /// TF-IDF weighted models, whose documents are not plain likelihood
+    /// sums, cannot be scored by the shader.
+    TfIdf,
+    Device(wgpu::RequestDeviceError),
+    Map(wgpu::BufferAsyncError),
+}
+
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GpuError::NoAdapter => write!(f, "no GPU adapter available"),
+            GpuError::TfIdf => write!(f, "TF-IDF weighted models cannot be scored on the GPU"),
+            GpuError::Device(error) => write!(f, "could not open GPU device: {}", error),
+            GpuError::Map(error) => write!(f, "could not read back scores: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for GpuError {}
+
+/// A model uploaded to the GPU, scoring batches of token-id documents.
+pub struct GpuScorer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    likelihoods: wgpu::Buffer,
+    log_prior: wgpu::Buffer,
+    classes: Vec<Class>,
+    tokens: HashMap<Word, u32>,
+    tokenizer: SharedTokenizer,
+}
+
+impl GpuScorer {
+    pub fn new(model: &NaiveBayes) -> Result<Self, GpuError> {
+        if model.is_tf_idf() {
+            return Err(GpuError::TfIdf);
+        }
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .ok_or(GpuError::NoAdapter)?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .map_err(GpuError::Device)?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("naive-bayes scoring"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("naive-bayes scoring"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let classes = model.classes.len();
+        let mut likelihoods = Vec::with_capacity(model.vocab.len() * classes);
+        for token in 0..model.vocab.len() {
+            for class in 0..classes {
+                likelihoods.push(clamp(model.likelihood.get(class, token)));
+            }
+        }
+        let log_prior: Vec<f32> = model.log_prior.iter().map(|v| clamp(*v)).collect();
+
+        Ok(Self {
+            likelihoods: storage(&device, &f32_bytes(&likelihoods)),
+            log_prior: storage(&device, &f32_bytes(&log_prior)),
+            device,
+            queue,
+            pipeline,
+            classes: model.classes.clone(),
+            tokenizer: model.tokenizer.clone(),
+            tokens: model
+                .vocab
+                .iter()
+                .map(|(word, token)| (word.clone(), *token as u32))
+                .collect(),
+        })
+    }
+
+    /// Classes in the order used by the score vectors.
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    /// Token ids of the in-vocabulary words of `document`.
+    pub fn token_ids(&self, document: &Document) -> Vec<u32> {
+        let mut ids = Vec::new();
+        self.tokenizer.for_each_token(&document.text, &mut |word| {
+            ids.extend(self.tokens.get(word).copied())
+        });
+        ids
+    }
+
+    /// Log prior plus summed likelihoods of every class, for each document.
+    pub fn score_batch(&self, documents: &[Vec<u32>]) -> Result<Vec<Vec<f32>>, GpuError> {
+        let classes = self.classes.len();
+        let chunk = (MAX_CELLS / classes.max(1)).max(1);
+        let mut scores = Vec::with_capacity(documents.len());
+        for documents in documents.chunks(chunk) {
+            let flat = self.score_chunk(documents)?;
+            scores.extend(flat.chunks(classes.max(1)).map(|row| row.to_vec()));
+        }
+        Ok(scores)
+    }
+
+    fn score_chunk(&self, documents: &[Vec<u32>]) -> Result<Vec<f32>, GpuError> {
+        let classes = self.classes.len();
+        let cells = documents.len() * classes;
+        if cells == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut offsets = Vec::with_capacity(documents.len() + 1);
+        let mut tokens = Vec::new();
+        offsets.push(0u32);
+        for document in documents {
+            tokens.extend_from_slice(document);
+            offsets.push(tokens.len() as u32);
+        }
+        // storage bindings may not be empty
+        tokens.push(0);
+
+        let params = storage(
+            &self.device,
+            &u32_bytes(&[documents.len() as u32, classes as u32]),
+        );
+        let offsets = storage(&self.device, &u32_bytes(&offsets));
+        let tokens = storage(&self.device, &u32_bytes(&tokens));
+        let size = (cells * std::mem::size_of::<f32>()) as u64;
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let layout = self.pipeline.get_bind_group_layout(0);
+        let buffers = [
+            &params,
+            &self.likelihoods,
+            &self.log_prior,
+            &offsets,
+            &tokens,
+            &output,
+        ];
+        let entries: Vec<wgpu::BindGroupEntry> = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &layout,
+            entries: &entries,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(cells.div_ceil(WORKGROUP_SIZE) as u32, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .expect("map callback dropped")
+            .map_err(GpuError::Map)?;
+
+        let scores = slice
+            .get_mapped_range()
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect();
+        staging.unmap();
+        Ok(scores)
+    }
+}
+
+fn clamp(value: f64) -> f32 {
+    (value as f32).max(NEG_LIKELIHOOD_LIMIT)
+}
+
+fn storage(device: &wgpu::Device, contents: &[u8]) -> wgpu::Buffer {
+    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents,
+        usage: wgpu::BufferUsages::STORAGE,
+    })
+}
+
+fn f32_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn u32_bytes(values: &[u32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpu_scores_match_cpu() {
+        let model = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap", "meeting", "now"],
+            vec![-0.3, -1.4],
+            vec![
+                vec![(0, -6.0), (1, -1.2), (2, -2.5)],
+                vec![(0, -1.1), (1, -5.5), (2, -2.0)],
+            ],
+            vec![-8.0, -8.0],
+        );
+        let mut tf_idf = model.clone();
+        tf_idf.idf = Some(vec![1.0; 3]);
+        assert!(matches!(GpuScorer::new(&tf_idf), Err(GpuError::TfIdf)));
+        let scorer = match GpuScorer::new(&model) {
+            Ok(scorer) => scorer,
+            Err(error) => {
+                println!("### skipping, {}", error);
+                return;
+            }
+        };
+
+        let batch = vec![vec![0, 2, 2], vec![], vec![1]];
+        let expected = model.token_scorer().score_batch(&batch);
+        let scores = scorer.score_batch(&batch).unwrap();
+        for (scores, expected) in scores.iter().zip(expected) {
+            for (score, expected) in scores.iter().zip(expected) {
+                assert!((*score as f64 - expected).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::{collections::HashSet, fmt::Write, ops::ControlFlow};
+
+use crate::{
+    counts::Counts, Class, Classifier, Document, FeatureDocument, NaiveBayes, SharedTokenizer,
+    TiePolicy, Tokenizer, TokenizerSpec, TrainError,
+};
+
+/// Largest supported number of hash bits, about 16 million buckets.
+pub const MAX_HASH_BITS: u32 = 24;
+
+/// A tokenizer replacing every token with one of `2^bits` hash buckets, the
+/// hashing trick: the vocabulary is the set of buckets, so memory is bounded
+/// however many distinct words the documents hold, at the cost of the words
+/// that share a bucket. The hash is stable across platforms and releases,
+/// so saved models keep working. [`HashedNaiveBayes`] counts the bucket ids
+/// themselves, without naming them.
+#[derive(Clone, Debug)]
+pub struct FeatureHashing<T> {
+    pub tokenizer: T,
+    pub bits: u32,
+}
+
+impl<T: Tokenizer> Tokenizer for FeatureHashing<T> {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        let mut name = String::new();
+        self.tokenizer.for_each_token(text, &mut |token| {
+            name.clear();
+            write_bucket(&mut name, bucket(token, self.bits));
+            emit(&name);
+        });
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let mut name = String::new();
+        self.tokenizer.try_for_each_token(text, &mut |token| {
+            name.clear();
+            write_bucket(&mut name, bucket(token, self.bits));
+            emit(&name)
+        })
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::Hashed(
+            Box::new(self.tokenizer.spec()?),
+            self.bits,
+        ))
+    }
+}
+
+/// The bucket of `token` among `2^bits`, by 64-bit FNV-1a.
+fn bucket(token: &str, bits: u32) -> u64 {
+    let hash = token.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    hash & ((1 << bits) - 1)
+}
+
+fn write_bucket(name: &mut String, bucket: u64) {
+    write!(name, "#{:x}", bucket).unwrap();
+}
+
+/// Naive Bayes over the hash buckets of the tokens of every document, the
+/// hashing trick: the features of the model are bucket ids, which tokens
+/// are hashed to directly, so no words are kept. Only buckets that occur in
+/// training take memory, at most `2^bits` however many distinct words the
+/// documents hold.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HashedNaiveBayes {
+    model: NaiveBayes<Class, u64>,
+    tokenizer: SharedTokenizer,
+    bits: u32,
+}
+
+impl HashedNaiveBayes {
+    /// Trains on the buckets `hashing` maps the words of `documents` to.
+    /// Documents of classes outside `classes` are ignored.
+    pub fn new<T: Tokenizer + 'static>(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        hashing: FeatureHashing<T>,
+    ) -> Result<Self, TrainError> {
+        if hashing.bits == 0 || hashing.bits > MAX_HASH_BITS {
+            return Err(TrainError::InvalidHashBits);
+        }
+        let tokenizer = SharedTokenizer::new(hashing.tokenizer);
+        let bits = hashing.bits;
+        let documents = hash_documents(documents, &tokenizer, bits);
+        let vocab = documents
+            .iter()
+            .flat_map(|document| document.features.iter().copied())
+            .collect();
+        Ok(Self {
+            model: NaiveBayes::from_features(&documents, classes, vocab),
+            tokenizer,
+            bits,
+        })
+    }
+
+    /// The model of the bucket ids.
+    pub fn model(&self) -> &NaiveBayes<Class, u64> {
+        &self.model
+    }
+
+    pub fn classes(&self) -> &[Class] {
+        self.model.classes()
+    }
+
+    /// The bucket of every token of `text`, in order.
+    pub fn buckets(&self, text: &str) -> Vec<u64> {
+        let mut buckets = Vec::new();
+        self.tokenizer
+            .for_each_token(text, &mut |token| buckets.push(bucket(token, self.bits)));
+        buckets
+    }
+
+    /// Adds `documents` to the counts of the model and re-estimates it, like
+    /// [`NaiveBayes::partial_fit`]. Buckets first seen in `documents` join
+    /// the model, so words unseen at training time are learned too.
+    pub fn partial_fit(&mut self, documents: &[Document]) {
+        let documents = hash_documents(documents, &self.tokenizer, self.bits);
+        let model = &mut self.model;
+        for document in &documents {
+            for bucket in &document.features {
+                let next = model.vocab.len();
+                if *model.vocab.entry(*bucket).or_insert(next) == next {
+                    model.counts.document_frequency.push(0);
+                }
+            }
+        }
+        let counts = Counts::new_features(&documents, &model.classes, &model.vocab);
+        model.counts = std::mem::take(&mut model.counts).merge(counts);
+        model.total_documents += documents.len();
+        model.estimate();
+    }
+
+    /// Log prior plus summed likelihoods of every class, in the order of
+    /// [`HashedNaiveBayes::classes`]. Buckets not seen in training are
+    /// ignored.
+    pub fn scores(&self, document: &Document) -> Vec<f64> {
+        self.model.feature_scores(&self.buckets(&document.text))
+    }
+
+    pub fn predict(&self, document: &Document) -> Option<Class> {
+        self.model
+            .best_classes(&self.scores(document), TiePolicy::First)
+            .pop()
+    }
+}
+
+impl Classifier for HashedNaiveBayes {
+    fn classes(&self) -> &[Class] {
+        self.model.classes()
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        HashedNaiveBayes::scores(self, document)
+    }
+}
+
+fn hash_documents(
+    documents: &[Document],
+    tokenizer: &dyn Tokenizer,
+    bits: u32,
+) -> Vec<FeatureDocument<u64>> {
+    documents
+        .iter()
+        .map(|document| {
+            let mut features = Vec::new();
+            tokenizer.for_each_token(&document.text, &mut |token| {
+                features.push(bucket(token, bits))
+            });
+            FeatureDocument {
+                class: document.class.clone(),
+                features,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WhitespaceTokenizer;
+
+    #[test]
+    fn test_feature_hashing() {
+        let hashed = FeatureHashing {
+            tokenizer: WhitespaceTokenizer,
+            bits: 4,
+        };
+        let tokens = hashed.tokenize("good movie good");
+        assert_eq!(tokens.len(), 3);
+        assert_eq!(tokens[0], tokens[2]);
+        assert!(tokens.iter().all(|token| token.starts_with('#')));
+        let rebuilt = hashed.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("good movie good"), tokens);
+
+        let document = |class: &str, text: &str| Document {
+            class: class.into(),
+            text: text.into(),
+        };
+        let classes: HashSet<Class> = ["pos".into(), "neg".into()].into();
+        let hashing = |bits| FeatureHashing {
+            tokenizer: WhitespaceTokenizer,
+            bits,
+        };
+        let mut model = HashedNaiveBayes::new(
+            &[document("pos", "good great"), document("neg", "bad awful")],
+            classes.clone(),
+            hashing(8),
+        )
+        .unwrap();
+        // only the buckets of the training words are kept
+        assert_eq!(model.model().vocab.len(), 4);
+        let buckets = model.buckets("good bad good");
+        assert_eq!(buckets[0], buckets[2]);
+        assert!(buckets.iter().all(|bucket| *bucket < 256));
+        assert_eq!(model.predict(&document("", "good")), Some("pos".into()));
+
+        // words unseen at training time still land in a bucket
+        model.partial_fit(&[document("pos", "superb superb")]);
+        assert!(model.model().vocab.len() <= 5);
+        assert_eq!(model.predict(&document("", "superb")), Some("pos".into()));
+
+        let error = HashedNaiveBayes::new(
+            &[document("pos", "good")],
+            classes,
+            hashing(MAX_HASH_BITS + 1),
+        )
+        .err();
+        assert_eq!(error, Some(TrainError::InvalidHashBits));
+    }
+}
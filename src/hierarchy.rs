@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use crate::{
+    counts::Counts,
+    rng::{RandomSource, SplitMix64},
+    tf_idf::document_weights,
+    Class, Classifier, Document, NaiveBayes, Tokenizer,
+};
+
+#[derive(Clone, Debug)]
+pub struct HierarchyOptions {
+    /// Number of class groups to cluster the classes into.
+    pub groups: usize,
+    /// Groups whose classes are scored at inference.
+    pub beam: usize,
+    /// Rounds of k-means clustering.
+    pub iterations: usize,
+    pub seed: u64,
+}
+
+impl Default for HierarchyOptions {
+    fn default() -> Self {
+        Self {
+            groups: 64,
+            beam: 4,
+            iterations: 10,
+            seed: 0,
+        }
+    }
+}
+
+/// Two-level scoring for models with very many classes: classes with similar
+/// word distributions are clustered into groups, which are scored first as
+/// the classes of a coarse model. Only the classes of the best groups are
+/// then scored with the full model.
+pub struct HierarchicalModel {
+    model: NaiveBayes,
+    coarse: NaiveBayes,
+    /// Class ids of every group.
+    members: Vec<Vec<usize>>,
+    beam: usize,
+}
+
+impl HierarchicalModel {
+    pub fn new(model: NaiveBayes, options: &HierarchyOptions) -> Self {
+        let mut rng = SplitMix64::new(options.seed);
+        Self::new_with(model, options, &mut rng)
+    }
+
+    /// Like [`HierarchicalModel::new`], picking the initial group centers
+    /// with `rng` instead of `options.seed`.
+    pub fn new_with(
+        model: NaiveBayes,
+        options: &HierarchyOptions,
+        rng: &mut impl RandomSource,
+    ) -> Self {
+        let groups = options.groups.clamp(1, model.classes.len().max(1));
+        let assignment = cluster(&model, groups, options.iterations, rng);
+
+        let mut members = vec![Vec::new(); groups];
+        for (class, group) in assignment.iter().enumerate() {
+            members[*group].push(class);
+        }
+        let mut counts = Counts::empty(groups, model.vocab.len());
+        for (class, group) in assignment.iter().enumerate() {
+            counts.documents[*group] += model.counts.documents[class];
+            for (token, count) in &model.counts.words[class] {
+                *counts.words[*group].entry(*token).or_default() += count;
+            }
+        }
+        counts.document_frequency = model.counts.document_frequency.clone();
+        // zero-padded so that sorting the names keeps the group order
+        let width = groups.to_string().len();
+        let names = (0..groups).map(|g| format!("{:0width$}", g)).collect();
+        let mut coarse = NaiveBayes::from_counts(
+            model.vocab.clone(),
+            names,
+            counts,
+            model.total_documents,
+            &mut (),
+        );
+        // groups sum TF-IDF weighted counts, so they are scored the same way
+        coarse.idf = model.idf.clone();
+
+        Self {
+            model,
+            coarse,
+            members,
+            beam: options.beam.max(1),
+        }
+    }
+
+    pub fn model(&self) -> &NaiveBayes {
+        &self.model
+    }
+
+    /// Classes of every group.
+    pub fn groups(&self) -> Vec<Vec<&Class>> {
+        self.members
+            .iter()
+            .map(|members| members.iter().map(|c| &self.model.classes[*c]).collect())
+            .collect()
+    }
+
+    /// Scores of the classes in the best groups, in the order of
+    /// [`NaiveBayes::classes`]; every other class scores negative infinity.
+    pub fn scores(&self, document: &Document) -> Vec<f64> {
+        let mut scratch = crate::Scratch::default();
+        let group_scores = self
+            .coarse
+            .scores_into(document, &Default::default(), &mut scratch);
+        let mut groups: Vec<usize> = (0..group_scores.len()).collect();
+        groups.sort_by(|a, b| group_scores[*b].total_cmp(&group_scores[*a]));
+        groups.truncate(self.beam);
+
+        let mut tokens: Vec<usize> = Vec::new();
+        self.model
+            .tokenizer
+            .for_each_token(&document.text, &mut |word| {
+                tokens.extend(self.model.vocab.get(word).copied())
+            });
+        let weights = document_weights(self.model.idf.as_deref(), tokens);
+        let mut scores = vec![f64::NEG_INFINITY; self.model.classes.len()];
+        for class in groups.into_iter().flat_map(|g| &self.members[g]) {
+            scores[*class] = weights
+                .iter()
+                .fold(self.model.log_prior[*class], |sum, (token, weight)| {
+                    sum + weight * self.model.likelihood.get(*class, *token)
+                });
+        }
+        scores
+    }
+
+    pub fn predict(&self, document: &Document) -> Option<Class> {
+        Classifier::predict(self, document)
+    }
+}
+
+impl Classifier for HierarchicalModel {
+    fn classes(&self) -> &[Class] {
+        self.model.classes()
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        HierarchicalModel::scores(self, document)
+    }
+}
+
+/// Spherical k-means over the classes' word count vectors, returning the
+/// group of every class.
+fn cluster(
+    model: &NaiveBayes,
+    groups: usize,
+    iterations: usize,
+    rng: &mut impl RandomSource,
+) -> Vec<usize> {
+    let vectors: Vec<HashMap<usize, f64>> = model
+        .counts
+        .words
+        .iter()
+        .map(|words| {
+            let norm = words
+                .values()
+                .map(|c| c.powi(2))
+                .sum::<f64>()
+                .sqrt()
+                .max(f64::MIN_POSITIVE);
+            words.iter().map(|(t, c)| (*t, *c / norm)).collect()
+        })
+        .collect();
+    let similarity = |vector: &HashMap<usize, f64>, center: &[f64]| -> f64 {
+        vector.iter().map(|(token, v)| v * center[*token]).sum()
+    };
+
+    let mut order: Vec<usize> = (0..vectors.len()).collect();
+    rng.shuffle(&mut order);
+    let mut centers: Vec<Vec<f64>> = order[..groups]
+        .iter()
+        .map(|class| {
+            let mut center = vec![0.0; model.vocab.len()];
+            for (token, v) in &vectors[*class] {
+                center[*token] = *v;
+            }
+            center
+        })
+        .collect();
+
+    let mut assignment = vec![0; vectors.len()];
+    for _ in 0..iterations.max(1) {
+        for (class, vector) in vectors.iter().enumerate() {
+            assignment[class] = (0..groups)
+                .max_by(|a, b| {
+                    similarity(vector, &centers[*a]).total_cmp(&similarity(vector, &centers[*b]))
+                })
+                .unwrap_or(0);
+        }
+        for (group, center) in centers.iter_mut().enumerate() {
+            let mut sum = vec![0.0; model.vocab.len()];
+            for (vector, _) in vectors
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, g)| **g == group)
+            {
+                for (token, v) in vector {
+                    sum[*token] += v;
+                }
+            }
+            let norm = sum.iter().map(|v| v * v).sum::<f64>().sqrt();
+            // an empty group keeps its previous center
+            if norm > 0.0 {
+                *center = sum.into_iter().map(|v| v / norm).collect();
+            }
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_hierarchical_scoring_follows_topics() {
+        let documents: Vec<Document> = [
+            ("laptops", "cpu screen keyboard laptop"),
+            ("phones", "cpu screen phone battery"),
+            ("boots", "leather sole boot lace"),
+            ("sneakers", "leather sole sneaker run"),
+            ("apples", "fruit sweet apple tree"),
+            ("pears", "fruit sweet pear tree"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = crate::vocabulary(&documents);
+        let model = NaiveBayes::new(&documents, classes, vocab);
+        let options = HierarchyOptions {
+            groups: 3,
+            beam: 1,
+            ..Default::default()
+        };
+        let hierarchy = HierarchicalModel::new(model, &options);
+
+        let mut groups: Vec<Vec<&Class>> = hierarchy.groups();
+        groups.sort();
+        assert_eq!(
+            groups,
+            [
+                vec!["apples", "pears"],
+                vec!["boots", "sneakers"],
+                vec!["laptops", "phones"],
+            ]
+        );
+
+        let query = Document {
+            class: String::new(),
+            text: "leather boot lace".into(),
+        };
+        assert_eq!(hierarchy.predict(&query), Some("boots".into()));
+        assert_eq!(hierarchy.predict(&query), hierarchy.model().predict(&query));
+        let scores = hierarchy.scores(&query);
+        assert_eq!(scores.iter().filter(|s| s.is_finite()).count(), 2);
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{
+    counts::Counts, Class, Document, NaiveBayes, SharedTokenizer, Tokenizer, TrainError, Word,
+};
+
+/// Renames and merges of class labels, so that messy historical labels can
+/// be cleaned up without rewriting the corpus. Every label is mapped once:
+/// the targets of the mapping are not mapped again.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LabelMap {
+    targets: HashMap<Class, Class>,
+}
+
+impl LabelMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the label `from` as `to`, such as `positif` as `positive`.
+    pub fn alias(mut self, from: &str, to: &str) -> Self {
+        self.targets.insert(from.to_owned(), to.to_owned());
+        self
+    }
+
+    /// Reads every label of `classes` as `into`.
+    pub fn merge(mut self, classes: &[&str], into: &str) -> Self {
+        for class in classes {
+            self.targets.insert(class.to_string(), into.to_owned());
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    pub fn map<'a>(&'a self, class: &'a str) -> &'a str {
+        self.targets.get(class).map_or(class, String::as_str)
+    }
+
+    pub fn map_documents(&self, documents: &[Document]) -> Vec<Document> {
+        documents
+            .iter()
+            .map(|d| Document {
+                class: self.map(&d.class).to_owned(),
+                text: d.text.clone(),
+            })
+            .collect()
+    }
+
+    pub fn map_classes(&self, classes: &HashSet<Class>) -> HashSet<Class> {
+        classes.iter().map(|c| self.map(c).to_owned()).collect()
+    }
+}
+
+impl NaiveBayes {
+    /// Applies `labels` to a trained model, summing the counts of merged
+    /// classes, and re-estimates it. The training summary is dropped, and
+    /// quantization and feature limits are not kept.
+    pub fn remap_classes(&mut self, labels: &LabelMap) {
+        let mut classes: Vec<Class> = self
+            .classes
+            .iter()
+            .map(|class| labels.map(class).to_owned())
+            .collect();
+        let targets = classes.clone();
+        classes.sort_unstable();
+        classes.dedup();
+
+        let index = Counts::class_index(&classes);
+        let mut counts = Counts::empty(classes.len(), self.vocab.len());
+        let old = std::mem::take(&mut self.counts);
+        for ((target, documents), words) in targets.iter().zip(old.documents).zip(old.words) {
+            let class = index[target.as_str()];
+            counts.documents[class] += documents;
+            for (token, count) in words {
+                *counts.words[class].entry(token).or_default() += count;
+            }
+        }
+        counts.document_frequency = old.document_frequency;
+
+        self.classes = classes;
+        self.counts = counts;
+        self.summary = None;
+        self.estimate();
+    }
+
+    /// The model predicting `label(class)` in place of every class, such as
+    /// a variant of an enum of the application, so that predictions need no
+    /// parsing. Classes keep their order.
+    pub fn with_labels<L: Clone + Eq + Hash>(
+        self,
+        mut label: impl FnMut(&str) -> L,
+    ) -> Result<NaiveBayes<L>, TrainError> {
+        let labels: Vec<L> = self.classes.iter().map(|class| label(class)).collect();
+        if let Some(duplicate) = (0..labels.len()).find(|&i| labels[..i].contains(&labels[i])) {
+            return Err(TrainError::DuplicateClass(self.classes[duplicate].clone()));
+        }
+        Ok(NaiveBayes {
+            vocab: self.vocab,
+            classes: labels,
+            counts: self.counts,
+            total_documents: self.total_documents,
+            log_prior: self.log_prior,
+            likelihood: self.likelihood,
+            sorted_document_frequency: self.sorted_document_frequency,
+            clipping: self.clipping,
+            alpha: self.alpha,
+            summary: self.summary,
+            tokenizer: self.tokenizer,
+            idf: self.idf,
+        })
+    }
+}
+
+/// Training on texts labelled with classes of any type, the counterpart of
+/// [`NaiveBayes::new_with_tokenizer`] and [`NaiveBayes::partial_fit`] for
+/// models whose classes are not names.
+impl<L: Clone + Ord + Hash + Sync> NaiveBayes<L> {
+    /// Trains on `documents`, pairs of a label and a text, without
+    /// validating the input, splitting texts with `tokenizer`. Classes are
+    /// ordered by `Ord`; texts with labels outside `classes` are ignored.
+    pub fn new_labeled(
+        documents: &[(L, &str)],
+        classes: HashSet<L>,
+        vocab: HashSet<Word>,
+        tokenizer: impl Tokenizer + 'static,
+    ) -> Self {
+        let mut words: Vec<Word> = vocab.into_iter().collect();
+        words.sort_unstable();
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+        let mut classes: Vec<L> = classes.into_iter().collect();
+        classes.sort_unstable();
+
+        let tokenizer = SharedTokenizer::new(tokenizer);
+        let class_index = label_index(&classes);
+        let counts = Counts::new_labeled(
+            documents,
+            classes.len(),
+            &vocab,
+            &tokenizer,
+            None,
+            |(label, text)| Some((*class_index.get(label)?, *text)),
+        );
+        let mut model = Self::from_counts(vocab, classes, counts, documents.len(), &mut ());
+        model.tokenizer = tokenizer;
+        model
+    }
+}
+
+impl<L: Clone + Eq + Hash + Sync> NaiveBayes<L> {
+    /// Like [`NaiveBayes::partial_fit`], for pairs of a label and a text.
+    /// The training summary, which records documents by class name, is not
+    /// updated.
+    pub fn partial_fit_labeled(&mut self, documents: &[(L, &str)]) {
+        let class_index = label_index(&self.classes);
+        let counts = Counts::new_labeled(
+            documents,
+            self.classes.len(),
+            &self.vocab,
+            &self.tokenizer,
+            self.idf.as_deref(),
+            |(label, text)| Some((*class_index.get(label)?, *text)),
+        );
+        self.counts = std::mem::take(&mut self.counts).merge(counts);
+        self.total_documents += documents.len();
+        self.estimate();
+    }
+}
+
+/// The class id of every label.
+fn label_index<L: Eq + Hash>(classes: &[L]) -> HashMap<&L, usize> {
+    classes
+        .iter()
+        .enumerate()
+        .map(|(index, class)| (class, index))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary;
+
+    #[test]
+    fn test_label_map() {
+        let labels = LabelMap::new()
+            .alias("positif", "positive")
+            .merge(&["sport", "sports"], "sports");
+        let documents: Vec<Document> = [
+            ("positive", "good film"),
+            ("positif", "bon film"),
+            ("sport", "goal"),
+            ("sports", "match goal"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let mapped = labels.map_documents(&documents);
+        let classes: Vec<&str> = mapped.iter().map(|d| d.class.as_str()).collect();
+        assert_eq!(classes, ["positive", "positive", "sports", "sports"]);
+        assert_eq!(labels.map("other"), "other");
+
+        // remapping a trained model equals training on the mapped labels
+        let all: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let mut model = NaiveBayes::new(&documents, all.clone(), vocabulary(&documents));
+        model.remap_classes(&labels);
+        let expected = NaiveBayes::new(&mapped, labels.map_classes(&all), vocabulary(&documents));
+        assert_eq!(model.classes(), ["positive", "sports"]);
+        assert_eq!(model.counts, expected.counts);
+        assert_eq!(model.log_prior, expected.log_prior);
+
+        // and to training with the mapping
+        let options = crate::TrainOptions {
+            labels,
+            ..Default::default()
+        };
+        let (fitted, _) =
+            NaiveBayes::try_new(&documents, all, vocabulary(&documents), &options).unwrap();
+        assert_eq!(fitted.counts, expected.counts);
+    }
+
+    #[test]
+    fn test_typed_labels() {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        enum Sentiment {
+            Pos,
+            Neg,
+        }
+        let documents: Vec<Document> = [("pos", "good film"), ("neg", "bad film")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let model = NaiveBayes::new(&documents, classes, vocabulary(&documents));
+        let scores = model.predict_scores(&documents[0]);
+        let parse = |class: &str| match class {
+            "pos" => Sentiment::Pos,
+            _ => Sentiment::Neg,
+        };
+
+        let typed: NaiveBayes<Sentiment> = model.clone().with_labels(parse).unwrap();
+        assert_eq!(typed.classes(), [Sentiment::Neg, Sentiment::Pos]);
+        assert_eq!(typed.predict(&documents[0]), Some(Sentiment::Pos));
+        assert_eq!(
+            typed.predict_scores(&documents[0]),
+            [(Sentiment::Pos, scores[0].1), (Sentiment::Neg, scores[1].1)]
+        );
+        assert_eq!(typed.predict_proba(&documents[1]).len(), 2);
+        assert_eq!(
+            model.clone().with_labels(|_| Sentiment::Pos).err(),
+            Some(TrainError::DuplicateClass("pos".into()))
+        );
+
+        // training on typed labels directly gives the same model
+        let labeled: Vec<(Sentiment, &str)> = documents
+            .iter()
+            .map(|d| (parse(&d.class), d.text.as_str()))
+            .collect();
+        let mut trained = NaiveBayes::new_labeled(
+            &labeled,
+            [Sentiment::Pos, Sentiment::Neg].into(),
+            vocabulary(&documents),
+            crate::WhitespaceTokenizer,
+        );
+        assert_eq!(trained.classes(), [Sentiment::Pos, Sentiment::Neg]);
+        assert_eq!(
+            trained.predict_scores(&documents[0]),
+            typed.predict_scores(&documents[0])
+        );
+        trained.partial_fit_labeled(&[(Sentiment::Neg, "bad bad film")]);
+        let mut updated = model;
+        updated.partial_fit(&[Document {
+            class: "neg".into(),
+            text: "bad bad film".into(),
+        }]);
+        let updated = updated.with_labels(parse).unwrap();
+        assert_eq!(
+            trained.predict_scores(&documents[1]),
+            updated.predict_scores(&documents[1])
+        );
+    }
+}
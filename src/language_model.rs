@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use crate::{
+    rng::{RandomSource, SplitMix64},
+    NaiveBayes, Word,
+};
+
+/// The smoothed unigram word distribution of one class, detached from the
+/// model it was taken from.
+#[derive(Clone, Debug)]
+pub struct LanguageModel {
+    /// Vocabulary words by token id.
+    words: Vec<Word>,
+    tokens: HashMap<Word, usize>,
+    probabilities: Vec<f64>,
+    /// Running sums of `probabilities`, for sampling.
+    cumulative: Vec<f64>,
+}
+
+impl LanguageModel {
+    /// Probability of `word` in the class; zero outside the vocabulary.
+    pub fn p(&self, word: &str) -> f64 {
+        self.tokens
+            .get(word)
+            .map_or(0.0, |token| self.probabilities[*token])
+    }
+
+    /// The `k` most probable words, most probable first.
+    pub fn top_k(&self, k: usize) -> Vec<(&str, f64)> {
+        let mut words: Vec<(&str, f64)> = self
+            .words
+            .iter()
+            .map(|w| w.as_str())
+            .zip(self.probabilities.iter().copied())
+            .collect();
+        words.sort_by(|(a, p), (b, q)| q.total_cmp(p).then_with(|| a.cmp(b)));
+        words.truncate(k);
+        words
+    }
+
+    /// `n` words drawn independently from the distribution.
+    pub fn sample(&self, n: usize, seed: u64) -> Vec<&str> {
+        self.sample_with(n, &mut SplitMix64::new(seed))
+    }
+
+    pub fn sample_with(&self, n: usize, rng: &mut impl RandomSource) -> Vec<&str> {
+        let total = self.cumulative.last().copied().unwrap_or(0.0);
+        (0..n)
+            .filter_map(|_| {
+                let target = rng.next_f64() * total;
+                let token = self.cumulative.partition_point(|sum| *sum <= target);
+                self.words.get(token).map(|w| w.as_str())
+            })
+            .collect()
+    }
+}
+
+impl NaiveBayes {
+    /// The smoothed unigram distribution `class` was trained with.
+    pub fn language_model(&self, class: &str) -> Option<LanguageModel> {
+        let class = self
+            .classes
+            .binary_search_by(|other| other.as_str().cmp(class))
+            .ok()?;
+        let vocab_size = self.vocab.len();
+        let class_words = &self.counts.words[class];
+        let total = class_words.values().sum::<f64>() + self.alpha * vocab_size as f64;
+
+        let mut words = vec![Word::new(); vocab_size];
+        for (word, token) in &self.vocab {
+            words[*token] = word.clone();
+        }
+        let probabilities: Vec<f64> = (0..vocab_size)
+            .map(|token| (class_words.get(&token).copied().unwrap_or(0.0) + self.alpha) / total)
+            .collect();
+        let cumulative = probabilities
+            .iter()
+            .scan(0.0, |sum, p| {
+                *sum += p;
+                Some(*sum)
+            })
+            .collect();
+
+        Some(LanguageModel {
+            words,
+            tokens: self.vocab.clone(),
+            probabilities,
+            cumulative,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    #[test]
+    fn test_language_model_of_class() {
+        let documents: Vec<Document> = [("pos", "good good great"), ("neg", "bad")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let model = NaiveBayes::new(&documents, classes, crate::vocabulary(&documents));
+        assert!(model.language_model("meh").is_none());
+
+        let pos = model.language_model("pos").unwrap();
+        // (count + 1) / (3 words + 3 vocabulary words)
+        assert_eq!(pos.p("good"), 0.5);
+        assert_eq!(pos.p("bad"), 1.0 / 6.0);
+        assert_eq!(pos.p("unknown"), 0.0);
+        assert_eq!(pos.top_k(2), [("good", 0.5), ("great", 2.0 / 6.0)]);
+        assert!((model.likelihood.get(1, model.vocab["good"]) - pos.p("good").ln()).abs() < 1e-12);
+
+        let sample = pos.sample(600, 1);
+        assert_eq!(sample.len(), 600);
+        let good = sample.iter().filter(|w| **w == "good").count();
+        assert!((250..350).contains(&good));
+        assert_eq!(sample, pos.sample(600, 1));
+    }
+}
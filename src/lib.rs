@@ -1,125 +1,1037 @@
 use std::{
-    cmp::Ordering,
     collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::ControlFlow,
 };
 
+use audit::TrainingSummary;
+use counts::Counts;
+use likelihood::{Likelihoods, Row};
+use progress::Steps;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub use builder::NaiveBayesBuilder;
+pub use classifier::Classifier;
+pub use clipping::Clipping;
+pub use compact::Compaction;
+#[cfg(feature = "parallel")]
+pub use concurrency::Concurrency;
+pub use conflicts::{resolve_conflicts, ConflictPolicy};
+pub use counts::{vocabulary, vocabulary_with};
+pub use coverage::{Coverage, CoverageReport};
+pub use diagnostics::{LikelihoodShift, UpdateDiagnostics};
+pub use evidence::{EvidenceFallback, MinEvidence, Prediction};
+pub use explain::{ClassExplanation, Contribution};
+pub use export::StaticModel;
+pub use features::FeatureDocument;
+pub use hashing::{FeatureHashing, HashedNaiveBayes, MAX_HASH_BITS};
+pub use labels::LabelMap;
+pub use merge::MergeError;
+pub use ngrams::{CharNgramTokenizer, WordNgrams};
+pub use normalize::{Normalized, TextNormalization, UnicodeForm};
+#[cfg(feature = "serde")]
+pub use persist::FormatError;
+pub use privacy::DifferentialPrivacy;
+pub use progress::{Phase, Progress};
+pub use score_export::write_scores_csv;
+pub use soft::SoftDocument;
+#[cfg(feature = "stemming")]
+pub use stemming::{Stemmed, StemmerLanguage};
+pub use stopwords::{StopwordFilter, StopwordLanguage};
+pub use store::{ParameterStore, StoredModel};
+pub use streaming::VocabularySelection;
+#[cfg(feature = "regex")]
+pub use tokenizer::RegexTokenizer;
+#[cfg(feature = "unicode")]
+pub use tokenizer::UnicodeTokenizer;
+pub use tokenizer::{SharedTokenizer, Tokenizer, TokenizerSpec, WhitespaceTokenizer};
+pub use validation::{TrainError, TrainOptions, TrainWarning, WarningThresholds};
+
+pub mod active;
+pub mod audit;
+pub mod batch;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod bernoulli;
+pub mod boilerplate;
+mod builder;
+pub mod cache;
+pub mod canonical;
+pub mod cascade;
+pub mod categorical;
+mod classifier;
+mod clipping;
+mod compact;
+pub mod complement;
+#[cfg(feature = "parallel")]
+mod concurrency;
+mod conflicts;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+mod counts;
+mod coverage;
+pub mod datasets;
+mod diagnostics;
+pub mod ensemble;
+pub mod evaluation;
+mod evidence;
+mod explain;
+mod export;
+mod features;
+pub mod fields;
+pub mod gaussian;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+mod hashing;
+pub mod hierarchy;
+mod labels;
+pub mod language_model;
+mod likelihood;
+pub mod live;
+pub mod locale;
+mod merge;
+pub mod monitor;
+mod ngrams;
+mod normalize;
+#[cfg(feature = "serde")]
+mod persist;
+pub mod pipeline;
+pub mod presets;
+mod privacy;
+mod progress;
+pub mod quantized;
+pub mod registry;
+pub mod rng;
+pub mod routing;
+mod score_export;
+pub mod selection;
+mod soft;
+#[cfg(feature = "stemming")]
+mod stemming;
+mod stopwords;
+mod store;
+mod streaming;
+mod tf_idf;
+mod tokenizer;
+mod validation;
+
 type Class = String;
 type Word = String;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Document {
     pub class: Class,
     pub text: String,
 }
 
 impl Document {
+    /// The words of the text split at whitespace, as by
+    /// [`WhitespaceTokenizer`]. Models tokenize with their own
+    /// [`Tokenizer`]; see [`NaiveBayes::tokenize`].
     pub fn words(&self) -> Vec<&str> {
         self.text.split_whitespace().collect()
     }
 }
 
-pub struct NaiveBayes {
-    vocab: HashSet<Word>,
-    classes: HashSet<Class>,
-    log_prior: HashMap<Class, f64>,
-    likelihood: HashMap<(Class, Word), f64>,
+#[derive(Clone, Debug, Default)]
+pub struct PredictOptions {
+    /// Ignore tokens whose document frequency is above this percentile (0-100)
+    /// of the document frequencies recorded at training time.
+    pub max_df_percentile: Option<f64>,
+    pub ties: TiePolicy,
+    pub normalization: LengthNormalization,
+    pub mode: ScoringMode,
+    /// Score at most this many tokens, bounding the cost of huge inputs.
+    pub token_budget: Option<TokenBudget>,
+    pub repetition: Repetition,
+    /// Score out-of-vocabulary n-grams by their in-vocabulary parts.
+    pub backoff: Option<NgramBackoff>,
+    /// Treat documents with too few known tokens differently.
+    pub min_evidence: Option<MinEvidence>,
+}
+
+/// Back-off of n-gram tokens such as `new_york` that are missing from the
+/// vocabulary to the unigrams they are made of, each scored with a reduced
+/// `weight`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NgramBackoff {
+    /// Joins the words of an n-gram token.
+    pub separator: char,
+    pub weight: f64,
+}
+
+impl Default for NgramBackoff {
+    fn default() -> Self {
+        Self {
+            separator: '_',
+            weight: 0.5,
+        }
+    }
+}
+
+/// How much the repeated occurrences of a token in a document count.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Repetition {
+    /// Every occurrence counts fully, as in training.
+    #[default]
+    Count,
+    /// Only whether the token occurs at all, so keyword stuffing is futile.
+    Presence,
+    /// `1 + ln(count)` occurrences.
+    LogDampened,
+}
+
+/// Which tokens of a document are scored when it has too many.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenBudget {
+    /// The first `n` in-vocabulary tokens; the rest of the text is not
+    /// tokenized, see [`Tokenizer::try_for_each_token`].
+    First(usize),
+    /// The `n` distinct tokens that discriminate most between classes, with
+    /// all of their occurrences.
+    MostInformative(usize),
+}
+
+/// Which parts of the model contribute to scores, for debugging.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScoringMode {
+    /// Log prior plus likelihoods.
+    #[default]
+    Full,
+    /// The log prior alone, ignoring the document.
+    PriorOnly,
+    /// Likelihoods with a uniform prior in place of the learned one.
+    LikelihoodOnly,
+}
+
+/// Rescaling of the summed log-likelihoods of a document, so that scores of
+/// short and long documents are comparable. The log prior is not rescaled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LengthNormalization {
+    #[default]
+    None,
+    /// Divide by the number of scored tokens, counted by their weights, so
+    /// that a backed-off or TF-IDF weighted token counts for its weight.
+    PerToken,
+    /// Divide by the sum of the absolute log-likelihoods of each class over
+    /// the vocabulary, as in weight-normalized complement naive Bayes.
+    WeightNormalized,
 }
 
+/// How [`NaiveBayes::predict_with`] resolves classes tied for the top score.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TiePolicy {
+    /// The tied class that sorts first.
+    #[default]
+    First,
+    /// Every tied class, in sorted order.
+    All,
+    /// No class at all.
+    Abstain,
+}
+
+/// Buffers reused between predictions.
+#[derive(Clone, Debug, Default)]
+pub struct Scratch {
+    tokens: Vec<usize>,
+    /// Distinct tokens and their number of occurrences.
+    runs: Vec<(usize, usize)>,
+    /// Unigrams of out-of-vocabulary n-grams.
+    backoff: Vec<usize>,
+    scores: Vec<f64>,
+    /// Known tokens of the last document scored, and the fallback applied
+    /// to it.
+    known_tokens: usize,
+    fallback: Option<EvidenceFallback>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "L: serde::Serialize, F: serde::Serialize + Eq + Hash",
+        deserialize = "L: serde::Deserialize<'de>, F: serde::Deserialize<'de> + Eq + Hash"
+    ))
+)]
+pub struct NaiveBayes<L = Class, F = Word> {
+    /// Vocabulary words, or other features, and their token ids.
+    vocab: HashMap<F, usize>,
+    /// Class labels by class id; everything else is indexed by id.
+    classes: Vec<L>,
+    /// Raw counts the parameters below are estimated from.
+    counts: Counts,
+    /// Every training document, including those of classes outside the model.
+    total_documents: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::persist::floats"))]
+    log_prior: Vec<f64>,
+    likelihood: Likelihoods,
+    sorted_document_frequency: Vec<usize>,
+    clipping: Clipping,
+    /// Pseudo-count added to every word of every class.
+    alpha: f64,
+    summary: Option<TrainingSummary>,
+    /// Splits documents into words, at training and prediction time alike.
+    tokenizer: SharedTokenizer,
+    /// Inverse document frequency of every token when term occurrences are
+    /// TF-IDF weighted, see [`TrainOptions::tf_idf`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    idf: Option<Vec<f64>>,
+}
+
+/// Training on [`Document`]s, whose classes are names. This stays specific
+/// to `String` labels, as do class remapping and [`NaiveBayes::add_class`]:
+/// class ids follow the sorted order of the names, and training summaries
+/// record documents by name. [`NaiveBayes::new_labeled`] and
+/// [`NaiveBayes::partial_fit_labeled`] train models of any label type.
 impl NaiveBayes {
+    /// Trains without validating the input: classes without documents get a
+    /// prior of zero, and an empty corpus gives NaN priors. See
+    /// [`NaiveBayes::fit`] for the checked version.
     pub fn new(documents: &[Document], classes: HashSet<Class>, vocab: HashSet<Word>) -> Self {
-        let (log_prior, likelihood) = classes
+        Self::new_with_progress(documents, classes, vocab, &mut ())
+    }
+
+    /// Like [`NaiveBayes::new`], reporting the progress of counting and
+    /// estimation to `progress`.
+    pub fn new_with_progress(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        progress: &mut impl Progress,
+    ) -> Self {
+        Self::train(
+            documents,
+            classes,
+            vocab,
+            SharedTokenizer::default(),
+            progress,
+        )
+    }
+
+    /// Like [`NaiveBayes::new`], splitting documents into words with
+    /// `tokenizer` instead of at whitespace. The model keeps the tokenizer
+    /// for prediction and further training.
+    pub fn new_with_tokenizer(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        tokenizer: impl Tokenizer + 'static,
+    ) -> Self {
+        Self::train(
+            documents,
+            classes,
+            vocab,
+            SharedTokenizer::new(tokenizer),
+            &mut (),
+        )
+    }
+
+    fn train(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        tokenizer: SharedTokenizer,
+        progress: &mut impl Progress,
+    ) -> Self {
+        let mut words: Vec<Word> = vocab.into_iter().collect();
+        words.sort_unstable();
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+
+        // count in chunks of about a percent, each chunk in parallel
+        let mut steps = Steps::new(progress, Phase::CountingWords, documents.len());
+        let mut counts = Counts::empty(classes.len(), vocab.len());
+        for chunk in documents.chunks((documents.len() / 100).max(1)) {
+            counts = counts.merge(Counts::new(chunk, &classes, &vocab, &tokenizer));
+            steps.step(chunk.len());
+        }
+        steps.finish();
+        let mut model = Self::from_counts(vocab, classes, counts, documents.len(), progress);
+        model.tokenizer = tokenizer;
+        model
+    }
+
+    /// Adds `documents` to the counts of the model and re-estimates all
+    /// parameters from them, as if they had been part of the original
+    /// training set. Words outside the vocabulary and classes outside the
+    /// model are ignored. Quantization and feature limits are not kept.
+    /// TF-IDF weighting keeps the idf of the original training set.
+    pub fn partial_fit(&mut self, documents: &[Document]) {
+        self.add_counts(documents);
+        if let Some(summary) = &mut self.summary {
+            summary.record(documents, &self.tokenizer);
+        }
+        self.estimate();
+    }
+
+    /// Like [`NaiveBayes::partial_fit`], sampling the documents kept by the
+    /// training summary with `rng` instead of [`audit::AuditOptions::seed`].
+    pub fn partial_fit_with(&mut self, documents: &[Document], rng: &mut impl rng::RandomSource) {
+        self.add_counts(documents);
+        if let Some(summary) = &mut self.summary {
+            summary.record_with(documents, &self.tokenizer, rng);
+        }
+        self.estimate();
+    }
+
+    fn add_counts(&mut self, documents: &[Document]) {
+        let counts = match &self.idf {
+            Some(idf) => {
+                Counts::new_tf_idf(documents, &self.classes, &self.vocab, &self.tokenizer, idf)
+            }
+            None => Counts::new(documents, &self.classes, &self.vocab, &self.tokenizer),
+        };
+        self.counts = std::mem::take(&mut self.counts).merge(counts);
+        self.total_documents += documents.len();
+    }
+
+    /// Statistics and sample documents of the training data, when training
+    /// was audited through [`TrainOptions::audit`].
+    pub fn training_summary(&self) -> Option<&TrainingSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Trains a new class from `documents`, whatever their labels, keeping
+    /// the vocabulary and the counts of every existing class. Priors of all
+    /// classes are re-estimated.
+    pub fn add_class(&mut self, class: &str, documents: &[Document]) -> Result<(), TrainError> {
+        let index = match self
+            .classes
+            .binary_search_by(|other| other.as_str().cmp(class))
+        {
+            Ok(_) => return Err(TrainError::DuplicateClass(class.to_owned())),
+            Err(index) => index,
+        };
+        if documents.is_empty() {
+            return Err(TrainError::EmptyClass(class.to_owned()));
+        }
+
+        self.classes.insert(index, class.to_owned());
+        self.counts.insert_class(index);
+        if let Some(summary) = &mut self.summary {
+            summary.insert_class(index, class);
+        }
+        let documents: Vec<Document> = documents
             .iter()
-            .map(|class| {
-                println!("# starting to train class {}", class);
-                let class_documents: Vec<_> =
-                    documents.iter().filter(|doc| doc.class == *class).collect();
-
-                println!("# starting to collect all words");
-                let class_documents_words: Vec<_> =
-                    class_documents.iter().flat_map(|doc| doc.words()).collect();
-
-                println!("# starting to count all words");
-                let class_words_count: usize = vocab
-                    .iter()
-                    .map(|v| class_documents_words.iter().filter(|w| *w == v).count() + 1)
-                    .sum();
-
-                println!("# starting to log prior");
-                let log_prior = ((documents.len() / class_documents.len()) as f64).log2();
-
-                println!("# starting to log likelihood");
-                let likelihood: HashMap<(Class, Word), f64> = vocab
-                    .iter()
-                    .map(|word| {
-                        println!("# starting to train word {}", word);
-                        let count = class_documents_words.iter().filter(|w| *w == word).count();
-                        let likelihood = (((count + 1) / class_words_count) as f64).log2();
-                        ((class.clone(), word.clone()), likelihood)
-                    })
-                    .collect();
-                ((class.clone(), log_prior), likelihood)
+            .map(|d| Document {
+                class: class.to_owned(),
+                text: d.text.clone(),
             })
-            .fold(
-                (HashMap::new(), HashMap::new()),
-                |(mut s_log_prior, mut s_likelihood), (log_prior, likelihood)| {
-                    s_log_prior.insert(log_prior.0, log_prior.1);
-                    s_likelihood.extend(likelihood);
-                    (s_log_prior, s_likelihood)
-                },
-            );
+            .collect();
+        self.partial_fit(&documents);
+        Ok(())
+    }
 
+    /// Model with the given parameters, for tests that need exact values.
+    #[cfg(test)]
+    pub(crate) fn from_parts(
+        classes: &[&str],
+        words: &[&str],
+        log_prior: Vec<f64>,
+        rows: Vec<Row>,
+        defaults: Vec<f64>,
+    ) -> Self {
         Self {
+            likelihood: Likelihoods::new(rows, words.len(), defaults),
+            vocab: words
+                .iter()
+                .enumerate()
+                .map(|(token, word)| (word.to_string(), token))
+                .collect(),
+            classes: classes.iter().map(|class| class.to_string()).collect(),
+            counts: Counts::empty(classes.len(), words.len()),
+            total_documents: 0,
+            log_prior,
+            sorted_document_frequency: vec![0; words.len()],
+            clipping: Clipping::default(),
+            alpha: 1.0,
+            summary: None,
+            tokenizer: SharedTokenizer::default(),
+            idf: None,
+        }
+    }
+
+    #[cfg(test)]
+    fn class_id(&self, class: &str) -> Option<usize> {
+        self.classes
+            .binary_search_by(|other| other.as_str().cmp(class))
+            .ok()
+    }
+}
+
+/// Estimation and scoring, which only need the classes and features to be
+/// told apart: a model can predict labels of any type, see
+/// [`NaiveBayes::with_labels`], from features of any type, see
+/// [`NaiveBayes::from_features`].
+impl<L: Clone + Eq + Hash, F: Eq + Hash> NaiveBayes<L, F> {
+    pub(crate) fn from_counts(
+        vocab: HashMap<F, usize>,
+        classes: Vec<L>,
+        counts: Counts,
+        total_documents: usize,
+        progress: &mut impl Progress,
+    ) -> Self {
+        let mut model = Self {
+            likelihood: Likelihoods::new(Vec::new(), vocab.len(), Vec::new()),
             vocab,
             classes,
-            log_prior,
-            likelihood,
+            counts,
+            total_documents,
+            log_prior: Vec::new(),
+            sorted_document_frequency: Vec::new(),
+            clipping: Clipping::default(),
+            alpha: 1.0,
+            summary: None,
+            tokenizer: SharedTokenizer::default(),
+            idf: None,
+        };
+        model.estimate_with(progress);
+        model
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Switches to Lidstone smoothing with pseudo-count `alpha`, 1 being
+    /// add-one smoothing, and re-estimates the model. An `alpha` of 0 gives
+    /// unseen words a likelihood of zero.
+    ///
+    /// # Panics
+    ///
+    /// If `alpha` is negative or not finite.
+    pub fn set_alpha(&mut self, alpha: f64) {
+        assert!(
+            alpha.is_finite() && alpha >= 0.0,
+            "invalid smoothing alpha {}",
+            alpha
+        );
+        self.alpha = alpha;
+        self.estimate();
+    }
+
+    fn estimate(&mut self) {
+        self.estimate_with(&mut ())
+    }
+
+    fn estimate_with(&mut self, progress: &mut impl Progress) {
+        let vocab_size = self.vocab.len();
+        let estimate_class = |class_id: usize| {
+            let class_words = &self.counts.words[class_id];
+            // Lidstone smoothing over the vocabulary
+            let class_words_count =
+                class_words.values().sum::<f64>() + self.alpha * vocab_size as f64;
+
+            let log_prior = (self.counts.documents[class_id] / self.total_documents as f64).ln();
+
+            let likelihood = |(token, count): (&usize, &f64)| {
+                (*token, ((*count + self.alpha) / class_words_count).ln())
+            };
+            // every likelihood is computed on its own, so the parallel and
+            // serial rows are identical once sorted by token
+            #[cfg(feature = "parallel")]
+            let mut row: Row = class_words.par_iter().map(likelihood).collect();
+            #[cfg(not(feature = "parallel"))]
+            let mut row: Row = class_words.iter().map(likelihood).collect();
+            row.sort_unstable_by_key(|(token, _)| *token);
+            // unseen words are covered by the class default likelihood
+            let default_likelihood = (self.alpha / class_words_count).ln();
+            (log_prior, default_likelihood, row)
+        };
+
+        // estimate in chunks of about a percent of the classes, each chunk
+        // in parallel, keeping the class order
+        let classes = self.classes.len();
+        let chunk = (classes / 100).max(1);
+        let mut steps = Steps::new(progress, Phase::Estimating, classes);
+        let mut estimates = Vec::with_capacity(classes);
+        for start in (0..classes).step_by(chunk) {
+            let end = (start + chunk).min(classes);
+            #[cfg(feature = "parallel")]
+            estimates.par_extend((start..end).into_par_iter().map(estimate_class));
+            #[cfg(not(feature = "parallel"))]
+            estimates.extend((start..end).map(estimate_class));
+            steps.step(end - start);
         }
+        let (log_prior, defaults, rows) = estimates.into_iter().fold(
+            (Vec::new(), Vec::new(), Vec::new()),
+            |(mut s_log_prior, mut s_defaults, mut s_rows), (log_prior, default, row)| {
+                s_log_prior.push(log_prior);
+                s_defaults.push(default);
+                s_rows.push(row);
+                (s_log_prior, s_defaults, s_rows)
+            },
+        );
+
+        let mut sorted_document_frequency = self.counts.document_frequency.clone();
+        sorted_document_frequency.sort_unstable();
+
+        let (rows, defaults) = self.clipping.apply(rows, defaults, vocab_size);
+        steps.finish();
+        self.log_prior = log_prior;
+        self.likelihood = Likelihoods::new(rows, vocab_size, defaults);
+        self.sorted_document_frequency = sorted_document_frequency;
     }
 
-    pub fn guess(&self, document: &Document) -> Vec<Class> {
-        let mut sum = self.log_prior.clone();
-        for class in self.classes.iter() {
-            for word in document.words() {
-                if self.vocab.contains(word) {
-                    sum.insert(
-                        class.to_string(),
-                        sum[class.as_str()] + self.likelihood[&(class.clone(), word.to_string())],
-                    );
-                }
+    /// Snaps log-likelihoods that lie within `epsilon` of each other to a shared
+    /// value, storing every entry as an index into the resulting codebook.
+    /// Returns the number of distinct values kept.
+    pub fn quantize_likelihoods(&mut self, epsilon: f64) -> usize {
+        self.likelihood.quantize(epsilon);
+        self.likelihood.distinct_values()
+    }
+
+    /// Keeps only the `budget` most informative words of every class, ranked by
+    /// the log-ratio of their likelihood against the best competing class. The
+    /// dropped words fall back to the class default likelihood.
+    pub fn limit_features_per_class(&mut self, budget: usize) {
+        let mut keep: HashSet<(usize, usize)> = HashSet::new();
+        for class in 0..self.classes.len() {
+            keep.extend(
+                self.ranked_features(class)
+                    .into_iter()
+                    .take(budget)
+                    .map(|(_, token, _)| (class, token)),
+            );
+        }
+        self.likelihood
+            .retain(|class, token| keep.contains(&(class, token)));
+    }
+
+    /// Every feature with the log-ratio of its likelihood in `class` against
+    /// the best competing class, most informative first.
+    fn ranked_features(&self, class: usize) -> Vec<(&F, usize, f64)> {
+        let mut ranked: Vec<(&F, usize, f64)> = self
+            .vocab
+            .iter()
+            .map(|(feature, &token)| {
+                let likelihood = self.likelihood.get(class, token);
+                let competitor = (0..self.classes.len())
+                    .filter(|other| *other != class)
+                    .map(|other| self.likelihood.get(other, token))
+                    .fold(f64::NEG_INFINITY, f64::max);
+                let ratio = likelihood - competitor;
+                (
+                    feature,
+                    token,
+                    if ratio.is_nan() {
+                        f64::NEG_INFINITY
+                    } else {
+                        ratio
+                    },
+                )
+            })
+            .collect();
+        // token ids follow the order of the words, which breaks ties
+        ranked.sort_unstable_by(|(_, a, a_ratio), (_, b, b_ratio)| {
+            b_ratio.total_cmp(a_ratio).then_with(|| a.cmp(b))
+        });
+        ranked
+    }
+
+    /// The `n` features that speak most for `class`, with the log-ratio of
+    /// their likelihood in it against the best competing class, like the
+    /// informative features of NLTK. `None` for a class outside the model.
+    pub fn most_informative_features(&self, class: &L, n: usize) -> Option<Vec<(&F, f64)>> {
+        let class = self.classes.iter().position(|other| other == class)?;
+        Some(
+            self.ranked_features(class)
+                .into_iter()
+                .take(n)
+                .map(|(feature, _, ratio)| (feature, ratio))
+                .collect(),
+        )
+    }
+
+    /// Document frequency at the given percentile (0-100) of the vocabulary.
+    pub fn document_frequency_cutoff(&self, percentile: f64) -> usize {
+        if self.sorted_document_frequency.is_empty() {
+            return 0;
+        }
+        let last = self.sorted_document_frequency.len() - 1;
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * last as f64).ceil() as usize;
+        self.sorted_document_frequency[rank]
+    }
+
+    /// Number of training documents of the classes of the model, the ones
+    /// document frequencies are counted over. Fractional when documents were
+    /// trained with soft labels whose weights do not sum to one.
+    pub fn counted_documents(&self) -> f64 {
+        self.counts.documents.iter().sum()
+    }
+
+    /// Classes in the order used by score vectors.
+    pub fn classes(&self) -> &[L] {
+        &self.classes
+    }
+
+    fn best_classes(&self, scores: &[f64], ties: TiePolicy) -> Vec<L> {
+        best_class_ids(scores, ties)
+            .into_iter()
+            .map(|class| self.classes[class].clone())
+            .collect()
+    }
+}
+
+/// Indices of the best `scores`, of which `ties` keeps one, all or none.
+fn best_class_ids(scores: &[f64], ties: TiePolicy) -> Vec<usize> {
+    let Some(best) = scores.iter().copied().max_by(f64::total_cmp) else {
+        return Vec::new();
+    };
+
+    let mut tied = scores
+        .iter()
+        .enumerate()
+        .filter(|(_, score)| score.total_cmp(&best).is_eq())
+        .map(|(class, _)| class);
+    match ties {
+        TiePolicy::First => tied.next().into_iter().collect(),
+        TiePolicy::All => tied.collect(),
+        TiePolicy::Abstain => match (tied.next(), tied.next()) {
+            (Some(class), None) => vec![class],
+            _ => Vec::new(),
+        },
+    }
+}
+
+/// Text documents, split into words by the tokenizer of the model.
+impl<L: Clone + Eq + Hash> NaiveBayes<L> {
+    /// Number of training documents `word` occurs in, if it is in the
+    /// vocabulary.
+    pub fn document_frequency(&self, word: &str) -> Option<usize> {
+        self.vocab
+            .get(word)
+            .map(|token| self.counts.document_frequency[*token])
+    }
+
+    /// Every vocabulary word with its document frequency, in no particular
+    /// order.
+    pub fn document_frequencies(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.vocab
+            .iter()
+            .map(|(word, token)| (word.as_str(), self.counts.document_frequency[*token]))
+    }
+
+    /// Smoothed inverse document frequency of `word`,
+    /// `ln((1 + n) / (1 + df)) + 1` over the `n` counted documents.
+    pub fn idf(&self, word: &str) -> Option<f64> {
+        let documents = self.counted_documents();
+        self.document_frequency(word)
+            .map(|df| ((1.0 + documents) / (1.0 + df as f64)).ln() + 1.0)
+    }
+
+    #[deprecated(note = "use `predict`, or `predict_with` to choose a tie policy")]
+    pub fn guess(&self, document: &Document) -> Vec<L> {
+        self.predict_with(
+            document,
+            &PredictOptions {
+                ties: TiePolicy::All,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[deprecated(note = "use `predict_with`")]
+    pub fn guess_with(&self, document: &Document, options: &PredictOptions) -> Vec<L> {
+        self.predict_with(document, options)
+    }
+
+    /// The class with the highest posterior, ties going to the class that
+    /// sorts first. `None` only for a model without classes.
+    pub fn predict(&self, document: &Document) -> Option<L> {
+        self.predict_with(document, &PredictOptions::default())
+            .pop()
+    }
+
+    /// The class with the highest posterior, or the classes tied for it as
+    /// chosen by `options.ties`. Nothing when abstaining for lack of
+    /// evidence, see [`PredictOptions::min_evidence`].
+    pub fn predict_with(&self, document: &Document, options: &PredictOptions) -> Vec<L> {
+        let mut scratch = Scratch::default();
+        self.scores_into(document, options, &mut scratch);
+        if scratch.fallback == Some(EvidenceFallback::Abstain) {
+            return Vec::new();
+        }
+        self.best_classes(&scratch.scores, options.ties)
+    }
+
+    /// Every class with its log prior plus summed likelihoods, best first.
+    /// Tied classes keep their order.
+    pub fn predict_scores(&self, document: &Document) -> Vec<(L, f64)> {
+        let mut scratch = Scratch::default();
+        let scores = self.scores_into(document, &PredictOptions::default(), &mut scratch);
+        let mut ranked: Vec<(L, f64)> = self
+            .classes
+            .iter()
+            .cloned()
+            .zip(scores.iter().copied())
+            .collect();
+        ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        ranked
+    }
+
+    /// The `k` best classes with their log prior plus summed likelihoods,
+    /// best first, as the head of [`NaiveBayes::predict_scores`] without
+    /// ranking every class.
+    pub fn predict_top_k(&self, document: &Document, k: usize) -> Vec<(L, f64)> {
+        let mut scratch = Scratch::default();
+        let scores = self.scores_into(document, &PredictOptions::default(), &mut scratch);
+        // tied classes keep their order
+        let rank = |a: &usize, b: &usize| scores[*b].total_cmp(&scores[*a]).then(a.cmp(b));
+        let mut best: Vec<usize> = (0..scores.len()).collect();
+        if k < best.len() {
+            if k > 0 {
+                best.select_nth_unstable_by(k - 1, rank);
             }
+            best.truncate(k);
         }
+        best.sort_unstable_by(rank);
+        best.into_iter()
+            .map(|class| (self.classes[class].clone(), scores[class]))
+            .collect()
+    }
+
+    /// Posterior probability of every class, normalized with log-sum-exp so
+    /// that long documents do not underflow. Sums to 1.
+    pub fn predict_proba(&self, document: &Document) -> HashMap<L, f64> {
+        let mut scratch = Scratch::default();
+        let scores = self.scores_into(document, &PredictOptions::default(), &mut scratch);
+        self.classes
+            .iter()
+            .cloned()
+            .zip(classifier::softmax(scores))
+            .collect()
+    }
+
+    pub fn tokenizer(&self) -> &dyn Tokenizer {
+        &self.tokenizer
+    }
+
+    /// The words of `text` as the model sees them, in or out of its
+    /// vocabulary.
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        self.tokenizer.tokenize(text)
+    }
 
-        let max_item = sum.iter().max_by(|(_, i), (_, j)| {
-            if i < j {
-                Ordering::Greater
-            } else {
-                Ordering::Less
+    /// Log prior plus summed likelihoods of every class, in the order of
+    /// [`NaiveBayes::classes`]. Reusing `scratch` across calls keeps this
+    /// free of allocations once its buffers have grown.
+    pub fn scores_into<'a>(
+        &self,
+        document: &Document,
+        options: &PredictOptions,
+        scratch: &'a mut Scratch,
+    ) -> &'a [f64] {
+        let limit = match options.token_budget {
+            Some(TokenBudget::First(n)) => n,
+            _ => usize::MAX,
+        };
+        scratch.tokens.clear();
+        let tokens = &mut scratch.tokens;
+        let _ = self
+            .tokenizer
+            .try_for_each_token(&document.text, &mut |word| {
+                if tokens.len() < limit {
+                    tokens.extend(self.vocab.get(word).copied());
+                }
+                within_budget(tokens.len(), limit)
+            });
+        let backoff = |backoff, pruned: &dyn Fn(usize) -> bool, unigrams: &mut Vec<usize>| {
+            self.backoff_unigrams(&document.text, limit, backoff, pruned, unigrams)
+        };
+        self.score_tokens(backoff, options, scratch)
+    }
+
+    /// [`NaiveBayes::scores_into`] for a document whose words were already
+    /// mapped to the token ids of this model, in document order.
+    pub(crate) fn scores_of_tokens<'a>(
+        &self,
+        tokens: &[usize],
+        text: &str,
+        options: &PredictOptions,
+        scratch: &'a mut Scratch,
+    ) -> &'a [f64] {
+        let limit = match options.token_budget {
+            Some(TokenBudget::First(n)) => n,
+            _ => usize::MAX,
+        };
+        scratch.tokens.clear();
+        scratch
+            .tokens
+            .extend_from_slice(&tokens[..tokens.len().min(limit)]);
+        let backoff = |backoff, pruned: &dyn Fn(usize) -> bool, unigrams: &mut Vec<usize>| {
+            self.backoff_unigrams(text, limit, backoff, pruned, unigrams)
+        };
+        self.score_tokens(backoff, options, scratch)
+    }
+
+    /// The in-vocabulary unigrams of the out-of-vocabulary n-grams of
+    /// `text` up to its `limit`-th in-vocabulary token, except the `pruned`
+    /// ones.
+    fn backoff_unigrams(
+        &self,
+        text: &str,
+        limit: usize,
+        backoff: NgramBackoff,
+        pruned: &dyn Fn(usize) -> bool,
+        unigrams: &mut Vec<usize>,
+    ) {
+        let mut known = 0;
+        let _ = self.tokenizer.try_for_each_token(text, &mut |word| {
+            if self.vocab.contains_key(word) {
+                known += 1;
+            } else if word.contains(backoff.separator) {
+                unigrams.extend(
+                    word.split(backoff.separator)
+                        .filter_map(|part| self.vocab.get(part).copied())
+                        .filter(|token| !pruned(*token)),
+                );
             }
+            within_budget(known, limit)
         });
+    }
+}
 
-        match max_item {
-            None => Vec::new(),
-            Some((_, max_value)) => self
-                .log_prior
+/// Whether tokenizing goes on after `tokens` of a budget of `limit` tokens.
+fn within_budget(tokens: usize, limit: usize) -> ControlFlow<()> {
+    if tokens < limit {
+        ControlFlow::Continue(())
+    } else {
+        ControlFlow::Break(())
+    }
+}
+
+impl<L: Clone + Eq + Hash, F: Eq + Hash> NaiveBayes<L, F> {
+    /// Scores the tokens in `scratch.tokens`, adding the unigrams found by
+    /// `backoff_unigrams` with n-gram back-off.
+    pub(crate) fn score_tokens<'a>(
+        &self,
+        backoff_unigrams: impl FnOnce(NgramBackoff, &dyn Fn(usize) -> bool, &mut Vec<usize>),
+        options: &PredictOptions,
+        scratch: &'a mut Scratch,
+    ) -> &'a [f64] {
+        let max_df = options
+            .max_df_percentile
+            .map(|percentile| self.document_frequency_cutoff(percentile));
+        let is_pruned = |token: usize| match max_df {
+            Some(max_df) => self.counts.document_frequency[token] > max_df,
+            None => false,
+        };
+
+        scratch.known_tokens = scratch.tokens.len();
+        scratch.fallback = options
+            .min_evidence
+            .filter(|evidence| scratch.known_tokens < evidence.tokens)
+            .map(|evidence| evidence.fallback);
+        let mode = match scratch.fallback {
+            Some(EvidenceFallback::Prior) => ScoringMode::PriorOnly,
+            _ => options.mode,
+        };
+        let damping = match scratch.fallback {
+            Some(EvidenceFallback::Dampen(weight)) => weight,
+            _ => 1.0,
+        };
+
+        // every word is looked up once; repeated tokens are then grouped so
+        // their likelihoods are read once and added with their multiplicity
+        scratch.tokens.sort_unstable();
+        scratch.runs.clear();
+        if mode != ScoringMode::PriorOnly {
+            scratch.runs.extend(
+                scratch
+                    .tokens
+                    .chunk_by(|a, b| a == b)
+                    .map(|run| (run[0], run.len()))
+                    .filter(|(token, _)| !is_pruned(*token)),
+            );
+        }
+        if let Some(TokenBudget::MostInformative(n)) = options.token_budget {
+            if scratch.runs.len() > n {
+                let informativeness = |token| self.likelihood.informativeness(token);
+                scratch.runs.select_nth_unstable_by(n, |(a, _), (b, _)| {
+                    informativeness(*b).total_cmp(&informativeness(*a))
+                });
+                scratch.runs.truncate(n);
+            }
+        }
+
+        scratch.backoff.clear();
+        if let Some(backoff) = options.backoff.filter(|_| mode != ScoringMode::PriorOnly) {
+            backoff_unigrams(backoff, &is_pruned, &mut scratch.backoff);
+        }
+
+        scratch.scores.clear();
+        scratch.scores.resize(self.classes.len(), 0.0);
+        let mut scored = 0.0;
+        if let Some(backoff) = options.backoff {
+            for token in scratch.backoff.iter() {
+                scored += backoff.weight;
+                self.likelihood
+                    .add_token(*token, backoff.weight * damping, &mut scratch.scores);
+            }
+        }
+        let repeated = |count: usize| match options.repetition {
+            Repetition::Count => count as f64,
+            Repetition::Presence => 1.0,
+            Repetition::LogDampened => 1.0 + (count as f64).ln(),
+        };
+        // TF-IDF weights are scaled to unit length, as in training
+        let tf_idf = self.idf.as_ref().map(|idf| {
+            let norm = scratch
+                .runs
                 .iter()
-                .map_while(|(class, value)| {
-                    if value == max_value {
-                        Some(class.clone())
-                    } else {
-                        None
+                .map(|(token, count)| (repeated(*count) * idf[*token]).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            (idf, norm)
+        });
+        for (token, count) in scratch.runs.iter() {
+            let weight = match tf_idf {
+                Some((idf, norm)) => repeated(*count) * idf[*token] / norm,
+                None => repeated(*count),
+            };
+            scored += weight;
+            self.likelihood
+                .add_token(*token, weight * damping, &mut scratch.scores);
+        }
+
+        for (class, score) in scratch.scores.iter_mut().enumerate() {
+            match options.normalization {
+                LengthNormalization::None => {}
+                LengthNormalization::PerToken => {
+                    if scored > 0.0 {
+                        *score /= scored;
                     }
-                })
-                .collect(),
+                }
+                LengthNormalization::WeightNormalized => {
+                    let norm = self.likelihood.norm(class);
+                    if norm > 0.0 {
+                        *score /= norm;
+                    }
+                }
+            }
+            *score += match mode {
+                ScoringMode::LikelihoodOnly => -(self.classes.len() as f64).ln(),
+                ScoringMode::Full | ScoringMode::PriorOnly => self.log_prior[class],
+            };
         }
+        &scratch.scores
     }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
-    use std::{
-        fs::{read_dir, read_to_string},
-        path::{Path, PathBuf},
-    };
+    use std::path::{Path, PathBuf};
 
     struct Dataset {
         pub vocab: HashSet<Word>,
@@ -128,51 +1040,19 @@ mod tests {
         pub test_docs: Vec<Document>,
     }
 
-    fn read_folder_documents(path: &Path) -> Vec<Document> {
-        read_dir(path)
-            .unwrap()
-            .map(|item| match item {
-                Ok(i) if i.path().is_dir() => {
-                    println!("## reading folder: {:?}", i.file_name());
-                    let class = i.file_name();
-                    let docs: Vec<Document> = read_dir(i.path())
-                        .unwrap()
-                        .map_while(|file| match file {
-                            Ok(file) if file.path().is_file() => {
-                                println!("## reading file: {:?}", file.file_name());
-                                let text = read_to_string(file.path()).unwrap();
-                                Some(Document {
-                                    class: class.to_str().unwrap().into(),
-                                    text,
-                                })
-                            }
-                            _ => None,
-                        })
-                        .collect();
-                    docs
-                }
-                _ => [].to_vec(),
-            })
-            .fold(Vec::new(), |mut s, v| {
-                s.extend(v);
-                s
-            })
-    }
-
-    fn read_dataset<'a>(path: &'a str) -> Result<Dataset, &'static str> {
+    fn read_dataset(path: &str) -> Result<Dataset, &'static str> {
         if !Path::new(path).is_dir() {
             return Err("path must be folder of dataset");
         }
 
-        let vocab = read_to_string(PathBuf::from_iter([path, "imdb.vocab"])).unwrap();
-        let vocab: HashSet<Word> = vocab.split_whitespace().map(|s| s.to_owned()).collect();
+        let vocab = datasets::read_vocabulary(PathBuf::from_iter([path, "imdb.vocab"])).unwrap();
 
         let train_path = PathBuf::from_iter([path, "train"]);
-        let train_docs = read_folder_documents(&train_path);
+        let train_docs = datasets::read_labelled_folders(train_path).unwrap();
         let classes: HashSet<Class> = train_docs.iter().map(|d| d.class.to_owned()).collect();
 
         let test_path = PathBuf::from_iter([path, "test"]);
-        let test_docs = read_folder_documents(&test_path);
+        let test_docs = datasets::read_labelled_folders(test_path).unwrap();
 
         Ok(Dataset {
             vocab,
@@ -181,8 +1061,705 @@ mod tests {
             test_docs,
         })
     }
+    fn document(class: &str, text: &str) -> Document {
+        Document {
+            class: class.into(),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn test_estimates_hand_computed_probabilities() {
+        let documents = [
+            document("pos", "good good movie"),
+            document("neg", "bad movie"),
+        ];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = ["bad", "good", "movie"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+
+        let (pos, neg) = (
+            naive_bayes.class_id("pos").unwrap(),
+            naive_bayes.class_id("neg").unwrap(),
+        );
+        let probability = |class, word: &str| {
+            naive_bayes
+                .likelihood
+                .get(class, naive_bayes.vocab[word])
+                .exp()
+        };
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+
+        assert!(close(naive_bayes.log_prior[pos].exp(), 0.5));
+        assert!(close(naive_bayes.log_prior[neg].exp(), 0.5));
+        // (count + 1) / (words in class + vocabulary size)
+        assert!(close(probability(pos, "good"), 3.0 / 6.0));
+        assert!(close(probability(pos, "movie"), 2.0 / 6.0));
+        assert!(close(probability(pos, "bad"), 1.0 / 6.0));
+        assert!(close(probability(neg, "bad"), 2.0 / 5.0));
+        assert!(close(probability(neg, "movie"), 2.0 / 5.0));
+        assert!(close(probability(neg, "good"), 1.0 / 5.0));
+
+        assert_eq!(
+            naive_bayes.predict(&document("", "good")),
+            Some("pos".into())
+        );
+        assert_eq!(
+            naive_bayes.predict(&document("", "bad")),
+            Some("neg".into())
+        );
+    }
+
+    #[test]
+    fn test_lidstone_smoothing() {
+        let documents = [
+            document("pos", "good good movie"),
+            document("neg", "bad movie"),
+        ];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = ["bad", "good", "movie"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+        naive_bayes.set_alpha(0.5);
+
+        let pos = naive_bayes.class_id("pos").unwrap();
+        let probability = |naive_bayes: &NaiveBayes, word: &str| {
+            naive_bayes
+                .likelihood
+                .get(pos, naive_bayes.vocab[word])
+                .exp()
+        };
+        assert_eq!(naive_bayes.alpha(), 0.5);
+        // (count + alpha) / (words in class + alpha * vocabulary size)
+        assert!((probability(&naive_bayes, "good") - 2.5 / 4.5).abs() < 1e-12);
+        assert!((probability(&naive_bayes, "bad") - 0.5 / 4.5).abs() < 1e-12);
+
+        naive_bayes.set_alpha(0.0);
+        assert_eq!(probability(&naive_bayes, "bad"), 0.0);
+        assert!((probability(&naive_bayes, "good") - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_training_matches_serial_reference() {
+        // enough classes and documents to be split into chunks, which run in
+        // parallel with the `parallel` feature
+        let documents: Vec<Document> = (0..2000)
+            .map(|i| {
+                document(
+                    &format!("c{}", i % 230),
+                    &format!("w{} w{}", i % 17, i % 31),
+                )
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = vocabulary(&documents);
+        let naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+
+        let class_index = Counts::class_index(&naive_bayes.classes);
+        let counts = documents.iter().fold(
+            Counts::empty(naive_bayes.classes.len(), naive_bayes.vocab.len()),
+            |counts, document| {
+                counts.add(
+                    document,
+                    &class_index,
+                    &naive_bayes.vocab,
+                    &WhitespaceTokenizer,
+                )
+            },
+        );
+        assert_eq!(naive_bayes.counts, counts);
+        for (class, words) in counts.words.iter().enumerate() {
+            let total = words.values().sum::<f64>() + naive_bayes.vocab.len() as f64;
+            for token in 0..naive_bayes.vocab.len() {
+                let count = words.get(&token).copied().unwrap_or(0.0);
+                let expected = ((count + 1.0) / total).ln();
+                assert_eq!(
+                    naive_bayes.likelihood.get(class, token).to_bits(),
+                    expected.to_bits()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_class() {
+        let documents = [document("pos", "good fine"), document("neg", "bad")];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = ["good", "bad", "fine", "meh"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let mut naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+
+        naive_bayes
+            .add_class(
+                "mixed",
+                &[document("", "meh meh"), document("", "meh fine")],
+            )
+            .unwrap();
+        assert_eq!(naive_bayes.classes(), ["mixed", "neg", "pos"]);
+        assert_eq!(naive_bayes.counts.documents, [2.0, 1.0, 1.0]);
+        assert_eq!(naive_bayes.total_documents, 4);
+        assert!((naive_bayes.log_prior[0].exp() - 0.5).abs() < 1e-12);
+        assert_eq!(
+            naive_bayes.predict(&document("", "meh")),
+            Some("mixed".into())
+        );
+        assert_eq!(
+            naive_bayes.predict(&document("", "good")),
+            Some("pos".into())
+        );
+
+        assert_eq!(
+            naive_bayes.add_class("neg", &[document("", "bad")]),
+            Err(TrainError::DuplicateClass("neg".into()))
+        );
+    }
+
+    #[test]
+    fn test_document_frequency_pruning() {
+        let documents = [
+            document("pos", "the movie was great"),
+            document("pos", "the acting was great"),
+            document("neg", "the movie was awful"),
+            document("neg", "the plot was boring"),
+        ];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .map(|w| w.to_owned())
+            .collect();
+        let naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+
+        assert_eq!(naive_bayes.document_frequency_cutoff(100.0), 4);
+        assert_eq!(naive_bayes.document_frequency_cutoff(0.0), 1);
+        assert_eq!(naive_bayes.document_frequency("movie"), Some(2));
+        assert_eq!(naive_bayes.document_frequency("film"), None);
+        assert_eq!(naive_bayes.document_frequencies().count(), 8);
+        assert_eq!(naive_bayes.idf("the"), Some(1.0));
+        assert!((naive_bayes.idf("plot").unwrap() - (2.5f64.ln() + 1.0)).abs() < 1e-12);
+
+        let options = PredictOptions {
+            max_df_percentile: Some(50.0),
+            ..Default::default()
+        };
+        let stopwords_only = document("pos", "the was the");
+        assert_eq!(
+            naive_bayes.predict_with(&stopwords_only, &options),
+            naive_bayes.predict_with(&document("pos", ""), &PredictOptions::default())
+        );
+    }
+
+    #[test]
+    fn test_limit_features_per_class() {
+        let documents = [
+            document("pos", "good good good great fine"),
+            document("neg", "bad bad awful fine"),
+        ];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .map(|w| w.to_owned())
+            .collect();
+        let mut naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+        naive_bayes.limit_features_per_class(2);
+
+        let stored = |class: &str| {
+            let class = naive_bayes.class_id(class).unwrap();
+            let mut words: Vec<&str> = naive_bayes
+                .vocab
+                .iter()
+                .filter(|(_, token)| naive_bayes.likelihood.is_stored(class, **token))
+                .map(|(word, _)| word.as_str())
+                .collect();
+            words.sort_unstable();
+            words
+        };
+        // "fine" is as frequent in both classes, so it is the least telling
+        assert_eq!(stored("pos"), ["good", "great"]);
+        assert_eq!(stored("neg"), ["awful", "bad"]);
+
+        let fine = naive_bayes.vocab["fine"];
+        let scores = Classifier::scores(&naive_bayes, &document("", "fine"));
+        for (class, score) in scores.iter().enumerate() {
+            let default = naive_bayes.likelihood.default(class);
+            assert_eq!(naive_bayes.likelihood.get(class, fine), default);
+            assert_eq!(*score, naive_bayes.log_prior[class] + default);
+        }
+    }
+
+    #[test]
+    fn test_most_informative_features() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap", "meeting", "now", "win"],
+            vec![-0.5, -0.9],
+            vec![
+                vec![(0, -6.0), (1, -1.2), (2, -2.0), (3, -7.0)],
+                vec![(0, -1.1), (1, -5.5), (2, -2.0), (3, -3.0)],
+            ],
+            vec![-8.0, -8.0],
+        );
+        let spam = naive_bayes
+            .most_informative_features(&"spam".into(), 2)
+            .unwrap();
+        let words: Vec<&str> = spam.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(words, ["cheap", "win"]);
+        assert!((spam[0].1 - 4.9).abs() < 1e-12);
+        let ham = naive_bayes
+            .most_informative_features(&"ham".into(), 9)
+            .unwrap();
+        assert_eq!(ham.len(), 4);
+        assert_eq!(ham[0].0, "meeting");
+        assert_eq!(ham[1], (&"now".to_string(), 0.0));
+        assert_eq!(
+            naive_bayes.most_informative_features(&"eggs".into(), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unseen_words_use_class_default() {
+        let documents = [document("pos", "good good"), document("neg", "bad")];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = ["good", "bad", "meh"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+
+        let pos = naive_bayes.class_id("pos").unwrap();
+        let (bad, meh) = (naive_bayes.vocab["bad"], naive_bayes.vocab["meh"]);
+        assert!(!naive_bayes.likelihood.is_stored(pos, bad));
+        assert_eq!(
+            naive_bayes.likelihood.get(pos, meh),
+            naive_bayes.likelihood.default(pos)
+        );
+        assert_eq!(
+            naive_bayes.predict(&document("", "bad meh good")),
+            Some("neg".into())
+        );
+
+        let scores = Classifier::scores(&naive_bayes, &document("", "meh"));
+        for (class, score) in scores.iter().enumerate() {
+            let expected = naive_bayes.log_prior[class] + naive_bayes.likelihood.default(class);
+            assert!((score - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_scores_into_reuses_scratch() {
+        let documents = [document("pos", "good fine"), document("neg", "bad fine")];
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .map(|w| w.to_owned())
+            .collect();
+        let naive_bayes = NaiveBayes::new(&documents, classes, vocab);
+
+        let mut scratch = Scratch::default();
+        let options = PredictOptions::default();
+        let first = naive_bayes
+            .scores_into(&document("", "good fine bad"), &options, &mut scratch)
+            .to_vec();
+        let (scores, tokens) = (scratch.scores.as_ptr(), scratch.tokens.as_ptr());
+        naive_bayes.scores_into(&document("", "bad good"), &options, &mut scratch);
+
+        assert_eq!(first.len(), naive_bayes.classes().len());
+        assert_eq!(scratch.scores.as_ptr(), scores);
+        assert_eq!(scratch.tokens.as_ptr(), tokens);
+    }
+
+    #[test]
+    fn test_predict_resolves_ties_by_policy() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["a", "b", "c"],
+            &["x", "y"],
+            vec![-1.0, -1.0, -3.0],
+            vec![
+                vec![(0, -1.0), (1, -2.0)],
+                vec![(0, -1.0), (1, -0.5)],
+                vec![(0, -0.1), (1, -0.1)],
+            ],
+            vec![-9.0; 3],
+        );
+        let with = |ties| PredictOptions {
+            ties,
+            ..Default::default()
+        };
+
+        assert_eq!(naive_bayes.predict(&document("", "y")), Some("b".into()));
+        assert_eq!(
+            naive_bayes.predict(&document("", "y x y")),
+            Some("b".into())
+        );
+        // "x" alone ties a and b
+        let tied = document("", "x");
+        assert_eq!(naive_bayes.predict(&tied), Some("a".into()));
+        assert_eq!(
+            naive_bayes.predict_with(&tied, &with(TiePolicy::All)),
+            ["a", "b"]
+        );
+        assert!(naive_bayes
+            .predict_with(&tied, &with(TiePolicy::Abstain))
+            .is_empty());
+        // most words favour c, whose prior is lower
+        assert_eq!(
+            naive_bayes.predict(&document("", "x x x x")),
+            Some("c".into())
+        );
+    }
+
+    #[test]
+    fn test_length_normalization() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.5, -1.0],
+            vec![vec![(0, -0.5), (1, -2.0)], vec![(0, -2.5), (1, -0.25)]],
+            vec![-5.0, -5.0],
+        );
+        let mut scratch = Scratch::default();
+        let mut scores = |text: &str, normalization| {
+            let options = PredictOptions {
+                normalization,
+                ..Default::default()
+            };
+            naive_bayes
+                .scores_into(&document("", text), &options, &mut scratch)
+                .to_vec()
+        };
+
+        let short = scores("good bad", LengthNormalization::PerToken);
+        let long = scores(&"good bad ".repeat(50), LengthNormalization::PerToken);
+        assert_eq!(short, long);
+        assert_eq!(short, [-0.5 - 2.5 / 2.0, -1.0 - 2.75 / 2.0]);
+
+        let weighted = scores("good", LengthNormalization::WeightNormalized);
+        assert_eq!(weighted, [-0.5 - 2.0 / 2.5, -1.0 - 0.25 / 2.75]);
+        assert_eq!(scores("", LengthNormalization::PerToken), [-0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_scoring_modes_isolate_prior_and_likelihoods() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.1, -2.5],
+            vec![vec![(0, -0.5), (1, -2.0)], vec![(0, -2.5), (1, -0.25)]],
+            vec![-5.0, -5.0],
+        );
+        let good = document("", "good");
+        let with = |mode| PredictOptions {
+            mode,
+            ..Default::default()
+        };
+
+        // the prior outweighs the single word
+        assert_eq!(naive_bayes.predict(&good), Some("neg".into()));
+        assert_eq!(
+            naive_bayes.predict_with(&good, &with(ScoringMode::PriorOnly)),
+            ["neg"]
+        );
+        assert_eq!(
+            naive_bayes.predict_with(&good, &with(ScoringMode::LikelihoodOnly)),
+            ["pos"]
+        );
+
+        let mut scratch = Scratch::default();
+        let scores = naive_bayes.scores_into(&good, &with(ScoringMode::PriorOnly), &mut scratch);
+        assert_eq!(scores, [-0.1, -2.5]);
+        let uniform = -(2.0f64).ln();
+        let scores =
+            naive_bayes.scores_into(&good, &with(ScoringMode::LikelihoodOnly), &mut scratch);
+        assert_eq!(scores, [-2.0 + uniform, -0.25 + uniform]);
+    }
+
+    #[test]
+    fn test_token_budget() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good", "the"],
+            vec![-0.7, -0.7],
+            vec![
+                vec![(0, -0.5), (1, -3.0), (2, -1.0)],
+                vec![(0, -3.5), (1, -0.5), (2, -1.1)],
+            ],
+            vec![-5.0, -5.0],
+        );
+        let mut scratch = Scratch::default();
+        let mut scores = |text: &str, token_budget| {
+            let options = PredictOptions {
+                token_budget: Some(token_budget),
+                ..Default::default()
+            };
+            naive_bayes
+                .scores_into(&document("", text), &options, &mut scratch)
+                .to_vec()
+        };
+
+        assert_eq!(
+            scores("the good bad", TokenBudget::First(2)),
+            [-0.7 - 1.0 - 3.0, -0.7 - 1.1 - 0.5]
+        );
+        // "bad" spreads 3.0 and "good" 2.5, "the" only 0.1
+        assert_eq!(
+            scores("the the bad the", TokenBudget::MostInformative(1)),
+            [-0.7 - 0.5, -0.7 - 3.5]
+        );
+        assert_eq!(
+            scores("the good the", TokenBudget::MostInformative(5)),
+            [-0.7 + (-3.0 - 2.0), -0.7 + (-0.5 - 2.0 * 1.1)]
+        );
+    }
+
+    #[test]
+    fn test_first_tokens_stop_tokenizing() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        /// Splits at whitespace, counting the tokens read.
+        struct Counting(Arc<AtomicUsize>);
+
+        impl Tokenizer for Counting {
+            fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+                let _ = self.try_for_each_token(text, &mut |token| {
+                    emit(token);
+                    ControlFlow::Continue(())
+                });
+            }
+
+            fn try_for_each_token(
+                &self,
+                text: &str,
+                emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+            ) -> ControlFlow<()> {
+                text.split_whitespace().try_for_each(|token| {
+                    self.0.fetch_add(1, Ordering::Relaxed);
+                    emit(token)
+                })
+            }
+        }
+
+        let documents = [document("pos", "good fun"), document("neg", "bad plot")];
+        let read = Arc::new(AtomicUsize::new(0));
+        let naive_bayes = NaiveBayes::new_with_tokenizer(
+            &documents,
+            documents.iter().map(|d| d.class.clone()).collect(),
+            vocabulary(&documents),
+            Counting(read.clone()),
+        );
+        let options = PredictOptions {
+            token_budget: Some(TokenBudget::First(2)),
+            ..Default::default()
+        };
+        read.store(0, Ordering::Relaxed);
+        let long = document("", "good unknown fun bad bad bad bad bad");
+        let scores = naive_bayes
+            .scores_into(&long, &options, &mut Scratch::default())
+            .to_vec();
+        assert_eq!(read.load(Ordering::Relaxed), 3);
+        assert_eq!(
+            scores,
+            naive_bayes.scores_into(&document("", "good fun"), &options, &mut Scratch::default())
+        );
+    }
+
+    #[test]
+    fn test_predict_scores_ranks_every_class() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["a", "b", "c"],
+            &["x"],
+            vec![-1.0, -1.0, -1.5],
+            vec![vec![(0, -2.0)], vec![(0, -0.5)], vec![(0, -1.0)]],
+            vec![-5.0, -5.0, -5.0],
+        );
+
+        assert_eq!(
+            naive_bayes.predict_scores(&document("", "x")),
+            [("b".into(), -1.5), ("c".into(), -2.5), ("a".into(), -3.0)]
+        );
+        let ranked = naive_bayes.predict_scores(&document("", ""));
+        assert_eq!(ranked[0], ("a".into(), -1.0));
+        assert_eq!(ranked[1], ("b".into(), -1.0));
+    }
+
+    #[test]
+    fn test_predict_top_k() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["a", "b", "c", "d"],
+            &["x"],
+            vec![-1.0, -1.0, -1.5, -1.0],
+            vec![vec![(0, -2.0)], vec![(0, -0.5)], vec![(0, -1.0)], vec![]],
+            vec![-5.0, -5.0, -5.0, -5.0],
+        );
+        let x = document("", "x");
+        assert_eq!(
+            naive_bayes.predict_top_k(&x, 2),
+            [("b".into(), -1.5), ("c".into(), -2.5)]
+        );
+        assert_eq!(
+            naive_bayes.predict_top_k(&x, 9),
+            naive_bayes.predict_scores(&x)
+        );
+        assert!(naive_bayes.predict_top_k(&x, 0).is_empty());
+        // ties go to the class listed first
+        let empty = document("", "");
+        assert_eq!(
+            naive_bayes.predict_top_k(&empty, 3),
+            [("a".into(), -1.0), ("b".into(), -1.0), ("d".into(), -1.0)]
+        );
+    }
+
+    #[test]
+    fn test_predict_proba_normalizes_long_documents() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["a", "b"],
+            &["x"],
+            vec![-0.7, -0.7],
+            vec![vec![(0, -2.0)], vec![(0, -2.0 - 1e-3)]],
+            vec![-5.0, -5.0],
+        );
+
+        // joint scores of about -2000 underflow a plain exp
+        let proba = naive_bayes.predict_proba(&document("", &"x ".repeat(1000)));
+        let expected = 1.0 / (1.0 + (-1.0f64).exp());
+        assert!((proba["a"] - expected).abs() < 1e-9);
+        assert!((proba["a"] + proba["b"] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ngram_backoff() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["a", "b"],
+            &["new", "new_york", "york"],
+            vec![-0.7, -0.7],
+            vec![
+                vec![(0, -1.0), (1, -1.0), (2, -3.0)],
+                vec![(0, -2.0), (1, -2.0), (2, -1.0)],
+            ],
+            vec![-5.0, -5.0],
+        );
+        let options = PredictOptions {
+            backoff: Some(NgramBackoff::default()),
+            ..Default::default()
+        };
+        let mut scratch = Scratch::default();
+
+        let document = document("", "new_york york_city new_jersey");
+        let plain = naive_bayes.scores_into(&document, &Default::default(), &mut scratch)[0];
+        assert_eq!(plain, -0.7 - 1.0);
+        // in-vocabulary bigrams are scored as they are; missing ones fall
+        // back to the known half of their words
+        let scores = naive_bayes.scores_into(&document, &options, &mut scratch);
+        assert_eq!(
+            scores,
+            [-0.7 - 1.0 - 0.5 * 3.0 - 0.5, -0.7 - 2.0 - 0.5 - 1.0]
+        );
+
+        // a lone backed-off word weighs 0.5, and per token it scores in full
+        let normalized = PredictOptions {
+            normalization: LengthNormalization::PerToken,
+            ..options
+        };
+        let lone = Document {
+            class: String::new(),
+            text: "new_jersey".into(),
+        };
+        let scores = naive_bayes.scores_into(&lone, &normalized, &mut scratch);
+        assert_eq!(scores, [-0.7 - 1.0, -0.7 - 2.0]);
+    }
+
+    #[test]
+    fn test_repetition_modes() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.7, -0.7],
+            vec![vec![(0, -0.5), (1, -2.0)], vec![(0, -2.5), (1, -0.25)]],
+            vec![-5.0, -5.0],
+        );
+        let stuffed = document("", &format!("bad {}", "good ".repeat(20)));
+        let with = |repetition| PredictOptions {
+            repetition,
+            ..Default::default()
+        };
+
+        assert_eq!(naive_bayes.predict(&stuffed), Some("pos".into()));
+        assert_eq!(
+            naive_bayes.predict_with(&stuffed, &with(Repetition::Presence)),
+            ["neg"]
+        );
+        let mut scratch = Scratch::default();
+        let scores = naive_bayes.scores_into(
+            &document("", "good good bad"),
+            &with(Repetition::LogDampened),
+            &mut scratch,
+        );
+        let dampened = 1.0 + 2.0f64.ln();
+        assert!((scores[0] - (-0.7 - 0.5 - 2.0 * dampened)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_repeated_words_add_their_multiplicity() {
+        let naive_bayes = NaiveBayes::from_parts(
+            &["neg", "pos"],
+            &["bad", "good"],
+            vec![-0.7, -0.7],
+            vec![vec![(0, -0.5), (1, -2.0)], vec![(0, -2.5), (1, -0.25)]],
+            vec![-5.0, -5.0],
+        );
+
+        let mut scratch = Scratch::default();
+        let scores = naive_bayes.scores_into(
+            &document("", "good bad good unknown good"),
+            &PredictOptions::default(),
+            &mut scratch,
+        );
+        assert_eq!(scores, [-0.7 - 0.5 - 3.0 * 2.0, -0.7 - 2.5 - 3.0 * 0.25]);
+    }
+
+    #[test]
+    fn test_model_keeps_tokenizer() {
+        let commas = |text: &str| -> Vec<String> { text.split(',').map(str::to_owned).collect() };
+        let documents = [
+            document("pos", "good,movie"),
+            document("neg", "bad,movie"),
+            document("pos", "good,plot"),
+        ];
+        let classes: HashSet<Class> = ["neg".into(), "pos".into()].into();
+        let vocab = vocabulary_with(&documents, &commas);
+        assert_eq!(vocab.len(), 4);
+        let mut model = NaiveBayes::new_with_tokenizer(&documents, classes, vocab, commas);
+        assert_eq!(model.counts.document_frequency[model.vocab["movie"]], 2);
+
+        // whitespace does not split, commas do
+        assert_eq!(model.tokenize("bad plot,good"), ["bad plot", "good"]);
+        assert_eq!(
+            model.predict(&document("", "bad,bad,plot")),
+            Some("neg".into())
+        );
+        model.partial_fit(&[document("neg", "plot,plot,plot")]);
+        assert_eq!(model.counts.words[0][&model.vocab["plot"]], 3.0);
+    }
+
     #[test]
     fn test_train() {
+        if !Path::new("dataset").is_dir() {
+            println!("### dataset folder not found, skipping");
+            return;
+        }
+
         println!("### starting to read dataset");
         let dataset = read_dataset("dataset").unwrap();
         println!("### dataset read successfully");
@@ -192,7 +1769,7 @@ mod tests {
         println!("### naive_bayes made successfully");
 
         println!("### starting to guess");
-        let guess = naive_bayes.guess(&dataset.test_docs[0]);
+        let guess = naive_bayes.predict(&dataset.test_docs[0]);
         println!("### guess: {:#?}", guess);
     }
 }
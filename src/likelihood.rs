@@ -0,0 +1,430 @@
+/// Stored entries of one class, as `(token, log-likelihood)` sorted by token.
+pub(crate) type Row = Vec<(usize, f64)>;
+
+/// Tables at least this dense are stored as a full `vocab × classes` matrix.
+const DENSE_THRESHOLD: f64 = 0.5;
+
+/// Log-likelihoods indexed by class and token id. Tokens without an entry for
+/// a class use that class's default likelihood. Both layouts are token-major,
+/// so scoring reads the entries of one token for every class contiguously.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Likelihoods {
+    vocab_size: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::persist::floats"))]
+    defaults: Vec<f64>,
+    /// Sum of the absolute likelihoods of every class over the vocabulary.
+    #[cfg_attr(feature = "serde", serde(with = "crate::persist::floats"))]
+    norms: Vec<f64>,
+    /// Spread between the highest and lowest likelihood of every token.
+    #[cfg_attr(feature = "serde", serde(with = "crate::persist::floats"))]
+    spreads: Vec<f64>,
+    layout: Layout,
+    values: Values,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Layout {
+    /// Cell `token * classes + class`, missing entries holding the default.
+    Dense,
+    /// Compressed sparse rows: entries of `token` are
+    /// `offsets[token]..offsets[token + 1]`, sorted by class.
+    Sparse {
+        offsets: Vec<usize>,
+        classes: Vec<u32>,
+    },
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Values {
+    Exact(#[cfg_attr(feature = "serde", serde(with = "crate::persist::floats"))] Vec<f64>),
+    /// Values snapped to a shared codebook, each entry storing only its index.
+    Quantized {
+        #[cfg_attr(feature = "serde", serde(with = "crate::persist::floats"))]
+        codebook: Vec<f64>,
+        codes: Vec<u32>,
+    },
+}
+
+impl Likelihoods {
+    pub(crate) fn new(rows: Vec<Row>, vocab_size: usize, defaults: Vec<f64>) -> Self {
+        Self::build(rows, vocab_size, defaults, None)
+    }
+
+    fn build(
+        rows: Vec<Row>,
+        vocab_size: usize,
+        defaults: Vec<f64>,
+        codebook: Option<Vec<f64>>,
+    ) -> Self {
+        let entries: usize = rows.iter().map(|row| row.len()).sum();
+        let norms = rows
+            .iter()
+            .zip(defaults.iter())
+            .map(|(row, default)| {
+                let stored: f64 = row.iter().map(|(_, value)| value.abs()).sum();
+                stored + (vocab_size - row.len()) as f64 * default.abs()
+            })
+            .collect();
+        let cells = rows.len() * vocab_size;
+        let dense = cells > 0 && entries as f64 / cells as f64 >= DENSE_THRESHOLD;
+
+        let classes = rows.len();
+        let (layout, values) = if dense {
+            let mut values: Vec<f64> = (0..cells).map(|cell| defaults[cell % classes]).collect();
+            for (class, row) in rows.iter().enumerate() {
+                for (token, value) in row {
+                    values[token * classes + class] = *value;
+                }
+            }
+            (Layout::Dense, values)
+        } else {
+            // transpose the class rows into token rows
+            let mut offsets = vec![0; vocab_size + 1];
+            for (token, _) in rows.iter().flatten() {
+                offsets[token + 1] += 1;
+            }
+            for token in 0..vocab_size {
+                offsets[token + 1] += offsets[token];
+            }
+            let mut next = offsets.clone();
+            let mut stored_classes = vec![0; entries];
+            let mut values = vec![0.0; entries];
+            for (class, row) in rows.into_iter().enumerate() {
+                for (token, value) in row {
+                    stored_classes[next[token]] = class as u32;
+                    values[next[token]] = value;
+                    next[token] += 1;
+                }
+            }
+            let layout = Layout::Sparse {
+                offsets,
+                classes: stored_classes,
+            };
+            (layout, values)
+        };
+
+        let values = match codebook {
+            None => Values::Exact(values),
+            Some(codebook) => Values::Quantized {
+                codes: values.iter().map(|v| encode(&codebook, *v)).collect(),
+                codebook,
+            },
+        };
+
+        let mut likelihoods = Self {
+            vocab_size,
+            defaults,
+            norms,
+            spreads: Vec::new(),
+            layout,
+            values,
+        };
+        likelihoods.spreads = (0..vocab_size)
+            .map(|token| {
+                let (min, max) = (0..classes)
+                    .map(|class| likelihoods.get(class, token))
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                        (min.min(v), max.max(v))
+                    });
+                let spread = max - min;
+                if spread.is_nan() || classes == 0 {
+                    0.0
+                } else {
+                    spread
+                }
+            })
+            .collect();
+        likelihoods
+    }
+
+    pub(crate) fn get(&self, class: usize, token: usize) -> f64 {
+        match self.position(class, token) {
+            Some(position) => self.value(position),
+            None => self.defaults[class],
+        }
+    }
+
+    /// Adds the likelihood of `token` under every class to `scores`, `weight`
+    /// times over.
+    pub(crate) fn add_token(&self, token: usize, weight: f64, scores: &mut [f64]) {
+        let classes = self.defaults.len();
+        match &self.layout {
+            Layout::Dense => {
+                let start = token * classes;
+                for (class, score) in scores.iter_mut().enumerate() {
+                    *score += weight * self.value(start + class);
+                }
+            }
+            Layout::Sparse { offsets, classes } => {
+                let mut entries = (offsets[token]..offsets[token + 1]).peekable();
+                for (class, score) in scores.iter_mut().enumerate() {
+                    *score += weight
+                        * match entries.next_if(|e| classes[*e] as usize == class) {
+                            Some(position) => self.value(position),
+                            None => self.defaults[class],
+                        };
+                }
+            }
+        }
+    }
+
+    pub(crate) fn norm(&self, class: usize) -> f64 {
+        self.norms[class]
+    }
+
+    /// How strongly `token` discriminates between classes: the spread of its
+    /// log-likelihoods.
+    pub(crate) fn informativeness(&self, token: usize) -> f64 {
+        self.spreads[token]
+    }
+
+    #[cfg(test)]
+    pub(crate) fn default(&self, class: usize) -> f64 {
+        self.defaults[class]
+    }
+
+    /// Whether `token` has its own entry for `class`, rather than the default.
+    pub(crate) fn is_stored(&self, class: usize, token: usize) -> bool {
+        match self.layout {
+            Layout::Dense => self.get(class, token).to_bits() != self.defaults[class].to_bits(),
+            Layout::Sparse { .. } => self.position(class, token).is_some(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_dense(&self) -> bool {
+        matches!(self.layout, Layout::Dense)
+    }
+
+    fn position(&self, class: usize, token: usize) -> Option<usize> {
+        match &self.layout {
+            Layout::Dense => Some(token * self.defaults.len() + class),
+            Layout::Sparse { offsets, classes } => {
+                let row = &classes[offsets[token]..offsets[token + 1]];
+                row.binary_search(&(class as u32))
+                    .ok()
+                    .map(|i| offsets[token] + i)
+            }
+        }
+    }
+
+    fn value(&self, position: usize) -> f64 {
+        match &self.values {
+            Values::Exact(values) => values[position],
+            Values::Quantized { codebook, codes } => codebook[codes[position] as usize],
+        }
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        (0..self.defaults.len())
+            .map(|class| {
+                (0..self.vocab_size)
+                    .filter(|token| self.is_stored(class, *token))
+                    .map(|token| (token, self.get(class, token)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn codebook(&self) -> Option<Vec<f64>> {
+        match &self.values {
+            Values::Exact(_) => None,
+            Values::Quantized { codebook, .. } => Some(codebook.clone()),
+        }
+    }
+
+    /// Drops the entries for which `keep(class, token)` is false, switching to
+    /// the sparse layout once the table is no longer dense enough.
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(usize, usize) -> bool) {
+        let rows = self
+            .rows()
+            .into_iter()
+            .enumerate()
+            .map(|(class, row)| {
+                row.into_iter()
+                    .filter(|(token, _)| keep(class, *token))
+                    .collect()
+            })
+            .collect();
+        *self = Self::build(
+            rows,
+            self.vocab_size,
+            std::mem::take(&mut self.defaults),
+            self.codebook(),
+        );
+    }
+
+    /// Snaps values lying within `epsilon` of each other to a shared value.
+    pub(crate) fn quantize(&mut self, epsilon: f64) {
+        let rows = self.rows();
+
+        let mut sorted: Vec<f64> = rows
+            .iter()
+            .flatten()
+            .map(|(_, value)| *value)
+            .chain(self.defaults.iter().copied())
+            .collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+        sorted.dedup();
+
+        // greedily open a bucket at the smallest unassigned value and extend it
+        // while every member stays within `epsilon` of the bucket midpoint
+        let mut codebook = Vec::new();
+        let mut bounds = Vec::new();
+        let mut start = 0;
+        while start < sorted.len() {
+            let low = sorted[start];
+            let mut end = start + 1;
+            if low.is_finite() {
+                while end < sorted.len() && sorted[end] - low <= 2.0 * epsilon {
+                    end += 1;
+                }
+                codebook.push((low + sorted[end - 1]) / 2.0);
+            } else {
+                codebook.push(low);
+            }
+            bounds.push(sorted[end - 1]);
+            start = end;
+        }
+        let snap =
+            |value: f64| codebook[bounds.partition_point(|bound| bound.total_cmp(&value).is_lt())];
+
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|(t, v)| (t, snap(v))).collect())
+            .collect();
+        let defaults = self.defaults.iter().map(|v| snap(*v)).collect();
+        *self = Self::build(rows, self.vocab_size, defaults, Some(codebook));
+    }
+
+    /// Rebuilds the table without entries equal to their class default and
+    /// without unused codebook values, in whichever layout suits the
+    /// remaining entries.
+    pub(crate) fn compact(&mut self) {
+        let rows: Vec<Row> = self
+            .rows()
+            .into_iter()
+            .zip(&self.defaults)
+            .map(|(row, default)| {
+                row.into_iter()
+                    .filter(|(_, value)| value.to_bits() != default.to_bits())
+                    .collect()
+            })
+            .collect();
+        let codebook = self.codebook().map(|_| {
+            let mut used: Vec<f64> = rows
+                .iter()
+                .flatten()
+                .map(|(_, value)| *value)
+                .chain(self.defaults.iter().copied())
+                .collect();
+            used.sort_unstable_by(f64::total_cmp);
+            used.dedup_by(|a, b| a.to_bits() == b.to_bits());
+            used
+        });
+        *self = Self::build(
+            rows,
+            self.vocab_size,
+            std::mem::take(&mut self.defaults),
+            codebook,
+        );
+    }
+
+    /// Bytes allocated on the heap.
+    pub(crate) fn heap_bytes(&self) -> usize {
+        let floats = self.defaults.capacity() + self.norms.capacity() + self.spreads.capacity();
+        let layout = match &self.layout {
+            Layout::Dense => 0,
+            Layout::Sparse { offsets, classes } => {
+                offsets.capacity() * size_of::<usize>() + classes.capacity() * size_of::<u32>()
+            }
+        };
+        let values = match &self.values {
+            Values::Exact(values) => values.capacity() * size_of::<f64>(),
+            Values::Quantized { codebook, codes } => {
+                codebook.capacity() * size_of::<f64>() + codes.capacity() * size_of::<u32>()
+            }
+        };
+        floats * size_of::<f64>() + layout + values
+    }
+
+    pub(crate) fn distinct_values(&self) -> usize {
+        match &self.values {
+            Values::Exact(values) => values.len(),
+            Values::Quantized { codebook, .. } => codebook.len(),
+        }
+    }
+}
+
+fn encode(codebook: &[f64], value: f64) -> u32 {
+    let code = codebook.partition_point(|v| v.total_cmp(&value).is_lt());
+    code.min(codebook.len() - 1) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize_within_epsilon() {
+        let row: Row = [-10.0, -9.95, -9.9, -5.0, -4.99, f64::NEG_INFINITY]
+            .into_iter()
+            .enumerate()
+            .collect();
+        let exact = Likelihoods::new(vec![row.clone()], 8, vec![-20.0]);
+        let mut quantized = Likelihoods::new(vec![row.clone()], 8, vec![-20.0]);
+        quantized.quantize(0.05);
+
+        assert_eq!(quantized.distinct_values(), 4);
+        for token in 0..8 {
+            let (value, snapped) = (exact.get(0, token), quantized.get(0, token));
+            if value.is_finite() {
+                assert!((snapped - value).abs() <= 0.05 + 1e-12);
+            } else {
+                assert_eq!(snapped, value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_layout_follows_density() {
+        let rows = vec![vec![(0, -1.0), (1, -2.0)], vec![(1, -3.0)]];
+        let mut likelihoods = Likelihoods::new(rows, 2, vec![-9.0, -8.0]);
+        assert!(likelihoods.is_dense());
+        assert_eq!(likelihoods.get(1, 0), -8.0);
+
+        likelihoods.retain(|_, token| token == 1);
+        assert!(likelihoods.is_dense());
+        likelihoods.retain(|class, _| class == 0);
+        assert!(!likelihoods.is_dense());
+        assert_eq!(likelihoods.get(0, 1), -2.0);
+        assert_eq!(likelihoods.get(1, 1), -8.0);
+        assert!(!likelihoods.is_stored(0, 0));
+    }
+
+    #[test]
+    fn test_add_token_matches_get() {
+        let rows = vec![
+            vec![(0, -1.0), (1, -1.5), (3, -2.0)],
+            vec![(2, -2.5), (3, -3.0)],
+            vec![(1, -4.0), (3, -5.0)],
+        ];
+        for retain_all in [true, false] {
+            let mut likelihoods = Likelihoods::new(rows.clone(), 4, vec![-9.0, -8.0, -7.0]);
+            if !retain_all {
+                likelihoods.retain(|_, token| token == 3);
+            }
+            assert_eq!(likelihoods.is_dense(), retain_all);
+            for token in 0..4 {
+                let mut scores = vec![0.5; 3];
+                likelihoods.add_token(token, 2.0, &mut scores);
+                for (class, score) in scores.into_iter().enumerate() {
+                    assert_eq!(score, 0.5 + 2.0 * likelihoods.get(class, token));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::{
+    ops::Deref,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crate::{Document, NaiveBayes, TrainError};
+
+/// A model that keeps learning through [`LiveModel::partial_fit`] while
+/// readers classify with immutable snapshots. Every update trains a private
+/// copy and then atomically publishes it as a new epoch, so a snapshot never
+/// observes a half-applied update.
+pub struct LiveModel {
+    trainer: Mutex<NaiveBayes>,
+    published: RwLock<Snapshot>,
+}
+
+/// A consistent, immutable view of a [`LiveModel`] at one epoch.
+#[derive(Clone)]
+pub struct Snapshot {
+    pub epoch: u64,
+    model: Arc<NaiveBayes>,
+}
+
+impl Deref for Snapshot {
+    type Target = NaiveBayes;
+
+    fn deref(&self) -> &NaiveBayes {
+        &self.model
+    }
+}
+
+impl LiveModel {
+    pub fn new(model: NaiveBayes) -> Self {
+        Self {
+            published: RwLock::new(Snapshot {
+                epoch: 0,
+                model: Arc::new(model.clone()),
+            }),
+            trainer: Mutex::new(model),
+        }
+    }
+
+    /// The latest published model. Cheap: it only clones an `Arc`.
+    pub fn snapshot(&self) -> Snapshot {
+        self.published.read().unwrap().clone()
+    }
+
+    /// Learns from `documents` and publishes the result, returning its epoch.
+    /// Concurrent updates are applied one after the other.
+    pub fn partial_fit(&self, documents: &[Document]) -> u64 {
+        let mut trainer = self.trainer.lock().unwrap();
+        trainer.partial_fit(documents);
+        self.publish(&trainer)
+    }
+
+    /// Trains a new class into the model and publishes the result, returning
+    /// its epoch. See [`NaiveBayes::add_class`].
+    pub fn add_class(&self, class: &str, documents: &[Document]) -> Result<u64, TrainError> {
+        let mut trainer = self.trainer.lock().unwrap();
+        trainer.add_class(class, documents)?;
+        Ok(self.publish(&trainer))
+    }
+
+    fn publish(&self, trainer: &NaiveBayes) -> u64 {
+        let model = Arc::new(trainer.clone());
+        let mut published = self.published.write().unwrap();
+        *published = Snapshot {
+            epoch: published.epoch + 1,
+            model,
+        };
+        published.epoch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashSet, thread};
+
+    fn document(class: &str, text: &str) -> Document {
+        Document {
+            class: class.into(),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn test_snapshots_stay_consistent_while_training() {
+        let documents = [document("pos", "good"), document("neg", "bad")];
+        let classes: HashSet<_> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = ["good", "bad"].iter().map(|w| w.to_string()).collect();
+        let live = LiveModel::new(NaiveBayes::new(&documents, classes, vocab));
+
+        let before = live.snapshot();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    live.partial_fit(&[document("pos", "good good")]);
+                }
+            });
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    let snapshot = live.snapshot();
+                    assert_eq!(snapshot.total_documents as u64, 2 + snapshot.epoch);
+                }
+            });
+        });
+
+        assert_eq!(before.epoch, 0);
+        assert_eq!(before.total_documents, 2);
+        let after = live.snapshot();
+        assert_eq!(after.epoch, 10);
+        assert_eq!(after.counts.documents, vec![1.0, 11.0]);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use crate::{
+    pipeline::{Stage, Transform},
+    Document, Tokenizer, TokenizerSpec, Word,
+};
+
+/// French and Italian words that elide before a vowel.
+const ELISIONS: &[&str] = &[
+    "all", "c", "d", "dall", "dell", "j", "l", "lorsqu", "m", "n", "nell", "puisqu", "qu", "quell",
+    "s", "sull", "t", "un",
+];
+
+/// Language-specific case folding rules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CaseFolding {
+    /// Unicode default lowercasing.
+    #[default]
+    Root,
+    /// Turkish and Azerbaijani: `I` folds to dotless `ı`, `İ` to `i`.
+    Turkic,
+    /// German: `ß` and `ẞ` fold to `ss`, so both spellings match.
+    German,
+    /// Greek: final sigma `ς` folds to `σ`.
+    Greek,
+}
+
+impl CaseFolding {
+    pub fn fold(&self, word: &str) -> String {
+        let mut folded = String::with_capacity(word.len());
+        for c in word.chars() {
+            match (self, c) {
+                (CaseFolding::Turkic, 'I') => folded.push('ı'),
+                (CaseFolding::Turkic, 'İ') => folded.push('i'),
+                (CaseFolding::German, 'ß' | 'ẞ') => folded.push_str("ss"),
+                (CaseFolding::Greek, 'ς') => folded.push('σ'),
+                _ => folded.extend(c.to_lowercase()),
+            }
+        }
+        folded
+    }
+}
+
+/// Case folding and tokenization configured together for one locale, usable
+/// as a pipeline stage or, through [`Localized`], as part of a model's
+/// tokenizer.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocaleProfile {
+    pub folding: CaseFolding,
+    /// Strip punctuation from both ends of every word.
+    pub trim_punctuation: bool,
+    /// Split elided articles and pronouns such as `l'` and `dell'` off the
+    /// following word.
+    pub split_elisions: bool,
+}
+
+impl LocaleProfile {
+    pub fn root() -> Self {
+        Self {
+            trim_punctuation: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn turkish() -> Self {
+        Self {
+            folding: CaseFolding::Turkic,
+            ..Self::root()
+        }
+    }
+
+    pub fn german() -> Self {
+        Self {
+            folding: CaseFolding::German,
+            ..Self::root()
+        }
+    }
+
+    pub fn greek() -> Self {
+        Self {
+            folding: CaseFolding::Greek,
+            ..Self::root()
+        }
+    }
+
+    /// French and Italian.
+    pub fn romance() -> Self {
+        Self {
+            split_elisions: true,
+            ..Self::root()
+        }
+    }
+
+    pub fn tokens(&self, text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        for word in text.split_whitespace() {
+            let word = match self.trim_punctuation {
+                true => word.trim_matches(|c: char| !c.is_alphanumeric()),
+                false => word,
+            };
+            if word.is_empty() {
+                continue;
+            }
+            let word = self.folding.fold(word);
+            match word.find(['\'', '’']).filter(|_| self.split_elisions) {
+                Some(end) if ELISIONS.contains(&&word[..end]) => {
+                    let rest = end + word[end..].chars().next().map_or(0, char::len_utf8);
+                    tokens.push(format!("{}'", &word[..end]));
+                    if rest < word.len() {
+                        tokens.push(word[rest..].to_owned());
+                    }
+                }
+                _ => tokens.push(word),
+            }
+        }
+        tokens
+    }
+
+    pub fn text(&self, text: &str) -> String {
+        self.tokens(text).join(" ")
+    }
+}
+
+/// A tokenizer whose tokens are trimmed, folded and split by the
+/// conventions of a locale, so that models keep them when saved.
+#[derive(Clone, Debug)]
+pub struct Localized<T> {
+    pub tokenizer: T,
+    pub profile: LocaleProfile,
+}
+
+impl<T: Tokenizer> Tokenizer for Localized<T> {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        self.tokenizer.for_each_token(text, &mut |token| {
+            for token in self.profile.tokens(token) {
+                emit(&token);
+            }
+        });
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::Localized(
+            Box::new(self.tokenizer.spec()?),
+            self.profile.clone(),
+        ))
+    }
+}
+
+impl Stage for LocaleProfile {
+    fn fit(&self, _: &[Document], _: &HashSet<Word>) -> Box<dyn Transform> {
+        Box::new(self.clone())
+    }
+}
+
+impl Transform for LocaleProfile {
+    fn transform(&self, document: &Document) -> Document {
+        Document {
+            class: document.class.clone(),
+            text: self.text(&document.text),
+        }
+    }
+
+    fn transform_vocab(&self, vocab: HashSet<Word>) -> HashSet<Word> {
+        vocab.iter().flat_map(|word| self.tokens(word)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_profiles() {
+        assert_eq!(LocaleProfile::root().text("ILIK İstanbul"), "ilik i̇stanbul");
+        assert_eq!(
+            LocaleProfile::turkish().text("ILIK İstanbul"),
+            "ılık istanbul"
+        );
+        assert_eq!(
+            LocaleProfile::german().tokens("Die STRAẞE, die Straße!"),
+            ["die", "strasse", "die", "strasse"]
+        );
+        assert_eq!(LocaleProfile::greek().text("ΟΔΟΣ οδός"), "οδοσ οδόσ");
+        assert_eq!(
+            LocaleProfile::romance().tokens("L'Homme dell'arte aujourd'hui"),
+            ["l'", "homme", "dell'", "arte", "aujourd'hui"]
+        );
+        assert_eq!(LocaleProfile::default().text("A, b"), "a, b");
+
+        let localized = Localized {
+            tokenizer: crate::WhitespaceTokenizer,
+            profile: LocaleProfile::romance(),
+        };
+        let rebuilt = localized.spec().unwrap().build().unwrap();
+        assert_eq!(
+            rebuilt.tokenize("L'Homme, dell'arte"),
+            ["l'", "homme", "dell'", "arte"]
+        );
+    }
+}
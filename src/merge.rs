@@ -0,0 +1,131 @@
+use std::{fmt, hash::Hash};
+
+use crate::{NaiveBayes, Tokenizer};
+
+/// Why two models could not be merged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MergeError {
+    /// The models were trained over different vocabularies.
+    VocabularyMismatch,
+    /// The models were trained on different classes.
+    ClassMismatch,
+    /// The models use different smoothing, so no merge matches both.
+    AlphaMismatch,
+    /// The models split text differently, so their counts are over
+    /// different tokens.
+    TokenizerMismatch,
+    /// TF-IDF weighted models, whose counts depend on the idf of their own
+    /// shard.
+    TfIdf,
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeError::VocabularyMismatch => write!(f, "the vocabularies differ"),
+            MergeError::ClassMismatch => write!(f, "the classes differ"),
+            MergeError::AlphaMismatch => write!(f, "the smoothing alphas differ"),
+            MergeError::TokenizerMismatch => write!(f, "the tokenizers differ"),
+            MergeError::TfIdf => write!(f, "TF-IDF weighted models cannot be merged"),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl<L: Clone + Eq + Hash, F: Eq + Hash> NaiveBayes<L, F> {
+    /// Combines models trained on separate shards of a corpus into the model
+    /// of the whole corpus, by summing their counts and re-estimating. Both
+    /// must share vocabulary, classes, smoothing and tokenizer; tokenizers
+    /// without a [`crate::TokenizerSpec`] cannot be compared and are taken to
+    /// match. The clipping of `self`
+    /// is kept; quantization, feature limits and training summaries are not.
+    pub fn merge(mut self, other: Self) -> Result<Self, MergeError> {
+        if self.vocab != other.vocab {
+            return Err(MergeError::VocabularyMismatch);
+        }
+        if self.classes != other.classes {
+            return Err(MergeError::ClassMismatch);
+        }
+        if self.alpha.to_bits() != other.alpha.to_bits() {
+            return Err(MergeError::AlphaMismatch);
+        }
+        if self.tokenizer.spec() != other.tokenizer.spec() {
+            return Err(MergeError::TokenizerMismatch);
+        }
+        if self.idf.is_some() || other.idf.is_some() {
+            return Err(MergeError::TfIdf);
+        }
+        self.counts = std::mem::take(&mut self.counts).merge(other.counts);
+        self.total_documents += other.total_documents;
+        self.summary = None;
+        self.estimate();
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::{vocabulary, Class, Document, StopwordFilter, WhitespaceTokenizer};
+
+    #[test]
+    fn test_merge_shards() {
+        let documents: Vec<Document> = [
+            ("pos", "good movie"),
+            ("neg", "bad movie"),
+            ("pos", "good plot"),
+            ("neg", "bad bad plot"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = vocabulary(&documents);
+        let train =
+            |documents: &[Document]| NaiveBayes::new(documents, classes.clone(), vocab.clone());
+
+        let merged = train(&documents[..2])
+            .merge(train(&documents[2..]))
+            .unwrap();
+        let whole = train(&documents);
+        assert_eq!(merged.counts, whole.counts);
+        assert_eq!(merged.log_prior, whole.log_prior);
+        assert_eq!(merged.total_documents, 4);
+
+        let other_vocab = NaiveBayes::new(&documents, classes.clone(), ["good".into()].into());
+        assert_eq!(
+            whole.clone().merge(other_vocab).err(),
+            Some(MergeError::VocabularyMismatch)
+        );
+        let other_classes = NaiveBayes::new(&documents, ["pos".into()].into(), vocab.clone());
+        assert_eq!(
+            whole.clone().merge(other_classes).err(),
+            Some(MergeError::ClassMismatch)
+        );
+        let mut smoothed = train(&documents);
+        smoothed.set_alpha(0.5);
+        assert_eq!(
+            whole.clone().merge(smoothed).err(),
+            Some(MergeError::AlphaMismatch)
+        );
+        let filtered = NaiveBayes::new_with_tokenizer(
+            &documents,
+            classes.clone(),
+            vocab.clone(),
+            StopwordFilter {
+                tokenizer: WhitespaceTokenizer,
+                stopwords: ["plot".into()].into(),
+            },
+        );
+        assert_eq!(
+            whole.merge(filtered).err(),
+            Some(MergeError::TokenizerMismatch)
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::Class;
+
+/// Alert levels of an [`AccuracyMonitor`]. A disabled threshold never fires.
+#[derive(Clone, Debug, Default)]
+pub struct MonitorThresholds {
+    pub min_accuracy: Option<f64>,
+    pub min_macro_f1: Option<f64>,
+}
+
+/// A metric of the window that dropped below its threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Alert {
+    Accuracy(f64),
+    MacroF1(f64),
+}
+
+/// Accuracy and macro F1 of a deployed model over its latest `window`
+/// predictions, as their true labels arrive.
+pub struct AccuracyMonitor {
+    window: usize,
+    thresholds: MonitorThresholds,
+    /// `(predicted, actual)` pairs, oldest first.
+    outcomes: VecDeque<(Class, Class)>,
+    /// Whether each threshold is currently violated, so alerts fire once
+    /// per drop rather than on every record.
+    below: (bool, bool),
+}
+
+impl AccuracyMonitor {
+    pub fn new(window: usize, thresholds: MonitorThresholds) -> Self {
+        Self {
+            window: window.max(1),
+            thresholds,
+            outcomes: VecDeque::with_capacity(window),
+            below: (false, false),
+        }
+    }
+
+    /// Records the true label of a prediction. Returns the metrics that just
+    /// dropped below their threshold; nothing is reported until the window
+    /// is full.
+    pub fn record(&mut self, predicted: &str, actual: &str) -> Vec<Alert> {
+        if self.outcomes.len() == self.window {
+            self.outcomes.pop_front();
+        }
+        self.outcomes
+            .push_back((predicted.to_owned(), actual.to_owned()));
+        if self.outcomes.len() < self.window {
+            return Vec::new();
+        }
+
+        let mut alerts = Vec::new();
+        let accuracy = self.accuracy();
+        let below = self
+            .thresholds
+            .min_accuracy
+            .is_some_and(|min| accuracy < min);
+        if below && !self.below.0 {
+            alerts.push(Alert::Accuracy(accuracy));
+        }
+        self.below.0 = below;
+
+        let f1 = self.macro_f1();
+        let below = self.thresholds.min_macro_f1.is_some_and(|min| f1 < min);
+        if below && !self.below.1 {
+            alerts.push(Alert::MacroF1(f1));
+        }
+        self.below.1 = below;
+        alerts
+    }
+
+    pub fn len(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    pub fn accuracy(&self) -> f64 {
+        let correct = self.outcomes.iter().filter(|(p, a)| p == a).count();
+        correct as f64 / self.outcomes.len().max(1) as f64
+    }
+
+    /// Mean F1 over every class predicted or observed in the window.
+    pub fn macro_f1(&self) -> f64 {
+        let classes: BTreeSet<&str> = self
+            .outcomes
+            .iter()
+            .flat_map(|(p, a)| [p.as_str(), a.as_str()])
+            .collect();
+        let total: f64 = classes
+            .iter()
+            .map(|class| {
+                let (mut tp, mut fp, mut fn_) = (0, 0, 0);
+                for (p, a) in self.outcomes.iter() {
+                    match (p == class, a == class) {
+                        (true, true) => tp += 1,
+                        (true, false) => fp += 1,
+                        (false, true) => fn_ += 1,
+                        (false, false) => {}
+                    }
+                }
+                2.0 * tp as f64 / (2 * tp + fp + fn_).max(1) as f64
+            })
+            .sum();
+        total / classes.len().max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monitor_alerts_once_per_drop() {
+        let mut monitor = AccuracyMonitor::new(
+            4,
+            MonitorThresholds {
+                min_accuracy: Some(0.6),
+                min_macro_f1: None,
+            },
+        );
+        for _ in 0..3 {
+            assert!(monitor.record("a", "a").is_empty());
+        }
+        assert!(monitor.record("b", "b").is_empty());
+        assert_eq!(monitor.accuracy(), 1.0);
+        assert_eq!(monitor.macro_f1(), 1.0);
+
+        assert!(monitor.record("a", "b").is_empty());
+        assert_eq!(monitor.record("a", "b"), [Alert::Accuracy(0.5)]);
+        assert!(monitor.record("b", "a").is_empty());
+        assert_eq!(monitor.len(), 4);
+
+        // recovers, then drops again
+        for _ in 0..4 {
+            monitor.record("b", "b");
+        }
+        monitor.record("a", "b");
+        assert_eq!(monitor.record("a", "b"), [Alert::Accuracy(0.5)]);
+    }
+}
@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use crate::{Document, Tokenizer, TokenizerSpec, Word};
+
+/// A tokenizer emitting the runs of `min` to `max` consecutive tokens,
+/// joined with `_` as expected by [`crate::NgramBackoff`], so that phrases
+/// such as "not good" become words of their own. Unigrams are kept when
+/// `min` is 1.
+#[derive(Clone, Debug)]
+pub struct WordNgrams<T> {
+    pub tokenizer: T,
+    pub min: usize,
+    pub max: usize,
+}
+
+impl<T: Tokenizer> Tokenizer for WordNgrams<T> {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        let tokens = self.tokenizer.tokenize(text);
+        for_each_ngram(&tokens, self.min, self.max, &mut |_, ngram| emit(ngram));
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::WordNgrams(
+            Box::new(self.tokenizer.spec()?),
+            self.min,
+            self.max,
+        ))
+    }
+}
+
+/// Splits text into its runs of `min` to `max` characters instead of words,
+/// which suits short and misspelled text such as text messages. Runs of
+/// whitespace count as a single space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharNgramTokenizer {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Default for CharNgramTokenizer {
+    fn default() -> Self {
+        Self { min: 3, max: 5 }
+    }
+}
+
+impl Tokenizer for CharNgramTokenizer {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        let mut collapsed = String::with_capacity(text.len());
+        for word in text.split_whitespace() {
+            if !collapsed.is_empty() {
+                collapsed.push(' ');
+            }
+            collapsed.push_str(word);
+        }
+        let starts: Vec<usize> = collapsed
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([collapsed.len()])
+            .collect();
+        for n in self.min.max(1)..=self.max {
+            for window in starts.windows(n + 1) {
+                emit(&collapsed[window[0]..window[n]]);
+            }
+        }
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::CharNgrams {
+            min: self.min,
+            max: self.max,
+        })
+    }
+}
+
+/// Calls `emit` with the words and the joined form of every n-gram of
+/// `tokens` from length `min` to `max`, shortest first.
+fn for_each_ngram(
+    tokens: &[String],
+    min: usize,
+    max: usize,
+    emit: &mut dyn FnMut(&[String], &str),
+) {
+    let mut ngram = String::new();
+    for n in min.max(1)..=max {
+        for window in tokens.windows(n) {
+            ngram.clear();
+            for (i, token) in window.iter().enumerate() {
+                if i > 0 {
+                    ngram.push('_');
+                }
+                ngram.push_str(token);
+            }
+            emit(window, &ngram);
+        }
+    }
+}
+
+/// The n-grams of `documents` made only of vocabulary `words`, which extend
+/// a vocabulary of single words to an n-gram range.
+pub(crate) fn ngram_vocabulary(
+    documents: &[Document],
+    words: &HashSet<Word>,
+    tokenizer: &dyn Tokenizer,
+    min: usize,
+    max: usize,
+) -> HashSet<Word> {
+    let mut vocab = HashSet::new();
+    for document in documents {
+        let tokens = tokenizer.tokenize(&document.text);
+        for_each_ngram(&tokens, min, max, &mut |window, ngram| {
+            if window.iter().all(|word| words.contains(word)) && !vocab.contains(ngram) {
+                vocab.insert(ngram.to_owned());
+            }
+        });
+    }
+    vocab
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Classifier, Document, NaiveBayesBuilder, WhitespaceTokenizer};
+
+    #[test]
+    fn test_char_ngrams() {
+        let tokenizer = CharNgramTokenizer { min: 2, max: 3 };
+        assert_eq!(
+            tokenizer.tokenize("wïn  it"),
+            ["wï", "ïn", "n ", " i", "it", "wïn", "ïn ", "n i", " it"]
+        );
+        assert!(tokenizer.tokenize("a").is_empty());
+        let rebuilt = tokenizer.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("abc"), ["ab", "bc", "abc"]);
+
+        // misspelled words still share most of their character n-grams
+        let documents: Vec<Document> = [("spam", "free prize winner"), ("ham", "see you at lunch")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let (fitted, _) = NaiveBayesBuilder::new()
+            .documents(&documents)
+            .char_ngrams(3, 5)
+            .fit()
+            .unwrap();
+        let unseen = Document {
+            class: String::new(),
+            text: "fre3 priz3 winnr".into(),
+        };
+        assert_eq!(fitted.predict(&unseen), Some("spam".into()));
+    }
+
+    #[test]
+    fn test_word_ngrams() {
+        let ngrams = WordNgrams {
+            tokenizer: WhitespaceTokenizer,
+            min: 1,
+            max: 2,
+        };
+        assert_eq!(
+            ngrams.tokenize("not good at all"),
+            ["not", "good", "at", "all", "not_good", "good_at", "at_all"]
+        );
+        let rebuilt = ngrams.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("a b"), ["a", "b", "a_b"]);
+
+        let documents: Vec<Document> = [("pos", "good"), ("neg", "not good"), ("neg", "bad")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let (fitted, _) = NaiveBayesBuilder::new()
+            .documents(&documents)
+            .ngram_range(1, 2)
+            .fit()
+            .unwrap();
+        assert!(fitted
+            .model()
+            .document_frequencies()
+            .any(|(word, _)| word == "not_good"));
+        let unseen = Document {
+            class: String::new(),
+            text: "really not good".into(),
+        };
+        assert_eq!(fitted.predict(&unseen), Some("neg".into()));
+        assert!(fitted.scores(&unseen).iter().all(|score| score.is_finite()));
+
+        let error = NaiveBayesBuilder::new()
+            .documents(&documents)
+            .ngram_range(2, 1)
+            .fit()
+            .err();
+        assert_eq!(error, Some(crate::TrainError::InvalidNgramRange));
+    }
+}
@@ -0,0 +1,155 @@
+use std::{borrow::Cow, ops::ControlFlow};
+
+use crate::{locale::CaseFolding, Tokenizer, TokenizerSpec};
+
+/// A Unicode normalization form, applied with the `unicode` feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnicodeForm {
+    /// Canonical composition: precomposed and combining accents match.
+    Nfc,
+    /// Compatibility composition, which also folds ligatures, full-width
+    /// letters and the like into their plain forms.
+    Nfkc,
+}
+
+/// Preprocessing of every token, so that spellings such as `Great` and
+/// `great` count as the same word. Models keep it with their tokenizer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextNormalization {
+    pub case_folding: Option<CaseFolding>,
+    /// Needs the `unicode` feature, like `strip_accents`: tokenizers saved
+    /// with either cannot be rebuilt without it.
+    pub form: Option<UnicodeForm>,
+    /// Drop combining accents, so that `café` matches `cafe`.
+    pub strip_accents: bool,
+}
+
+impl TextNormalization {
+    pub fn lowercase() -> Self {
+        Self {
+            case_folding: Some(CaseFolding::Root),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the normalization needs the `unicode` feature.
+    pub fn needs_unicode(&self) -> bool {
+        self.form.is_some() || self.strip_accents
+    }
+
+    pub fn apply<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        let mut word = Cow::Borrowed(word);
+        #[cfg(feature = "unicode")]
+        {
+            use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+            if self.strip_accents {
+                word = Cow::Owned(word.nfd().filter(|c| !is_combining_mark(*c)).collect());
+            }
+            match self.form {
+                Some(UnicodeForm::Nfc) => word = Cow::Owned(word.nfc().collect()),
+                Some(UnicodeForm::Nfkc) => word = Cow::Owned(word.nfkc().collect()),
+                None => {}
+            }
+        }
+        if let Some(folding) = self.case_folding {
+            word = Cow::Owned(folding.fold(&word));
+        }
+        word
+    }
+}
+
+/// A tokenizer whose tokens are normalized.
+#[derive(Clone, Debug)]
+pub struct Normalized<T> {
+    pub tokenizer: T,
+    pub normalization: TextNormalization,
+}
+
+impl<T: Tokenizer> Tokenizer for Normalized<T> {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        self.tokenizer
+            .for_each_token(text, &mut |token| emit(&self.normalization.apply(token)));
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.tokenizer
+            .try_for_each_token(text, &mut |token| emit(&self.normalization.apply(token)))
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        let tokenizer = self.tokenizer.spec()?;
+        Some(TokenizerSpec::Normalized(
+            Box::new(tokenizer),
+            self.normalization,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WhitespaceTokenizer;
+
+    #[test]
+    fn test_normalized_tokens() {
+        let tokenizer = Normalized {
+            tokenizer: WhitespaceTokenizer,
+            normalization: TextNormalization::lowercase(),
+        };
+        assert_eq!(tokenizer.tokenize("Great GREAT great"), ["great"; 3]);
+        let rebuilt = tokenizer.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("Movie"), ["movie"]);
+        assert!(TextNormalization::default().is_identity());
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    #[test]
+    fn test_unicode_normalization_needs_the_feature() {
+        let stripped = TextNormalization {
+            strip_accents: true,
+            ..Default::default()
+        };
+        let spec = TokenizerSpec::Normalized(Box::new(TokenizerSpec::Whitespace), stripped);
+        assert!(spec.build().is_err());
+        let lowercase = TokenizerSpec::Normalized(
+            Box::new(TokenizerSpec::Whitespace),
+            TextNormalization::lowercase(),
+        );
+        assert!(lowercase.build().is_ok());
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_unicode_normalization() {
+        let normalize =
+            |normalization: TextNormalization, word| normalization.apply(word).into_owned();
+        let nfc = TextNormalization {
+            form: Some(UnicodeForm::Nfc),
+            ..Default::default()
+        };
+        // "e" followed by a combining acute accent
+        assert_eq!(normalize(nfc, "cafe\u{301}"), "café");
+        let nfkc = TextNormalization {
+            form: Some(UnicodeForm::Nfkc),
+            ..Default::default()
+        };
+        assert_eq!(normalize(nfkc, "ﬁne"), "fine");
+        let stripped = TextNormalization {
+            strip_accents: true,
+            ..TextNormalization::lowercase()
+        };
+        assert_eq!(normalize(stripped, "Café"), "cafe");
+        assert_eq!(normalize(stripped, "cafe\u{301}"), "cafe");
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    fmt,
+    fs::File,
+    hash::Hash,
+    io::{self, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use crate::NaiveBayes;
+
+/// Start of every binary model.
+const MAGIC: &[u8; 4] = b"NBAY";
+/// Version of the binary layout, bumped whenever the model fields change.
+/// Version 3 added the idf of TF-IDF weighted models.
+const FORMAT_VERSION: u16 = 3;
+/// Oldest version still read: version 2 models are not TF-IDF weighted.
+const OLDEST_FORMAT_VERSION: u16 = 2;
+
+/// Why [`NaiveBayes::from_bytes`] could not read a model.
+#[derive(Debug)]
+pub enum FormatError {
+    /// The bytes do not start with the model header.
+    BadMagic,
+    /// A model written by an incompatible version of the library.
+    UnsupportedVersion(u16),
+    /// Corrupt or truncated model data.
+    Corrupt(String),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::BadMagic => write!(f, "not a naive bayes model"),
+            FormatError::UnsupportedVersion(version) => {
+                write!(f, "unsupported model format version {}", version)
+            }
+            FormatError::Corrupt(reason) => write!(f, "corrupt model: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl NaiveBayes {
+    /// Reads a model written by [`NaiveBayes::to_bytes`]. Models with
+    /// labels or features of other types are read with
+    /// [`NaiveBayes::from_bytes_labeled`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormatError> {
+        Self::from_bytes_labeled(bytes)
+    }
+
+    /// Reads a model written by [`NaiveBayes::save_json`]. Models with
+    /// labels or features of other types are read with
+    /// [`NaiveBayes::load_json_labeled`].
+    pub fn load_json(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::load_json_labeled(path)
+    }
+}
+
+/// Saving and loading models of any label and feature type that serde can
+/// handle. Only the readers of models of names and words are short, as the
+/// type to read cannot be inferred otherwise.
+impl<L, F> NaiveBayes<L, F>
+where
+    L: serde::Serialize + serde::de::DeserializeOwned,
+    F: serde::Serialize + serde::de::DeserializeOwned + Eq + Hash,
+{
+    /// The whole model in a compact binary layout: a magic header, the
+    /// format version as a little-endian `u16`, and the model as CBOR.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        ciborium::into_writer(self, &mut bytes).expect("writing to a Vec cannot fail");
+        bytes
+    }
+
+    /// Reads a model written by [`NaiveBayes::to_bytes`].
+    pub fn from_bytes_labeled(bytes: &[u8]) -> Result<Self, FormatError> {
+        let body = bytes.strip_prefix(MAGIC).ok_or(FormatError::BadMagic)?;
+        let (version, body) = match body {
+            [low, high, body @ ..] => (u16::from_le_bytes([*low, *high]), body),
+            _ => return Err(FormatError::Corrupt("missing format version".into())),
+        };
+        if !(OLDEST_FORMAT_VERSION..=FORMAT_VERSION).contains(&version) {
+            return Err(FormatError::UnsupportedVersion(version));
+        }
+        ciborium::from_reader(body).map_err(|error| FormatError::Corrupt(error.to_string()))
+    }
+
+    /// Writes the whole model, counts and smoothing settings included, as
+    /// JSON to `path`.
+    pub fn save_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Reads a model written by [`NaiveBayes::save_json`].
+    pub fn load_json_labeled(path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Serializes float vectors with infinities and NaN, which JSON numbers
+/// cannot hold, as the strings `"inf"`, `"-inf"` and `"NaN"`.
+pub(crate) mod floats {
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Float {
+        Finite(f64),
+        NonFinite(String),
+    }
+
+    pub(crate) fn serialize<S: Serializer>(
+        values: &[f64],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(|value| match value.is_finite() {
+            true => Float::Finite(*value),
+            false => Float::NonFinite(value.to_string()),
+        }))
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<f64>, D::Error> {
+        Vec::<Float>::deserialize(deserializer)?
+            .into_iter()
+            .map(|value| match value {
+                Float::Finite(value) => Ok(value),
+                Float::NonFinite(value) => value.parse().map_err(D::Error::custom),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::{audit::AuditOptions, Class, Document, TrainOptions};
+
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let documents: Vec<Document> = [
+            ("pos", "good movie good cast"),
+            ("neg", "bad movie"),
+            ("neg", "bad plot"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let options = TrainOptions {
+            // unseen words get a likelihood of zero, stored as -inf
+            alpha: Some(0.0),
+            audit: Some(AuditOptions::default()),
+            ..Default::default()
+        };
+        let (mut model, _) =
+            NaiveBayes::try_new(&documents, classes, crate::vocabulary(&documents), &options)
+                .unwrap();
+        model.quantize_likelihoods(0.01);
+
+        let path = std::env::temp_dir().join(format!("naive-bayes-{}.json", std::process::id()));
+        model.save_json(&path).unwrap();
+        let mut loaded = NaiveBayes::load_json(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.alpha(), 0.0);
+        assert_eq!(loaded.classes(), model.classes());
+        assert_eq!(loaded.counts, model.counts);
+        assert_eq!(
+            loaded
+                .training_summary()
+                .unwrap()
+                .class("pos")
+                .unwrap()
+                .documents,
+            1
+        );
+        for text in ["good cast", "bad plot", "good bad unknown"] {
+            let document = Document {
+                class: String::new(),
+                text: text.into(),
+            };
+            let expected: Vec<u64> = crate::Classifier::scores(&model, &document)
+                .iter()
+                .map(|s| s.to_bits())
+                .collect();
+            let scores: Vec<u64> = crate::Classifier::scores(&loaded, &document)
+                .iter()
+                .map(|s| s.to_bits())
+                .collect();
+            assert_eq!(scores, expected);
+        }
+        // training continues from the loaded counts
+        loaded.partial_fit(&documents);
+        assert_eq!(loaded.counted_documents(), 6.0);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let documents: Vec<Document> = (0..200)
+            .map(|i| Document {
+                class: format!("c{}", i % 4),
+                text: format!("w{} w{} w{}", i % 50, i % 37, i),
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let model = NaiveBayes::new(&documents, classes, crate::vocabulary(&documents));
+
+        let bytes = model.to_bytes();
+        assert!(bytes.len() < serde_json::to_vec(&model).unwrap().len());
+        let loaded = NaiveBayes::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.counts, model.counts);
+        assert_eq!(loaded.log_prior, model.log_prior);
+        assert_eq!(
+            crate::Classifier::scores(&loaded, &documents[7]),
+            crate::Classifier::scores(&model, &documents[7])
+        );
+
+        let typed = model
+            .clone()
+            .with_labels(|class| class[1..].parse::<u8>().unwrap())
+            .unwrap();
+        let loaded = NaiveBayes::<u8>::from_bytes_labeled(&typed.to_bytes()).unwrap();
+        assert_eq!(loaded.classes(), [0, 1, 2, 3]);
+        assert_eq!(loaded.predict(&documents[7]), typed.predict(&documents[7]));
+
+        assert!(matches!(
+            NaiveBayes::from_bytes(b"{\"vocab\""),
+            Err(FormatError::BadMagic)
+        ));
+        let mut future = bytes.clone();
+        future[4] = 9;
+        assert!(matches!(
+            NaiveBayes::from_bytes(&future),
+            Err(FormatError::UnsupportedVersion(9))
+        ));
+        // version 2 had no idf, which reads as no TF-IDF weighting
+        let mut old = bytes.clone();
+        old[4] = 2;
+        assert_eq!(NaiveBayes::from_bytes(&old).unwrap().idf, None);
+        old[4] = 1;
+        assert!(matches!(
+            NaiveBayes::from_bytes(&old),
+            Err(FormatError::UnsupportedVersion(1))
+        ));
+        assert!(matches!(
+            NaiveBayes::from_bytes(&bytes[..bytes.len() / 2]),
+            Err(FormatError::Corrupt(_))
+        ));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_tokenizer_is_saved() {
+        let documents: Vec<Document> = [("pos", "good, fun"), ("neg", "bad... dull")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = ["bad", "dull", "fun", "good"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let tokenizer = crate::RegexTokenizer::new(r"\w+").unwrap();
+        let model = NaiveBayes::new_with_tokenizer(&documents, classes, vocab, tokenizer);
+
+        let loaded = NaiveBayes::from_bytes(&model.to_bytes()).unwrap();
+        assert_eq!(loaded.tokenize("fun!"), ["fun"]);
+        assert_eq!(loaded.counts, model.counts);
+
+        let custom = |text: &str| -> Vec<String> { vec![text.to_owned()] };
+        let model =
+            NaiveBayes::new_with_tokenizer(&documents, HashSet::new(), HashSet::new(), custom);
+        assert!(serde_json::to_string(&model).is_err());
+    }
+}
@@ -0,0 +1,458 @@
+use std::{collections::HashSet, fmt};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::{
+    rng::{RandomSource, SplitMix64},
+    Class, Classifier, Document, NaiveBayes, TrainError, TrainOptions, TrainWarning, Word,
+};
+
+/// A preprocessing step that is learned from training documents.
+pub trait Stage: Send + Sync {
+    fn fit(&self, documents: &[Document], vocab: &HashSet<Word>) -> Box<dyn Transform>;
+}
+
+/// A fitted stage, applied identically to training and unseen documents.
+pub trait Transform: Send + Sync {
+    fn transform(&self, document: &Document) -> Document;
+
+    fn transform_vocab(&self, vocab: HashSet<Word>) -> HashSet<Word> {
+        vocab
+    }
+}
+
+/// Keeps only the words of a selected vocabulary.
+pub struct VocabularyFilter {
+    pub keep: HashSet<Word>,
+}
+
+impl Transform for VocabularyFilter {
+    fn transform(&self, document: &Document) -> Document {
+        let text = document
+            .words()
+            .into_iter()
+            .filter(|word| self.keep.contains(*word))
+            .collect::<Vec<_>>()
+            .join(" ");
+        Document {
+            class: document.class.clone(),
+            text,
+        }
+    }
+
+    fn transform_vocab(&self, vocab: HashSet<Word>) -> HashSet<Word> {
+        vocab
+            .into_iter()
+            .filter(|word| self.keep.contains(word))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Box<dyn Stage>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stage(mut self, stage: impl Stage + 'static) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    pub fn fit(
+        &self,
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+    ) -> FittedPipeline {
+        let (transforms, documents, vocab) = self.fit_stages(documents, vocab);
+        FittedPipeline {
+            transforms,
+            model: NaiveBayes::new(&documents, classes, vocab),
+        }
+    }
+
+    /// Like [`Pipeline::fit`], but trains the model through
+    /// [`NaiveBayes::try_new`].
+    pub fn try_fit(
+        &self,
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        options: &TrainOptions,
+    ) -> Result<(FittedPipeline, Vec<TrainWarning>), TrainError> {
+        let (transforms, documents, vocab) = self.fit_stages(documents, vocab);
+        let (model, warnings) = NaiveBayes::try_new(&documents, classes, vocab, options)?;
+        Ok((FittedPipeline { transforms, model }, warnings))
+    }
+
+    /// Fits every stage on the output of the previous one.
+    fn fit_stages(
+        &self,
+        documents: &[Document],
+        mut vocab: HashSet<Word>,
+    ) -> (Vec<Box<dyn Transform>>, Vec<Document>, HashSet<Word>) {
+        let mut documents = documents.to_vec();
+        let mut transforms = Vec::with_capacity(self.stages.len());
+        for stage in self.stages.iter() {
+            let transform = stage.fit(&documents, &vocab);
+            documents = documents.iter().map(|d| transform.transform(d)).collect();
+            vocab = transform.transform_vocab(vocab);
+            transforms.push(transform);
+        }
+        (transforms, documents, vocab)
+    }
+}
+
+pub struct FittedPipeline {
+    transforms: Vec<Box<dyn Transform>>,
+    model: NaiveBayes,
+}
+
+impl FittedPipeline {
+    pub fn model(&self) -> &NaiveBayes {
+        &self.model
+    }
+
+    /// The model alone, for saving. Documents must then be prepared by the
+    /// stages of the pipeline, if any, before the model sees them.
+    pub fn into_model(self) -> NaiveBayes {
+        self.model
+    }
+
+    pub fn transform(&self, document: &Document) -> Document {
+        self.transforms
+            .iter()
+            .fold(document.clone(), |document, t| t.transform(&document))
+    }
+
+    #[deprecated(note = "use `predict`")]
+    #[allow(deprecated)]
+    pub fn guess(&self, document: &Document) -> Vec<Class> {
+        self.model.guess(&self.transform(document))
+    }
+
+    pub fn predict(&self, document: &Document) -> Option<Class> {
+        self.model.predict(&self.transform(document))
+    }
+}
+
+impl Classifier for FittedPipeline {
+    fn classes(&self) -> &[Class] {
+        self.model.classes()
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        Classifier::scores(&self.model, &self.transform(document))
+    }
+}
+
+/// Why a cross-validation could not be run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CrossValidationError {
+    /// No folds, or more folds than documents, which would leave some of
+    /// them empty.
+    InvalidFolds { folds: usize, documents: usize },
+}
+
+impl fmt::Display for CrossValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossValidationError::InvalidFolds { folds, documents } => write!(
+                f,
+                "cannot split {} documents into {} folds",
+                documents, folds
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CrossValidationError {}
+
+/// Accuracy of each of `folds` folds, refitting every stage of the pipeline
+/// on the training part of the fold only. Document `i` is held out in fold
+/// `i % folds`. Folds are trained in parallel with the `parallel` feature.
+/// Fails unless there are between 1 and as many folds as documents.
+pub fn cross_validate(
+    pipeline: &Pipeline,
+    documents: &[Document],
+    classes: &HashSet<Class>,
+    vocab: &HashSet<Word>,
+    folds: usize,
+) -> Result<Vec<f64>, CrossValidationError> {
+    check_folds(folds, documents)?;
+    let assignment: Vec<usize> = (0..documents.len()).map(|i| i % folds).collect();
+    Ok(evaluate_folds(
+        pipeline,
+        documents,
+        classes,
+        vocab,
+        &[assignment],
+        folds,
+    ))
+}
+
+fn check_folds(folds: usize, documents: &[Document]) -> Result<(), CrossValidationError> {
+    if folds == 0 || folds > documents.len() {
+        return Err(CrossValidationError::InvalidFolds {
+            folds,
+            documents: documents.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Fold accuracies of a repeated cross-validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossValidation {
+    /// Accuracy of every fold, repeat by repeat.
+    pub fold_accuracy: Vec<f64>,
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+/// Cross-validates `repeats` times, each time over a different shuffle of
+/// `documents` drawn from `seed`. Every fold of every repeat is an
+/// independent job, so with the `parallel` feature they all run at once.
+/// Fails unless there are between 1 and as many folds as documents.
+pub fn repeated_cross_validate(
+    pipeline: &Pipeline,
+    documents: &[Document],
+    classes: &HashSet<Class>,
+    vocab: &HashSet<Word>,
+    folds: usize,
+    repeats: usize,
+    seed: u64,
+) -> Result<CrossValidation, CrossValidationError> {
+    let mut rng = SplitMix64::new(seed);
+    repeated_cross_validate_with(
+        pipeline, documents, classes, vocab, folds, repeats, &mut rng,
+    )
+}
+
+/// Like [`repeated_cross_validate`], shuffling with `rng` instead of `seed`.
+pub fn repeated_cross_validate_with(
+    pipeline: &Pipeline,
+    documents: &[Document],
+    classes: &HashSet<Class>,
+    vocab: &HashSet<Word>,
+    folds: usize,
+    repeats: usize,
+    rng: &mut impl RandomSource,
+) -> Result<CrossValidation, CrossValidationError> {
+    check_folds(folds, documents)?;
+    let assignments: Vec<Vec<usize>> = (0..repeats)
+        .map(|_| {
+            let mut order: Vec<usize> = (0..documents.len()).collect();
+            rng.shuffle(&mut order);
+            let mut assignment = vec![0; documents.len()];
+            for (position, document) in order.into_iter().enumerate() {
+                assignment[document] = position % folds;
+            }
+            assignment
+        })
+        .collect();
+
+    let fold_accuracy = evaluate_folds(pipeline, documents, classes, vocab, &assignments, folds);
+    let n = fold_accuracy.len().max(1) as f64;
+    let mean = fold_accuracy.iter().sum::<f64>() / n;
+    let variance = fold_accuracy
+        .iter()
+        .map(|a| (a - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    Ok(CrossValidation {
+        fold_accuracy,
+        mean,
+        std_dev: variance.sqrt(),
+    })
+}
+
+/// Shuffles `documents` with `rng` and splits off the last `test_fraction`
+/// of them, returning `(train, test)`.
+pub fn train_test_split(
+    documents: &[Document],
+    test_fraction: f64,
+    rng: &mut impl RandomSource,
+) -> (Vec<Document>, Vec<Document>) {
+    let mut documents = documents.to_vec();
+    rng.shuffle(&mut documents);
+    let test = (documents.len() as f64 * test_fraction.clamp(0.0, 1.0)).round() as usize;
+    let train = documents.len() - test;
+    let test = documents.split_off(train);
+    (documents, test)
+}
+
+/// As many documents as `documents` holds, drawn with replacement.
+pub fn bootstrap(documents: &[Document], rng: &mut impl RandomSource) -> Vec<Document> {
+    (0..documents.len())
+        .map(|_| documents[rng.below(documents.len())].clone())
+        .collect()
+}
+
+/// Accuracy of every `(assignment, fold)` job, in that order. `assignments`
+/// map each document to the fold it is held out in.
+fn evaluate_folds(
+    pipeline: &Pipeline,
+    documents: &[Document],
+    classes: &HashSet<Class>,
+    vocab: &HashSet<Word>,
+    assignments: &[Vec<usize>],
+    folds: usize,
+) -> Vec<f64> {
+    let jobs: Vec<(&[usize], usize)> = assignments
+        .iter()
+        .flat_map(|assignment| (0..folds).map(move |fold| (assignment.as_slice(), fold)))
+        .collect();
+    let evaluate = |&(assignment, fold): &(&[usize], usize)| {
+        let (test, train): (Vec<_>, Vec<_>) = documents
+            .iter()
+            .zip(assignment)
+            .partition(|(_, f)| **f == fold);
+        let train: Vec<Document> = train.into_iter().map(|(d, _)| d.clone()).collect();
+        let fitted = pipeline.fit(&train, classes.clone(), vocab.clone());
+
+        let correct = test
+            .iter()
+            .filter(|(d, _)| fitted.predict(d).as_ref() == Some(&d.class))
+            .count();
+        correct as f64 / test.len().max(1) as f64
+    };
+
+    #[cfg(feature = "parallel")]
+    return jobs.par_iter().map(evaluate).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    jobs.iter().map(evaluate).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingStage(&'static AtomicUsize);
+
+    impl Stage for CountingStage {
+        fn fit(&self, documents: &[Document], _: &HashSet<Word>) -> Box<dyn Transform> {
+            self.0.fetch_add(documents.len(), Ordering::SeqCst);
+            Box::new(VocabularyFilter {
+                keep: documents
+                    .iter()
+                    .flat_map(|d| d.words())
+                    .map(|w| w.to_owned())
+                    .collect(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_cross_validate_refits_stages_per_fold() {
+        static SEEN: AtomicUsize = AtomicUsize::new(0);
+        let documents: Vec<Document> = ["a b", "a c", "d e", "d f", "a g", "d h"]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| Document {
+                class: if i % 2 == 0 { "x" } else { "y" }.into(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .map(|w| w.to_owned())
+            .collect();
+
+        let pipeline = Pipeline::new().stage(CountingStage(&SEEN));
+        let accuracy = cross_validate(&pipeline, &documents, &classes, &vocab, 3).unwrap();
+
+        assert_eq!(accuracy.len(), 3);
+        // every fold fits the stage on the 4 training documents only
+        assert_eq!(SEEN.load(Ordering::SeqCst), 3 * 4);
+        assert_eq!(
+            cross_validate(&pipeline, &documents, &classes, &vocab, 0),
+            Err(CrossValidationError::InvalidFolds {
+                folds: 0,
+                documents: 6
+            })
+        );
+        assert!(cross_validate(&pipeline, &documents, &classes, &vocab, 7).is_err());
+        assert_eq!(SEEN.load(Ordering::SeqCst), 3 * 4);
+    }
+
+    #[test]
+    fn test_repeated_cross_validate_is_reproducible() {
+        let documents: Vec<Document> = (0..12)
+            .map(|i| Document {
+                class: if i % 2 == 0 { "x" } else { "y" }.into(),
+                text: format!("{} w{}", if i % 2 == 0 { "a" } else { "d" }, i),
+            })
+            .collect();
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .map(|w| w.to_owned())
+            .collect();
+        let pipeline = Pipeline::new();
+
+        let run =
+            |folds| repeated_cross_validate(&pipeline, &documents, &classes, &vocab, folds, 4, 7);
+        let first = run(3).unwrap();
+        assert_eq!(first.fold_accuracy.len(), 3 * 4);
+        assert_eq!(Ok(&first), run(3).as_ref());
+        let mean = first.fold_accuracy.iter().sum::<f64>() / 12.0;
+        assert!((first.mean - mean).abs() < 1e-12);
+
+        assert!(run(12).is_ok());
+        for folds in [0, 13] {
+            assert_eq!(
+                run(folds),
+                Err(CrossValidationError::InvalidFolds {
+                    folds,
+                    documents: 12
+                })
+            );
+        }
+    }
+
+    /// Scripted source drawing the same value every time.
+    struct Constant(u64);
+
+    impl RandomSource for Constant {
+        fn next_u64(&mut self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_sampling_uses_the_given_random_source() {
+        let documents: Vec<Document> = (0..5)
+            .map(|i| Document {
+                class: "x".into(),
+                text: format!("w{}", i),
+            })
+            .collect();
+        let texts = |documents: &[Document]| -> Vec<String> {
+            documents.iter().map(|d| d.text.clone()).collect()
+        };
+
+        // always drawing 0 leaves no document in place and resamples the first
+        let (train, test) = train_test_split(&documents, 0.4, &mut Constant(0));
+        assert_eq!(texts(&train), ["w1", "w2", "w3"]);
+        assert_eq!(texts(&test), ["w4", "w0"]);
+        assert_eq!(texts(&bootstrap(&documents, &mut Constant(0))), ["w0"; 5]);
+
+        let mut rng = SplitMix64::new(3);
+        let (train, test) = train_test_split(&documents, 0.4, &mut rng);
+        assert_eq!((train.len(), test.len()), (3, 2));
+        let (again, _) = train_test_split(&documents, 0.4, &mut SplitMix64::new(3));
+        assert_eq!(texts(&train), texts(&again));
+    }
+}
@@ -0,0 +1,6 @@
+//! Ready-made configurations for common classification tasks.
+
+#[cfg(feature = "langid")]
+pub mod langid;
+pub mod sentiment;
+pub mod spam;
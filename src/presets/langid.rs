@@ -0,0 +1,104 @@
+//! Language identification from character trigrams. The model is trained on
+//! the sentences in `langid_corpus.txt` and embedded as a [`StaticModel`].
+
+use crate::{Document, StaticModel, WhitespaceTokenizer};
+
+include!("langid_model.rs");
+
+/// Languages [`detect_language`] can tell apart, as ISO 639-1 codes.
+pub fn languages() -> &'static [&'static str] {
+    MODEL.classes
+}
+
+/// The most likely language of `text`, or `None` when it shares no
+/// trigram with the training sentences.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let trigrams = trigrams(text);
+    if trigrams
+        .iter()
+        .all(|trigram| MODEL.token(trigram).is_none())
+    {
+        return None;
+    }
+    let document = Document {
+        class: String::new(),
+        text: trigrams.join(" "),
+    };
+    MODEL.predict(&document, &WhitespaceTokenizer)
+}
+
+/// Lowercased character trigrams of every word, padded with `_` at the
+/// word boundaries.
+fn trigrams(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            let chars: Vec<char> = std::iter::once('_')
+                .chain(word.chars().flat_map(char::to_lowercase))
+                .chain(std::iter::once('_'))
+                .collect();
+            chars
+                .windows(3)
+                .map(|window| window.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NaiveBayes, VocabularySelection};
+
+    fn train() -> NaiveBayes {
+        let documents: Vec<Document> = include_str!("langid_corpus.txt")
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(language, sentence)| Document {
+                class: language.to_string(),
+                text: trigrams(sentence).join(" "),
+            })
+            .collect();
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let selection = VocabularySelection {
+            min_df: 2,
+            max_size: Some(600),
+            ..Default::default()
+        };
+        NaiveBayes::fit_two_pass(
+            || documents.clone(),
+            classes,
+            &selection,
+            WhitespaceTokenizer,
+        )
+    }
+
+    /// Run with `UPDATE_LANGID=1` to regenerate the model after changing
+    /// the corpus or the features.
+    #[test]
+    fn test_bundled_model_is_up_to_date() {
+        let source = train().to_rust_source("MODEL");
+        if std::env::var_os("UPDATE_LANGID").is_some() {
+            let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/presets/langid_model.rs");
+            std::fs::write(path, &source).unwrap();
+        }
+        assert_eq!(source, include_str!("langid_model.rs"));
+    }
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(languages(), ["de", "en", "es", "fr", "it"]);
+        assert_eq!(detect_language("Wo ist das nächste Hotel?"), Some("de"));
+        assert_eq!(detect_language("Where is the nearest hotel?"), Some("en"));
+        assert_eq!(
+            detect_language("¿Dónde está el hotel más cercano?"),
+            Some("es")
+        );
+        assert_eq!(
+            detect_language("Où est l'hôtel le plus proche?"),
+            Some("fr")
+        );
+        assert_eq!(detect_language("Dov'è l'albergo più vicino?"), Some("it"));
+        assert_eq!(detect_language("1234 !!"), None);
+    }
+}
@@ -0,0 +1,2236 @@
+// generated by NaiveBayes::to_rust_source, do not edit
+pub static MODEL: StaticModel = StaticModel {
+    classes: &[
+        "de",
+        "en",
+        "es",
+        "fr",
+        "it",
+    ],
+    words: &[
+        "_a_",
+        "_ai",
+        "_an",
+        "_ap",
+        "_ar",
+        "_au",
+        "_ba",
+        "_be",
+        "_br",
+        "_bu",
+        "_ca",
+        "_ce",
+        "_ch",
+        "_ci",
+        "_co",
+        "_da",
+        "_de",
+        "_di",
+        "_do",
+        "_ei",
+        "_el",
+        "_en",
+        "_es",
+        "_fa",
+        "_fi",
+        "_fo",
+        "_ga",
+        "_ge",
+        "_gi",
+        "_go",
+        "_ha",
+        "_he",
+        "_ho",
+        "_hu",
+        "_i_",
+        "_il",
+        "_in",
+        "_is",
+        "_ja",
+        "_je",
+        "_ju",
+        "_ka",
+        "_la",
+        "_le",
+        "_li",
+        "_lo",
+        "_ma",
+        "_me",
+        "_mi",
+        "_mu",
+        "_na",
+        "_ne",
+        "_ni",
+        "_no",
+        "_nu",
+        "_og",
+        "_pa",
+        "_pe",
+        "_pi",
+        "_pl",
+        "_po",
+        "_pr",
+        "_pu",
+        "_qu",
+        "_re",
+        "_sa",
+        "_sc",
+        "_se",
+        "_si",
+        "_so",
+        "_sp",
+        "_st",
+        "_te",
+        "_th",
+        "_ti",
+        "_to",
+        "_tr",
+        "_un",
+        "_vi",
+        "_vo",
+        "_we",
+        "_wi",
+        "_wo",
+        "_ye",
+        "_zu",
+        "abe",
+        "ace",
+        "ach",
+        "aff",
+        "afé",
+        "age",
+        "ahr",
+        "ain",
+        "alt",
+        "amo",
+        "ana",
+        "and",
+        "ann",
+        "ans",
+        "ant",
+        "apr",
+        "ar_",
+        "ard",
+        "are",
+        "arr",
+        "as_",
+        "at_",
+        "ava",
+        "ave",
+        "avo",
+        "ay_",
+        "bea",
+        "ber",
+        "bit",
+        "bro",
+        "caf",
+        "can",
+        "ce_",
+        "cen",
+        "ch_",
+        "cha",
+        "che",
+        "chi",
+        "chl",
+        "ci_",
+        "cor",
+        "cou",
+        "da_",
+        "dan",
+        "day",
+        "de_",
+        "dem",
+        "den",
+        "der",
+        "des",
+        "die",
+        "din",
+        "dir",
+        "do_",
+        "dop",
+        "dor",
+        "dre",
+        "eau",
+        "ede",
+        "ee_",
+        "eek",
+        "egg",
+        "ein",
+        "eit",
+        "el_",
+        "ele",
+        "ell",
+        "em_",
+        "eme",
+        "emp",
+        "en_",
+        "ena",
+        "end",
+        "eno",
+        "ent",
+        "er_",
+        "ere",
+        "ern",
+        "es_",
+        "esp",
+        "ess",
+        "est",
+        "ett",
+        "evo",
+        "fav",
+        "fee",
+        "ffe",
+        "fin",
+        "for",
+        "fé_",
+        "gar",
+        "gen",
+        "ggi",
+        "gi_",
+        "gov",
+        "hab",
+        "hac",
+        "has",
+        "hat",
+        "hav",
+        "he_",
+        "her",
+        "hie",
+        "hre",
+        "ht_",
+        "iam",
+        "ibr",
+        "ich",
+        "ie_",
+        "iel",
+        "iem",
+        "ier",
+        "il_",
+        "ill",
+        "ima",
+        "in_",
+        "ine",
+        "ing",
+        "ion",
+        "ir_",
+        "ire",
+        "irm",
+        "is_",
+        "ist",
+        "it_",
+        "ite",
+        "itt",
+        "ive",
+        "jar",
+        "jou",
+        "ken",
+        "kin",
+        "la_",
+        "le_",
+        "lee",
+        "leg",
+        "len",
+        "les",
+        "lib",
+        "liv",
+        "ll_",
+        "lle",
+        "lo_",
+        "los",
+        "lta",
+        "ma_",
+        "man",
+        "mar",
+        "me_",
+        "men",
+        "mi_",
+        "mir",
+        "mo_",
+        "mos",
+        "mpo",
+        "mps",
+        "na_",
+        "nac",
+        "nco",
+        "nd_",
+        "nde",
+        "ndr",
+        "ne_",
+        "nel",
+        "nen",
+        "ner",
+        "ng_",
+        "ni_",
+        "nic",
+        "nne",
+        "no_",
+        "noc",
+        "non",
+        "not",
+        "nou",
+        "ns_",
+        "nt_",
+        "och",
+        "oda",
+        "ois",
+        "oma",
+        "on_",
+        "ond",
+        "one",
+        "ons",
+        "or_",
+        "ora",
+        "ore",
+        "orm",
+        "os_",
+        "oss",
+        "ot_",
+        "our",
+        "ous",
+        "ouv",
+        "ova",
+        "ove",
+        "own",
+        "par",
+        "pas",
+        "per",
+        "pid",
+        "pla",
+        "po_",
+        "pon",
+        "por",
+        "pou",
+        "pre",
+        "pri",
+        "pro",
+        "ps_",
+        "pue",
+        "que",
+        "ra_",
+        "rai",
+        "rd_",
+        "rdi",
+        "re_",
+        "reg",
+        "ren",
+        "res",
+        "rin",
+        "ris",
+        "rmi",
+        "rne",
+        "rno",
+        "ro_",
+        "rov",
+        "rro",
+        "rte",
+        "run",
+        "sal",
+        "sch",
+        "se_",
+        "sem",
+        "sen",
+        "set",
+        "spo",
+        "sse",
+        "st_",
+        "sta",
+        "ste",
+        "sti",
+        "ta_",
+        "te_",
+        "tem",
+        "ten",
+        "ter",
+        "the",
+        "tie",
+        "tim",
+        "tio",
+        "to_",
+        "tod",
+        "tra",
+        "tre",
+        "tro",
+        "tte",
+        "uch",
+        "ue_",
+        "uen",
+        "ues",
+        "ui_",
+        "un_",
+        "und",
+        "une",
+        "unt",
+        "ur_",
+        "us_",
+        "ute",
+        "uve",
+        "ve_",
+        "vel",
+        "ver",
+        "viv",
+        "vor",
+        "we_",
+        "wee",
+        "wir",
+        "wn_",
+        "wor",
+        "yes",
+        "ños",
+        "ón_",
+    ],
+    log_prior: &[
+        -1.6094379124341003,
+        -1.6094379124341003,
+        -1.6094379124341003,
+        -1.6094379124341003,
+        -1.6094379124341003,
+    ],
+    likelihoods: &[
+        -6.495265555937008,
+        -5.076735095800779,
+        -5.355012710224582,
+        -5.86220971125072,
+        -5.07829394257007,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -5.86220971125072,
+        -4.855150391255861,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.076735095800779,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -4.885827643502908,
+        -5.076735095800779,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.07829394257007,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.771441123130016,
+        -4.703506086708953,
+        -6.46302945692067,
+        -4.507714849837378,
+        -4.76359742258261,
+        -5.771441123130016,
+        -5.1089711948171175,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -5.86220971125072,
+        -4.855150391255861,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -4.661865529664636,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -4.885827643502908,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.396653267268898,
+        -4.853591544486569,
+        -5.067330637772801,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.36441716825256,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -5.07829394257007,
+        -5.802118375377063,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -4.855150391255861,
+        -5.396653267268898,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.355012710224582,
+        -5.86220971125072,
+        -5.07829394257007,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -4.358132314474446,
+        -5.365976015021851,
+        -5.802118375377063,
+        -5.36441716825256,
+        -5.760477818332746,
+        -5.456744603142555,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -4.844187086458591,
+        -4.9459189793765646,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -4.609446742755352,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.07829394257007,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -5.169062530690774,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.456744603142555,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.355012710224582,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -4.885827643502908,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.456744603142555,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.07829394257007,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -3.898080099459133,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -4.383587915240834,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -5.067330637772801,
+        -4.9459189793765646,
+        -5.07829394257007,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.067330637772801,
+        -5.169062530690774,
+        -5.07829394257007,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.771441123130016,
+        -5.396653267268898,
+        -4.671269987692615,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.07829394257007,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.169062530690774,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.456744603142555,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.076735095800779,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -5.067330637772801,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.076735095800779,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -5.456744603142555,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -4.549355406881695,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -4.661865529664636,
+        -4.9459189793765646,
+        -5.771441123130016,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.36441716825256,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -4.549355406881695,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -4.661865529664636,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.365976015021851,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -5.86220971125072,
+        -5.365976015021851,
+        -3.499533282383017,
+        -5.076735095800779,
+        -4.661865529664636,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -4.192680462942962,
+        -4.383587915240834,
+        -5.355012710224582,
+        -4.9459189793765646,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -4.844187086458591,
+        -5.169062530690774,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -5.36441716825256,
+        -5.355012710224582,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.076735095800779,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -3.9781228071326695,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -4.853591544486569,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -5.07829394257007,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -5.1089711948171175,
+        -5.076735095800779,
+        -5.760477818332746,
+        -5.456744603142555,
+        -5.07829394257007,
+        -4.703506086708953,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -4.853591544486569,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -4.885827643502908,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -4.76359742258261,
+        -6.464588303689961,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -5.86220971125072,
+        -4.855150391255861,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.252771798816619,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.169062530690774,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.076735095800779,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -6.555356891810665,
+        -5.07829394257007,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -5.1089711948171175,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.07829394257007,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -4.885827643502908,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -4.855150391255861,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -4.885827643502908,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -4.853591544486569,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.07829394257007,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -6.555356891810665,
+        -4.672828834461907,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -4.475915350130829,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.169062530690774,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.169062530690774,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -4.3741834572128555,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.76359742258261,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.76359742258261,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.07829394257007,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.07829394257007,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.169062530690774,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.456744603142555,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -4.844187086458591,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.07829394257007,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -4.517119307865356,
+        -5.760477818332746,
+        -4.609446742755352,
+        -4.385146762010125,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.169062530690774,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -5.456744603142555,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -4.703506086708953,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -5.760477818332746,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.396653267268898,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -4.885827643502908,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.396653267268898,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.365976015021851,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -5.076735095800779,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -3.898080099459133,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -4.853591544486569,
+        -6.453624998892692,
+        -6.555356891810665,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -5.771441123130016,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.396653267268898,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.067330637772801,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.067330637772801,
+        -5.169062530690774,
+        -5.365976015021851,
+        -5.396653267268898,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.456744603142555,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.609446742755352,
+        -6.464588303689961,
+        -5.802118375377063,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -4.9459189793765646,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.076735095800779,
+        -5.760477818332746,
+        -5.86220971125072,
+        -5.07829394257007,
+        -6.495265555937008,
+        -6.46302945692067,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -5.076735095800779,
+        -6.453624998892692,
+        -5.86220971125072,
+        -5.771441123130016,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.771441123130016,
+        -5.802118375377063,
+        -6.46302945692067,
+        -5.760477818332746,
+        -6.555356891810665,
+        -5.365976015021851,
+        -6.495265555937008,
+        -5.076735095800779,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -5.86220971125072,
+        -6.464588303689961,
+        -5.1089711948171175,
+        -6.46302945692067,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.36441716825256,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -5.802118375377063,
+        -5.7698822763607245,
+        -6.453624998892692,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -5.7698822763607245,
+        -5.760477818332746,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+        -6.495265555937008,
+        -6.46302945692067,
+        -5.355012710224582,
+        -6.555356891810665,
+        -6.464588303689961,
+    ],
+    idf: None,
+};
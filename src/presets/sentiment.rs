@@ -0,0 +1,126 @@
+//! Binary sentiment analysis with the usual naive Bayes recipe: words after
+//! a negation are marked until the end of the clause, unigrams and bigrams
+//! are used as features, and counts are binarized per document.
+
+use std::collections::HashSet;
+
+use crate::{classifier::softmax, vocabulary, Classifier, Document, NaiveBayes};
+
+pub const POSITIVE: &str = "pos";
+pub const NEGATIVE: &str = "neg";
+
+const NEGATIONS: [&str; 7] = ["not", "no", "never", "nor", "cannot", "nothing", "without"];
+
+pub struct SentimentModel {
+    model: NaiveBayes,
+}
+
+impl SentimentModel {
+    /// Trains on documents labelled [`POSITIVE`] or [`NEGATIVE`].
+    pub fn train(documents: &[Document]) -> Self {
+        let documents: Vec<Document> = documents
+            .iter()
+            .map(|d| Document {
+                class: d.class.clone(),
+                text: features(&d.text).join(" "),
+            })
+            .collect();
+        let classes: HashSet<String> = [POSITIVE, NEGATIVE].iter().map(|c| c.to_string()).collect();
+        Self {
+            model: NaiveBayes::new(&documents, classes, vocabulary(&documents)),
+        }
+    }
+
+    /// From -1 for certainly negative to 1 for certainly positive.
+    pub fn polarity(&self, text: &str) -> f64 {
+        let document = Document {
+            class: String::new(),
+            text: features(text).join(" "),
+        };
+        let posterior = softmax(&Classifier::scores(&self.model, &document));
+        let probability = |class| match self.model.classes().iter().position(|c| c == class) {
+            Some(class) => posterior[class],
+            None => 0.0,
+        };
+        probability(POSITIVE) - probability(NEGATIVE)
+    }
+}
+
+/// Distinct negation-marked unigrams and bigrams of `text`, in order of
+/// first occurrence.
+fn features(text: &str) -> Vec<String> {
+    let mut unigrams = Vec::new();
+    let mut negated = false;
+    for token in text.split_whitespace() {
+        let token = token.to_lowercase();
+        let ends_clause = token.ends_with(['.', ',', '!', '?', ';', ':']);
+        let word = token.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+        if !word.is_empty() {
+            unigrams.push(if negated {
+                format!("not_{}", word)
+            } else {
+                word.to_string()
+            });
+            if NEGATIONS.contains(&word) || word.ends_with("n't") {
+                negated = true;
+            }
+        }
+        if ends_clause {
+            negated = false;
+        }
+    }
+
+    let bigrams: Vec<String> = unigrams
+        .windows(2)
+        .map(|pair| format!("{}_{}", pair[0], pair[1]))
+        .collect();
+    let mut seen = HashSet::new();
+    unigrams
+        .into_iter()
+        .chain(bigrams)
+        .filter(|feature| seen.insert(feature.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentiment_polarity() {
+        assert_eq!(
+            features("Not good, good good!"),
+            [
+                "not",
+                "not_good",
+                "good",
+                "not_not_good",
+                "not_good_good",
+                "good_good"
+            ]
+        );
+
+        let reviews = [
+            (POSITIVE, "a great movie, i loved it"),
+            (POSITIVE, "wonderful acting and a great story"),
+            (POSITIVE, "i loved the story. not boring at all"),
+            (NEGATIVE, "a boring movie, i hated it"),
+            (NEGATIVE, "terrible acting and a boring story"),
+            (NEGATIVE, "i did not like it. not great"),
+        ];
+        let documents: Vec<Document> = reviews
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let model = SentimentModel::train(&documents);
+
+        let positive = model.polarity("great story, loved it");
+        let negative = model.polarity("boring and terrible");
+        assert!(positive > 0.0 && positive <= 1.0);
+        assert!((-1.0..0.0).contains(&negative));
+        assert!(model.polarity("not great") < model.polarity("great"));
+    }
+}
@@ -0,0 +1,169 @@
+//! An opinionated spam filter: lowercased words with URLs and email
+//! addresses canonicalized, unigrams plus bigrams, a Bernoulli model and a
+//! spam-probability threshold calibrated on held-out messages.
+
+use std::collections::HashSet;
+
+use crate::{
+    bernoulli::BernoulliNaiveBayes,
+    canonical::{Canonicalize, Rule},
+    classifier::softmax,
+    vocabulary, Document,
+};
+
+pub const SPAM: &str = "spam";
+pub const HAM: &str = "ham";
+
+/// Every this many training messages one is held out for calibration.
+const CALIBRATION_EVERY: usize = 5;
+
+pub struct SpamFilter {
+    model: BernoulliNaiveBayes,
+    /// Messages scoring a spam probability above this are spam.
+    threshold: f64,
+}
+
+impl SpamFilter {
+    /// Trains on messages labelled [`SPAM`] or [`HAM`], choosing the lowest
+    /// threshold that keeps the false positive rate on held-out ham at or
+    /// below `max_false_positive_rate`. The final model is then refitted on
+    /// all messages.
+    pub fn train(documents: &[Document], max_false_positive_rate: f64) -> Self {
+        let documents: Vec<Document> = documents
+            .iter()
+            .map(|d| Document {
+                class: d.class.clone(),
+                text: features(&d.text).join(" "),
+            })
+            .collect();
+        let (held_out, train): (Vec<_>, Vec<_>) = documents
+            .iter()
+            .enumerate()
+            .partition(|(i, _)| i % CALIBRATION_EVERY == CALIBRATION_EVERY - 1);
+        let train: Vec<Document> = train.into_iter().map(|(_, d)| d.clone()).collect();
+
+        let calibration = Self {
+            model: fit(&train),
+            threshold: 0.5,
+        };
+        let mut ham: Vec<f64> = held_out
+            .iter()
+            .filter(|(_, d)| d.class == HAM)
+            .map(|(_, d)| calibration.probability(d))
+            .collect();
+        ham.sort_unstable_by(|a, b| b.total_cmp(a));
+        let allowed = (max_false_positive_rate * ham.len() as f64).floor() as usize;
+        let threshold = match ham.len() {
+            0 => 0.5,
+            n if allowed >= n => 0.0,
+            _ => ham[allowed],
+        };
+
+        Self {
+            model: fit(&documents),
+            threshold,
+        }
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Posterior probability that `text` is spam.
+    pub fn spam_probability(&self, text: &str) -> f64 {
+        self.probability(&Document {
+            class: String::new(),
+            text: features(text).join(" "),
+        })
+    }
+
+    pub fn is_spam(&self, text: &str) -> bool {
+        self.spam_probability(text) > self.threshold
+    }
+
+    fn probability(&self, features: &Document) -> f64 {
+        let posterior = softmax(&self.model.scores(features));
+        match self.model.classes().iter().position(|c| c == SPAM) {
+            Some(spam) => posterior[spam],
+            None => 0.0,
+        }
+    }
+}
+
+fn fit(documents: &[Document]) -> BernoulliNaiveBayes {
+    let classes: HashSet<String> = [SPAM, HAM].iter().map(|c| c.to_string()).collect();
+    BernoulliNaiveBayes::new(documents, classes, vocabulary(documents))
+}
+
+/// Canonical unigrams of `text` followed by its bigrams, joined with `_`.
+fn features(text: &str) -> Vec<String> {
+    let canonicalize = Canonicalize {
+        rules: vec![Rule::url(), Rule::email()],
+    };
+    let unigrams: Vec<String> = text
+        .split_whitespace()
+        .filter_map(|token| {
+            let canonical = canonicalize.token(token);
+            if canonical != token {
+                return Some(canonical.to_string());
+            }
+            let token = token.to_lowercase();
+            let token = token.trim_matches(|c: char| !c.is_alphanumeric());
+            (!token.is_empty()).then(|| token.to_string())
+        })
+        .collect();
+    let bigrams: Vec<String> = unigrams
+        .windows(2)
+        .map(|pair| format!("{}_{}", pair[0], pair[1]))
+        .collect();
+    unigrams.into_iter().chain(bigrams).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spam_filter() {
+        assert_eq!(
+            features("Visit https://x.io NOW, mail Bob@Example.com!"),
+            [
+                "visit",
+                "<URL>",
+                "now",
+                "mail",
+                "<EMAIL>",
+                "visit_<URL>",
+                "<URL>_now",
+                "now_mail",
+                "mail_<EMAIL>"
+            ]
+        );
+
+        let messages = [
+            (SPAM, "win a free prize now click http://win.example"),
+            (SPAM, "free money click here now"),
+            (SPAM, "cheap pills free shipping click www.pills.example"),
+            (SPAM, "you win a free cruise claim now"),
+            (SPAM, "claim your free prize money"),
+            (HAM, "are we still meeting for lunch tomorrow"),
+            (HAM, "please review the attached report before the meeting"),
+            (HAM, "can you send me the notes from today"),
+            (HAM, "lunch tomorrow sounds good see you then"),
+            (HAM, "the report is attached let me know what you think"),
+        ];
+        let documents: Vec<Document> = messages
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let filter = SpamFilter::train(&documents, 0.0);
+
+        assert!(filter.is_spam("click now to claim a free prize"));
+        assert!(!filter.is_spam("see you at the meeting tomorrow"));
+        let p = filter.spam_probability("free prize");
+        assert!((0.0..=1.0).contains(&p));
+    }
+}
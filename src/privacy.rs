@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    counts::Counts,
+    rng::{RandomSource, SplitMix64},
+    Class, Document, NaiveBayes, WhitespaceTokenizer, Word,
+};
+
+/// Laplace mechanism applied to the counts of a model before estimation.
+///
+/// The budget `epsilon` is split evenly between the class document counts,
+/// the per-class word counts and the document frequencies. Every document
+/// contributes at most `max_words_per_document` vocabulary words, which
+/// bounds its influence on the word counts.
+#[derive(Clone, Debug)]
+pub struct DifferentialPrivacy {
+    pub epsilon: f64,
+    pub max_words_per_document: usize,
+    pub seed: u64,
+}
+
+impl Default for DifferentialPrivacy {
+    fn default() -> Self {
+        Self {
+            epsilon: 1.0,
+            max_words_per_document: 100,
+            seed: 0,
+        }
+    }
+}
+
+impl NaiveBayes {
+    /// Like [`NaiveBayes::new`], but every count the model keeps, including
+    /// the zero ones, is perturbed with Laplace noise, so the model can be
+    /// shared with `epsilon`-differential privacy for each training document.
+    /// Documents added later through [`NaiveBayes::partial_fit`] are not
+    /// covered.
+    pub fn new_private(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        privacy: &DifferentialPrivacy,
+    ) -> Self {
+        let mut rng = SplitMix64::new(privacy.seed);
+        Self::new_private_with(documents, classes, vocab, privacy, &mut rng)
+    }
+
+    /// Like [`NaiveBayes::new_private`], drawing the noise from `rng` instead
+    /// of `privacy.seed`.
+    pub fn new_private_with(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        privacy: &DifferentialPrivacy,
+        rng: &mut impl RandomSource,
+    ) -> Self {
+        let mut words: Vec<Word> = vocab.into_iter().collect();
+        words.sort_unstable();
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+
+        let clipped: Vec<Document> = documents
+            .iter()
+            .map(|d| Document {
+                class: d.class.clone(),
+                text: d
+                    .words()
+                    .into_iter()
+                    .filter(|word| vocab.contains_key(*word))
+                    .take(privacy.max_words_per_document)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            })
+            .collect();
+        let counts = Counts::new(&clipped, &classes, &vocab, &WhitespaceTokenizer);
+
+        let epsilon = privacy.epsilon / 3.0;
+        let sensitivity = privacy.max_words_per_document as f64;
+        let mut noisy = |count: f64, sensitivity: f64| {
+            let noisy = count + laplace(rng, sensitivity / epsilon);
+            noisy.round().max(0.0)
+        };
+
+        let documents: Vec<f64> = counts.documents.iter().map(|c| noisy(*c, 1.0)).collect();
+        let words = counts
+            .words
+            .iter()
+            .map(|class_words| {
+                (0..vocab.len())
+                    .filter_map(|token| {
+                        let count = class_words.get(&token).copied().unwrap_or(0.0);
+                        Some((token, noisy(count, sensitivity))).filter(|(_, c)| *c > 0.0)
+                    })
+                    .collect()
+            })
+            .collect();
+        let document_frequency = counts
+            .document_frequency
+            .iter()
+            .map(|df| noisy(*df as f64, sensitivity) as usize)
+            .collect();
+
+        let total_documents = (documents.iter().sum::<f64>() as usize).max(1);
+        let counts = Counts {
+            documents,
+            words,
+            document_frequency,
+        };
+        Self::from_counts(vocab, classes, counts, total_documents, &mut ())
+    }
+}
+
+/// A sample of the Laplace distribution centered at zero.
+fn laplace(rng: &mut impl RandomSource, scale: f64) -> f64 {
+    let u = rng.next_f64() - 0.5;
+    -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_private_counts_are_noisy_and_clipped() {
+        let documents: Vec<Document> = [
+            ("pos", "good good good good great movie"),
+            ("pos", "good plot"),
+            ("neg", "bad movie"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab: HashSet<Word> = ["bad", "good", "great", "movie", "plot"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+
+        let exact = DifferentialPrivacy {
+            epsilon: 1e12,
+            max_words_per_document: 3,
+            seed: 1,
+        };
+        let model = NaiveBayes::new_private(&documents, classes.clone(), vocab.clone(), &exact);
+        assert_eq!(model.counts.documents, vec![1.0, 2.0]);
+        // only the first three words of the long review are counted
+        let good = model.vocab["good"];
+        assert_eq!(model.counts.words[1][&good], 4.0);
+        assert!(!model.counts.words[1].contains_key(&model.vocab["great"]));
+
+        let private = DifferentialPrivacy {
+            epsilon: 0.5,
+            ..exact
+        };
+        let a = NaiveBayes::new_private(&documents, classes.clone(), vocab.clone(), &private);
+        let b = NaiveBayes::new_private(&documents, classes, vocab, &private);
+        assert_eq!(a.counts, b.counts);
+        assert_ne!(a.counts, model.counts);
+    }
+
+    #[test]
+    fn test_laplace_has_the_right_scale() {
+        let mut rng = SplitMix64::new(3);
+        let samples: Vec<f64> = (0..20000).map(|_| laplace(&mut rng, 2.0)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let mean_abs = samples.iter().map(|s| s.abs()).sum::<f64>() / samples.len() as f64;
+        assert!(mean.abs() < 0.1);
+        assert!((mean_abs - 2.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,123 @@
+/// A stage of training.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The vocabulary selection pass of [`crate::NaiveBayes::fit_two_pass`].
+    DocumentFrequencies,
+    CountingWords,
+    Estimating,
+}
+
+/// Receives training progress, so applications decide what to show.
+/// Implemented by closures taking `(Phase, f64)`.
+pub trait Progress {
+    /// `fraction` of `phase` is done, from 0 to 1. Every phase starts with 0
+    /// and ends with 1.
+    fn report(&mut self, phase: Phase, fraction: f64);
+}
+
+impl<F: FnMut(Phase, f64)> Progress for F {
+    fn report(&mut self, phase: Phase, fraction: f64) {
+        self(phase, fraction)
+    }
+}
+
+/// Ignores all progress.
+impl Progress for () {
+    fn report(&mut self, _: Phase, _: f64) {}
+}
+
+/// Reports a phase of `total` steps at most once per percent.
+pub(crate) struct Steps<'a, P: ?Sized> {
+    progress: &'a mut P,
+    phase: Phase,
+    total: usize,
+    done: usize,
+    percent: usize,
+}
+
+impl<'a, P: Progress + ?Sized> Steps<'a, P> {
+    pub(crate) fn new(progress: &'a mut P, phase: Phase, total: usize) -> Self {
+        progress.report(phase, 0.0);
+        Self {
+            progress,
+            phase,
+            total,
+            done: 0,
+            percent: 0,
+        }
+    }
+
+    pub(crate) fn step(&mut self, steps: usize) {
+        self.done = (self.done + steps).min(self.total);
+        let percent = self.done * 100 / self.total.max(1);
+        if percent > self.percent && self.done < self.total {
+            self.percent = percent;
+            self.progress
+                .report(self.phase, self.done as f64 / self.total as f64);
+        }
+    }
+
+    pub(crate) fn finish(self) {
+        self.progress.report(self.phase, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Document, NaiveBayes, VocabularySelection};
+
+    #[test]
+    fn test_training_reports_progress() {
+        let documents: Vec<Document> = (0..250)
+            .map(|i| Document {
+                class: format!("c{}", i % 3),
+                text: format!("w{} w{}", i % 7, i % 11),
+            })
+            .collect();
+        let classes: std::collections::HashSet<_> =
+            documents.iter().map(|d| d.class.clone()).collect();
+        let mut reports = Vec::new();
+        let mut record = |phase: Phase, fraction: f64| reports.push((phase, fraction));
+        NaiveBayes::new_with_progress(
+            &documents,
+            classes.clone(),
+            crate::vocabulary(&documents),
+            &mut record,
+        );
+
+        let phase = |reports: &[(Phase, f64)], phase| -> Vec<f64> {
+            reports
+                .iter()
+                .filter(|(p, _)| *p == phase)
+                .map(|(_, fraction)| *fraction)
+                .collect()
+        };
+        let counting = phase(&reports, Phase::CountingWords);
+        assert_eq!(counting.first(), Some(&0.0));
+        assert_eq!(counting.last(), Some(&1.0));
+        assert!(counting.len() > 10 && counting.len() <= 102);
+        assert!(counting.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            phase(&reports, Phase::Estimating),
+            [0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0]
+        );
+
+        let mut reports = Vec::new();
+        let mut record = |phase: Phase, fraction: f64| reports.push((phase, fraction));
+        NaiveBayes::fit_two_pass_with_progress(
+            || documents.clone(),
+            classes,
+            &VocabularySelection::default(),
+            crate::WhitespaceTokenizer,
+            &mut record,
+        );
+        assert_eq!(phase(&reports, Phase::DocumentFrequencies), [0.0, 1.0]);
+        assert_eq!(phase(&reports, Phase::CountingWords).len(), 101);
+        assert_eq!(
+            reports.first().map(|r| r.0),
+            Some(Phase::DocumentFrequencies)
+        );
+        assert_eq!(reports.last(), Some(&(Phase::Estimating, 1.0)));
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use crate::{
+    classifier::argmax, tf_idf::document_weights, Class, Classifier, Document, NaiveBayes,
+    SharedTokenizer, Tokenizer, Word,
+};
+
+/// Code standing for a log-likelihood of negative infinity.
+const NEG_INFINITY_CODE: i8 = i8::MIN;
+
+/// An inference-only copy of a model whose likelihoods are stored as `i8`
+/// codes with a per-class scale and offset, an eighth of the `f64` table.
+pub struct QuantizedModel {
+    classes: Vec<Class>,
+    tokens: HashMap<Word, u32>,
+    tokenizer: SharedTokenizer,
+    log_prior: Vec<f64>,
+    offset: Vec<f32>,
+    scale: Vec<f32>,
+    /// Row `token` holds the code of every class.
+    codes: Vec<i8>,
+    /// Idf of every token of a TF-IDF weighted model.
+    idf: Option<Vec<f64>>,
+}
+
+impl QuantizedModel {
+    pub fn new(model: &NaiveBayes) -> Self {
+        let classes = model.classes.len();
+        let vocab_size = model.vocab.len();
+
+        let (offset, scale): (Vec<f32>, Vec<f32>) = (0..classes)
+            .map(|class| {
+                let (min, max) = (0..vocab_size)
+                    .map(|token| model.likelihood.get(class, token))
+                    .filter(|value| value.is_finite())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                        (min.min(v), max.max(v))
+                    });
+                if min > max {
+                    return (0.0, 1.0);
+                }
+                let scale = ((max - min) / 254.0).max(f64::MIN_POSITIVE);
+                (((max + min) / 2.0) as f32, scale as f32)
+            })
+            .unzip();
+
+        let mut codes = vec![0; vocab_size * classes];
+        for token in 0..vocab_size {
+            for class in 0..classes {
+                let value = model.likelihood.get(class, token);
+                codes[token * classes + class] = if value == f64::NEG_INFINITY {
+                    NEG_INFINITY_CODE
+                } else {
+                    let code = (value - offset[class] as f64) / scale[class] as f64;
+                    code.round().clamp(-127.0, 127.0) as i8
+                };
+            }
+        }
+
+        Self {
+            classes: model.classes.clone(),
+            tokenizer: model.tokenizer.clone(),
+            tokens: model
+                .vocab
+                .iter()
+                .map(|(word, token)| (word.clone(), *token as u32))
+                .collect(),
+            log_prior: model.log_prior.clone(),
+            offset,
+            scale,
+            codes,
+            idf: model.idf.clone(),
+        }
+    }
+
+    /// Classes in the order used by [`QuantizedModel::scores`].
+    pub fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    /// Log prior plus summed dequantized likelihoods of every class, TF-IDF
+    /// weighted if the model is.
+    pub fn scores(&self, document: &Document) -> Vec<f64> {
+        let classes = self.classes.len();
+        let mut tokens = Vec::new();
+        self.tokenizer.for_each_token(&document.text, &mut |word| {
+            tokens.extend(self.tokens.get(word).map(|token| *token as usize))
+        });
+        let mut sums = vec![0.0; classes];
+        let mut scores = self.log_prior.clone();
+        let mut total_weight = 0.0;
+        for (token, weight) in document_weights(self.idf.as_deref(), tokens) {
+            total_weight += weight;
+            let row = &self.codes[token * classes..][..classes];
+            for (class, code) in row.iter().enumerate() {
+                if *code == NEG_INFINITY_CODE {
+                    scores[class] = f64::NEG_INFINITY;
+                } else {
+                    sums[class] += weight * *code as f64;
+                }
+            }
+        }
+        // sum the codes first and dequantize once per class
+        for class in 0..classes {
+            scores[class] +=
+                total_weight * self.offset[class] as f64 + sums[class] * self.scale[class] as f64;
+        }
+        scores
+    }
+
+    /// Bytes held by the likelihood codes and their per-class parameters.
+    pub fn size_in_bytes(&self) -> usize {
+        self.codes.len() + self.classes.len() * 2 * std::mem::size_of::<f32>()
+    }
+}
+
+impl Classifier for QuantizedModel {
+    fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        QuantizedModel::scores(self, document)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuantizationReport {
+    pub accuracy: f64,
+    pub quantized_accuracy: f64,
+    /// Fraction of documents where both models pick the same class.
+    pub agreement: f64,
+    pub max_score_error: f64,
+    pub mean_score_error: f64,
+}
+
+/// Compares `quantized` against the full `model` on labelled `documents`.
+pub fn quantization_report(
+    model: &NaiveBayes,
+    quantized: &QuantizedModel,
+    documents: &[Document],
+) -> QuantizationReport {
+    let scorer = model.token_scorer();
+    let (mut correct, mut quantized_correct, mut agree) = (0, 0, 0);
+    let (mut max_error, mut total_error, mut scores_compared) = (0.0f64, 0.0, 0);
+
+    for document in documents {
+        let exact = scorer.score_batch(&[scorer.token_ids(document)]).remove(0);
+        let approximate = quantized.scores(document);
+
+        let (best, quantized_best) = (argmax(&exact), argmax(&approximate));
+        correct += (best.map(|c| &model.classes[c]) == Some(&document.class)) as usize;
+        quantized_correct +=
+            (quantized_best.map(|c| &quantized.classes[c]) == Some(&document.class)) as usize;
+        agree += (best == quantized_best) as usize;
+
+        for (a, b) in exact.iter().zip(approximate.iter()) {
+            if a.is_finite() && b.is_finite() {
+                max_error = max_error.max((a - b).abs());
+                total_error += (a - b).abs();
+                scores_compared += 1;
+            }
+        }
+    }
+
+    let documents = documents.len().max(1) as f64;
+    QuantizationReport {
+        accuracy: correct as f64 / documents,
+        quantized_accuracy: quantized_correct as f64 / documents,
+        agreement: agree as f64 / documents,
+        max_score_error: max_error,
+        mean_score_error: total_error / scores_compared.max(1) as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantized_scores_close_to_model() {
+        let model = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cheap", "meeting", "now", "win"],
+            vec![-0.3, -1.4],
+            vec![
+                vec![(0, -6.0), (1, -1.2), (2, -2.5), (3, -7.0)],
+                vec![(0, -1.1), (1, -5.5), (2, -2.0), (3, -0.9)],
+            ],
+            vec![-8.0, -8.0],
+        );
+        let quantized = QuantizedModel::new(&model);
+        let documents: Vec<Document> = [("spam", "win cheap now"), ("ham", "meeting now")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+
+        let report = quantization_report(&model, &quantized, &documents);
+        assert_eq!(report.accuracy, 1.0);
+        assert_eq!(report.agreement, 1.0);
+        // half a quantization step of error per token
+        assert!(report.max_score_error <= 3.0 * (5.1 / 254.0));
+        assert_eq!(quantized.size_in_bytes(), 8 + 16);
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+use crate::{Class, Classifier, Document};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    UnknownModel(String),
+    UnknownVersion {
+        name: String,
+        version: u32,
+    },
+    DuplicateVersion {
+        name: String,
+        version: u32,
+    },
+    /// The active version of a model cannot be retired.
+    ActiveVersion {
+        name: String,
+        version: u32,
+    },
+    /// A model key that is neither `name` nor `name:v<version>`.
+    InvalidKey(String),
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownModel(name) => write!(f, "no model named {:?}", name),
+            RegistryError::UnknownVersion { name, version } => {
+                write!(f, "model {:?} has no version {}", name, version)
+            }
+            RegistryError::DuplicateVersion { name, version } => {
+                write!(f, "model {:?} already has version {}", name, version)
+            }
+            RegistryError::ActiveVersion { name, version } => {
+                write!(f, "version {} of model {:?} is active", version, name)
+            }
+            RegistryError::InvalidKey(key) => write!(f, "invalid model key {:?}", key),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+struct Versions {
+    models: BTreeMap<u32, Arc<dyn Classifier>>,
+    active: u32,
+}
+
+/// Named, versioned classifiers. Requests are routed by a key that is
+/// either a model name, resolving to its active version, or
+/// `name:v<version>` for a specific one.
+#[derive(Default)]
+pub struct Registry {
+    models: RwLock<HashMap<String, Versions>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a version of model `name`. The first version of a model becomes
+    /// its active one; later versions serve only once promoted.
+    pub fn register(
+        &self,
+        name: &str,
+        version: u32,
+        model: impl Classifier + 'static,
+    ) -> Result<(), RegistryError> {
+        let mut models = self.models.write().unwrap();
+        let versions = models.entry(name.to_owned()).or_insert_with(|| Versions {
+            models: BTreeMap::new(),
+            active: version,
+        });
+        if versions.models.contains_key(&version) {
+            return Err(RegistryError::DuplicateVersion {
+                name: name.to_owned(),
+                version,
+            });
+        }
+        versions.models.insert(version, Arc::new(model));
+        Ok(())
+    }
+
+    /// Atomically makes `version` the one serving requests for `name`.
+    /// Requests already holding the previous version finish with it.
+    pub fn promote(&self, name: &str, version: u32) -> Result<(), RegistryError> {
+        let mut models = self.models.write().unwrap();
+        let versions = models
+            .get_mut(name)
+            .ok_or_else(|| RegistryError::UnknownModel(name.to_owned()))?;
+        if !versions.models.contains_key(&version) {
+            return Err(RegistryError::UnknownVersion {
+                name: name.to_owned(),
+                version,
+            });
+        }
+        versions.active = version;
+        Ok(())
+    }
+
+    /// Removes a version that is not active.
+    pub fn retire(&self, name: &str, version: u32) -> Result<(), RegistryError> {
+        let mut models = self.models.write().unwrap();
+        let versions = models
+            .get_mut(name)
+            .ok_or_else(|| RegistryError::UnknownModel(name.to_owned()))?;
+        if version == versions.active {
+            return Err(RegistryError::ActiveVersion {
+                name: name.to_owned(),
+                version,
+            });
+        }
+        if versions.models.remove(&version).is_none() {
+            return Err(RegistryError::UnknownVersion {
+                name: name.to_owned(),
+                version,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn active_version(&self, name: &str) -> Option<u32> {
+        self.models.read().unwrap().get(name).map(|v| v.active)
+    }
+
+    /// Every registered version of `name`, in increasing order.
+    pub fn versions(&self, name: &str) -> Vec<u32> {
+        self.models
+            .read()
+            .unwrap()
+            .get(name)
+            .map(|v| v.models.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// The model `key` refers to.
+    pub fn get(&self, key: &str) -> Result<Arc<dyn Classifier>, RegistryError> {
+        let (name, version) = parse_key(key)?;
+        let models = self.models.read().unwrap();
+        let versions = models
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownModel(name.to_owned()))?;
+        let version = version.unwrap_or(versions.active);
+        versions
+            .models
+            .get(&version)
+            .cloned()
+            .ok_or_else(|| RegistryError::UnknownVersion {
+                name: name.to_owned(),
+                version,
+            })
+    }
+
+    pub fn predict(&self, key: &str, document: &Document) -> Result<Option<Class>, RegistryError> {
+        Ok(self.get(key)?.predict(document))
+    }
+}
+
+fn parse_key(key: &str) -> Result<(&str, Option<u32>), RegistryError> {
+    let invalid = || RegistryError::InvalidKey(key.to_owned());
+    match key.split_once(':') {
+        None if !key.is_empty() => Ok((key, None)),
+        Some((name, version)) if !name.is_empty() => {
+            let version = version.strip_prefix('v').ok_or_else(invalid)?;
+            Ok((name, Some(version.parse().map_err(|_| invalid())?)))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    fn model(class: &str) -> NaiveBayes {
+        NaiveBayes::from_parts(&[class], &[], vec![0.0], vec![vec![]], vec![-1.0])
+    }
+
+    #[test]
+    fn test_registry_routes_by_name_and_version() {
+        let registry = Registry::new();
+        let document = Document {
+            class: String::new(),
+            text: "anything".into(),
+        };
+        registry.register("spam", 3, model("old")).unwrap();
+        registry.register("spam", 4, model("new")).unwrap();
+        registry.register("topic", 12, model("sports")).unwrap();
+
+        assert_eq!(registry.predict("spam", &document), Ok(Some("old".into())));
+        assert_eq!(
+            registry.predict("spam:v4", &document),
+            Ok(Some("new".into()))
+        );
+        let serving = registry.get("spam").unwrap();
+        registry.promote("spam", 4).unwrap();
+        assert_eq!(registry.predict("spam", &document), Ok(Some("new".into())));
+        // a request that resolved before the promotion keeps its model
+        assert_eq!(serving.predict(&document), Some("old".into()));
+        assert_eq!(
+            registry.predict("topic", &document),
+            Ok(Some("sports".into()))
+        );
+
+        registry.retire("spam", 3).unwrap();
+        assert_eq!(registry.versions("spam"), [4]);
+        assert_eq!(
+            registry.register("spam", 4, model("again")),
+            Err(RegistryError::DuplicateVersion {
+                name: "spam".into(),
+                version: 4
+            })
+        );
+        assert_eq!(
+            registry.retire("spam", 4),
+            Err(RegistryError::ActiveVersion {
+                name: "spam".into(),
+                version: 4
+            })
+        );
+        assert_eq!(
+            registry.get("spam:3").err(),
+            Some(RegistryError::InvalidKey("spam:3".into()))
+        );
+        assert_eq!(
+            registry.get("ham").err(),
+            Some(RegistryError::UnknownModel("ham".into()))
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    classifier::{argmax, softmax},
+    Class, Classifier, Document,
+};
+
+/// Maps how well one model matches a document onto a scale shared with
+/// other models. The match is the posterior probability of the best class,
+/// which is uniform for documents the model knows nothing about, and it is
+/// standardized by its mean and spread on a validation set because models
+/// differ in how confident they usually are.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreNormalizer {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl Default for ScoreNormalizer {
+    fn default() -> Self {
+        Self {
+            mean: 0.0,
+            std_dev: 1.0,
+        }
+    }
+}
+
+impl ScoreNormalizer {
+    /// Fits the normalizer of `classifier` to `validation`.
+    pub fn fit(classifier: &dyn Classifier, validation: &[Document]) -> Self {
+        let scores: Vec<f64> = validation
+            .iter()
+            .map(|document| confidence(classifier, document))
+            .filter(|score| score.is_finite())
+            .collect();
+        if scores.is_empty() {
+            return Self::default();
+        }
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / n;
+        let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            mean,
+            std_dev: if variance > 0.0 { variance.sqrt() } else { 1.0 },
+        }
+    }
+
+    /// How many standard deviations better than usual `classifier` matches
+    /// `document`.
+    pub fn normalize(&self, classifier: &dyn Classifier, document: &Document) -> f64 {
+        (confidence(classifier, document) - self.mean) / self.std_dev
+    }
+}
+
+/// Posterior probability of the best class of `document`.
+fn confidence(classifier: &dyn Classifier, document: &Document) -> f64 {
+    softmax(&classifier.scores(document))
+        .into_iter()
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Picks, for every document, the model that matches it best among several
+/// trained with different vocabularies or configurations, comparing their
+/// scores after normalization.
+#[derive(Default)]
+pub struct Router {
+    models: Vec<(String, Box<dyn Classifier>, ScoreNormalizer)>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a model, uncalibrated until [`Router::calibrate`] is called.
+    pub fn model(mut self, name: &str, classifier: impl Classifier + 'static) -> Self {
+        self.models.push((
+            name.to_owned(),
+            Box::new(classifier),
+            ScoreNormalizer::default(),
+        ));
+        self
+    }
+
+    /// Fits the normalizer of every model to the shared `validation` set.
+    pub fn calibrate(&mut self, validation: &[Document]) {
+        for (_, classifier, normalizer) in &mut self.models {
+            *normalizer = ScoreNormalizer::fit(classifier.as_ref(), validation);
+        }
+    }
+
+    pub fn normalizer(&self, name: &str) -> Option<ScoreNormalizer> {
+        self.models
+            .iter()
+            .find(|(other, _, _)| other == name)
+            .map(|(_, _, normalizer)| *normalizer)
+    }
+
+    /// Normalized score of every model for `document`, in the order added.
+    pub fn scores(&self, document: &Document) -> Vec<(&str, f64)> {
+        self.models
+            .iter()
+            .map(|(name, classifier, normalizer)| {
+                (
+                    name.as_str(),
+                    normalizer.normalize(classifier.as_ref(), document),
+                )
+            })
+            .collect()
+    }
+
+    /// The best matching model and its normalized score, the first added on
+    /// ties.
+    pub fn route(&self, document: &Document) -> Option<(&str, f64)> {
+        let scores = self.scores(document);
+        let normalized: Vec<f64> = scores.iter().map(|(_, score)| *score).collect();
+        Some(scores[argmax(&normalized)?])
+    }
+
+    /// The prediction of the best matching model, with its name.
+    pub fn predict(&self, document: &Document) -> Option<(&str, Class)> {
+        let (name, _) = self.route(document)?;
+        let (_, classifier, _) = self.models.iter().find(|(other, _, _)| other == name)?;
+        Some((name, classifier.predict(document)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    #[test]
+    fn test_router_compares_normalized_scores() {
+        let sports = NaiveBayes::from_parts(
+            &["football", "tennis"],
+            &["ball", "goal", "racket"],
+            vec![-0.7, -0.7],
+            vec![
+                vec![(0, -1.0), (1, -0.5), (2, -4.0)],
+                vec![(0, -1.0), (1, -4.0), (2, -0.5)],
+            ],
+            vec![-2.0, -2.0],
+        );
+        // nearly every cooking document is about baking, so the cooking
+        // model is confident even about documents it knows nothing about
+        let cooking = NaiveBayes::from_parts(
+            &["baking", "grilling"],
+            &["flour", "oven", "steak"],
+            vec![-0.01, -5.0],
+            vec![
+                vec![(0, -3.0), (1, -3.5), (2, -9.0)],
+                vec![(0, -9.0), (1, -3.5), (2, -3.0)],
+            ],
+            vec![-12.0, -12.0],
+        );
+        let document = |text: &str| Document {
+            class: String::new(),
+            text: text.into(),
+        };
+        let mut router = Router::new()
+            .model("sports", sports)
+            .model("cooking", cooking);
+        assert_eq!(router.route(&document("racket ball")).unwrap().0, "cooking");
+
+        let validation: Vec<Document> = ["goal ball", "racket", "goal", "flour oven", "steak"]
+            .iter()
+            .map(|text| document(text))
+            .collect();
+        router.calibrate(&validation);
+        assert_ne!(
+            router.normalizer("sports"),
+            Some(ScoreNormalizer::default())
+        );
+        assert_eq!(
+            router.predict(&document("flour oven")),
+            Some(("cooking", "baking".into()))
+        );
+        assert_eq!(
+            router.predict(&document("racket ball")),
+            Some(("sports", "tennis".into()))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use crate::{Classifier, Document};
+
+/// Scores documents with `classifier` and writes one CSV row per document,
+/// for analysis in SQL or pandas: its id, its true label (empty when
+/// unknown), the score of every class in a `score_<class>` column, the
+/// predicted class and the margin between the two best scores. Returns the
+/// number of rows written.
+pub fn write_scores_csv<'a, I: Display>(
+    classifier: &impl Classifier,
+    documents: impl IntoIterator<Item = (I, &'a Document)>,
+    mut writer: impl Write,
+) -> io::Result<usize> {
+    let classes = classifier.classes();
+    let mut header = vec!["id".to_owned(), "label".to_owned()];
+    header.extend(classes.iter().map(|class| format!("score_{}", class)));
+    header.extend(["winner".to_owned(), "margin".to_owned()]);
+    write_row(&mut writer, &header)?;
+
+    let mut rows = 0;
+    for (id, document) in documents {
+        let scores = classifier.scores(document);
+        let mut ranked: Vec<usize> = (0..scores.len()).collect();
+        // stable, so ties go to the class that sorts first
+        ranked.sort_by(|a, b| scores[*b].total_cmp(&scores[*a]));
+        let winner = ranked.first().map_or("", |class| classes[*class].as_str());
+        let margin = match ranked[..] {
+            [first, second, ..] => scores[first] - scores[second],
+            _ => f64::NAN,
+        };
+
+        let mut row = vec![id.to_string(), document.class.clone()];
+        row.extend(scores.iter().map(f64::to_string));
+        row.extend([winner.to_owned(), margin.to_string()]);
+        write_row(&mut writer, &row)?;
+        rows += 1;
+    }
+    writer.flush()?;
+    Ok(rows)
+}
+
+/// Writes `fields` as a CSV line, quoting those that need it.
+fn write_row(writer: &mut impl Write, fields: &[String]) -> io::Result<()> {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",")?;
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            write!(writer, "\"{}\"", field.replace('"', "\"\""))?;
+        } else {
+            writer.write_all(field.as_bytes())?;
+        }
+    }
+    writer.write_all(b"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NaiveBayes;
+
+    #[test]
+    fn test_scores_csv() {
+        let model = NaiveBayes::from_parts(
+            &["ham", "spam"],
+            &["cash", "lunch"],
+            vec![-0.5, -1.0],
+            vec![vec![(0, -3.0), (1, -1.0)], vec![(0, -0.5), (1, -4.0)]],
+            vec![-9.0, -9.0],
+        );
+        let documents = [
+            Document {
+                class: "spam".into(),
+                text: "cash".into(),
+            },
+            Document {
+                class: String::new(),
+                text: "lunch, \"now\"".into(),
+            },
+        ];
+        let mut csv = Vec::new();
+        let rows = write_scores_csv(
+            &model,
+            [("a", &documents[0]), ("b,c", &documents[1])],
+            &mut csv,
+        )
+        .unwrap();
+        assert_eq!(rows, 2);
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "id,label,score_ham,score_spam,winner,margin\n\
+             a,spam,-3.5,-1.5,spam,2\n\
+             \"b,c\",,-0.5,-1,ham,0.5\n"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    pipeline::{Stage, Transform, VocabularyFilter},
+    Document, Word,
+};
+
+/// Pipeline stage running [`remove_redundant_features`] on each training set.
+pub struct RedundantFeatures {
+    pub min_similarity: f64,
+}
+
+impl Stage for RedundantFeatures {
+    fn fit(&self, documents: &[Document], vocab: &HashSet<Word>) -> Box<dyn Transform> {
+        Box::new(VocabularyFilter {
+            keep: remove_redundant_features(documents, vocab, self.min_similarity),
+        })
+    }
+}
+
+/// Drops tokens that co-occur with an already kept token in nearly the same
+/// set of documents (Jaccard similarity of their document sets at least
+/// `min_similarity`), keeping the most frequent token of each such group.
+pub fn remove_redundant_features(
+    documents: &[Document],
+    vocab: &HashSet<Word>,
+    min_similarity: f64,
+) -> HashSet<Word> {
+    let min_similarity = min_similarity.clamp(f64::EPSILON, 1.0);
+
+    let mut postings: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (id, document) in documents.iter().enumerate() {
+        let words: HashSet<&str> = document.words().into_iter().collect();
+        for word in words {
+            if let Some(word) = vocab.get(word) {
+                postings.entry(word.as_str()).or_default().push(id);
+            }
+        }
+    }
+
+    let mut tokens: Vec<(&str, Vec<usize>)> = postings.into_iter().collect();
+    tokens.sort_unstable_by(|(a, a_docs), (b, b_docs)| {
+        b_docs.len().cmp(&a_docs.len()).then_with(|| a.cmp(b))
+    });
+
+    // prefix filtering: two sets with jaccard >= t must share one of the
+    // first `len - ceil(t * len) + 1` elements of each other
+    let prefix_len = |len: usize| len - (min_similarity * len as f64).ceil() as usize + 1;
+
+    let mut prefix_index: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut redundant = HashSet::new();
+    for (position, (word, docs)) in tokens.iter().enumerate() {
+        let mut candidates: Vec<usize> = docs[..prefix_len(docs.len())]
+            .iter()
+            .filter_map(|doc| prefix_index.get(doc))
+            .flatten()
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let is_redundant = candidates.into_iter().any(|kept| {
+            let kept_docs = &tokens[kept].1;
+            kept_docs.len() as f64 * min_similarity <= docs.len() as f64
+                && jaccard(docs, kept_docs) >= min_similarity
+        });
+
+        if is_redundant {
+            redundant.insert(*word);
+        } else {
+            for doc in &docs[..prefix_len(docs.len())] {
+                prefix_index.entry(*doc).or_default().push(position);
+            }
+        }
+    }
+
+    vocab
+        .iter()
+        .filter(|word| !redundant.contains(word.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Jaccard similarity of two sorted, duplicate-free lists.
+pub(crate) fn jaccard<T: Ord>(a: &[T], b: &[T]) -> f64 {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    shared as f64 / (a.len() + b.len() - shared) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_redundant_features() {
+        let documents: Vec<Document> = [
+            "visit http example com now",
+            "http example com has deals",
+            "see http example com today",
+            "no links here now",
+        ]
+        .iter()
+        .map(|text| Document {
+            class: "spam".into(),
+            text: text.to_string(),
+        })
+        .collect();
+        let vocab: HashSet<Word> = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .map(|w| w.to_owned())
+            .collect();
+
+        let selected = remove_redundant_features(&documents, &vocab, 1.0);
+        let url_shards = ["http", "example", "com"];
+        assert_eq!(
+            url_shards.iter().filter(|w| selected.contains(**w)).count(),
+            1
+        );
+        assert!(selected.contains("now"));
+        assert_eq!(selected.len(), 6);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{counts::Counts, Class, NaiveBayes, WhitespaceTokenizer, Word};
+
+/// A training document labelled with a probability distribution over
+/// classes, such as the output of a weak supervision pipeline.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SoftDocument {
+    /// Classes and their probabilities, which should sum to one.
+    pub labels: Vec<(Class, f64)>,
+    pub text: String,
+}
+
+impl NaiveBayes {
+    /// Trains on soft-labelled documents: every document adds its words and
+    /// itself to each of its classes, weighted by the probability of the
+    /// class. One-hot labels train exactly like [`NaiveBayes::new`].
+    pub fn new_soft(
+        documents: &[SoftDocument],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+    ) -> Self {
+        let mut words: Vec<Word> = vocab.into_iter().collect();
+        words.sort_unstable();
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+
+        let counts = Counts::new_soft(documents, &classes, &vocab, &WhitespaceTokenizer);
+        Self::from_counts(vocab, classes, counts, documents.len(), &mut ())
+    }
+
+    /// [`NaiveBayes::partial_fit`] for soft-labelled documents.
+    pub fn partial_fit_soft(&mut self, documents: &[SoftDocument]) {
+        let counts = Counts::new_soft(documents, &self.classes, &self.vocab, &self.tokenizer);
+        self.counts = std::mem::take(&mut self.counts).merge(counts);
+        self.total_documents += documents.len();
+        self.estimate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Document;
+
+    #[test]
+    fn test_soft_labels_count_fractionally() {
+        let soft = |labels: &[(&str, f64)], text: &str| SoftDocument {
+            labels: labels.iter().map(|(c, w)| (c.to_string(), *w)).collect(),
+            text: text.into(),
+        };
+        let documents = [
+            soft(&[("pos", 1.0)], "good movie"),
+            soft(&[("neg", 1.0)], "bad movie"),
+            soft(&[("pos", 0.75), ("neg", 0.25)], "good plot plot"),
+        ];
+        let classes: HashSet<Class> = ["neg".into(), "pos".into()].into();
+        let vocab: HashSet<Word> = ["bad", "good", "movie", "plot"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let model = NaiveBayes::new_soft(&documents, classes.clone(), vocab.clone());
+
+        let plot = model.vocab["plot"];
+        assert_eq!(model.counts.documents, [1.25, 1.75]);
+        assert_eq!(model.counts.words[0][&plot], 0.5);
+        assert_eq!(model.counts.words[1][&plot], 1.5);
+        assert_eq!(model.document_frequency("plot"), Some(1));
+        assert!((model.log_prior[1] - (1.75f64 / 3.0).ln()).abs() < 1e-12);
+
+        // one-hot labels are hard labels
+        let hard: Vec<Document> = documents[..2]
+            .iter()
+            .map(|d| Document {
+                class: d.labels[0].0.clone(),
+                text: d.text.clone(),
+            })
+            .collect();
+        let mut soft_model = NaiveBayes::new_soft(&documents[..2], classes.clone(), vocab.clone());
+        let hard_model = NaiveBayes::new(&hard, classes, vocab);
+        assert_eq!(soft_model.counts, hard_model.counts);
+        assert_eq!(soft_model.log_prior, hard_model.log_prior);
+
+        soft_model.partial_fit_soft(&documents[2..]);
+        assert_eq!(soft_model.counts, model.counts);
+    }
+}
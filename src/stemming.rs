@@ -0,0 +1,131 @@
+use std::ops::ControlFlow;
+
+use crate::{Tokenizer, TokenizerSpec};
+
+/// Languages with a Snowball stemmer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StemmerLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+impl StemmerLanguage {
+    fn stemmer(&self) -> rust_stemmers::Stemmer {
+        use rust_stemmers::Algorithm;
+
+        rust_stemmers::Stemmer::create(match self {
+            StemmerLanguage::Arabic => Algorithm::Arabic,
+            StemmerLanguage::Danish => Algorithm::Danish,
+            StemmerLanguage::Dutch => Algorithm::Dutch,
+            StemmerLanguage::English => Algorithm::English,
+            StemmerLanguage::Finnish => Algorithm::Finnish,
+            StemmerLanguage::French => Algorithm::French,
+            StemmerLanguage::German => Algorithm::German,
+            StemmerLanguage::Greek => Algorithm::Greek,
+            StemmerLanguage::Hungarian => Algorithm::Hungarian,
+            StemmerLanguage::Italian => Algorithm::Italian,
+            StemmerLanguage::Norwegian => Algorithm::Norwegian,
+            StemmerLanguage::Portuguese => Algorithm::Portuguese,
+            StemmerLanguage::Romanian => Algorithm::Romanian,
+            StemmerLanguage::Russian => Algorithm::Russian,
+            StemmerLanguage::Spanish => Algorithm::Spanish,
+            StemmerLanguage::Swedish => Algorithm::Swedish,
+            StemmerLanguage::Tamil => Algorithm::Tamil,
+            StemmerLanguage::Turkish => Algorithm::Turkish,
+        })
+    }
+
+    /// The stem of `word`, which should be lowercase.
+    pub fn stem(&self, word: &str) -> String {
+        self.stemmer().stem(word).into_owned()
+    }
+}
+
+/// A tokenizer reducing every token to its Snowball stem, so that "loved",
+/// "loves" and "loving" count as one word. The stemmers expect lowercase
+/// tokens, see [`crate::TextNormalization`].
+#[derive(Clone, Debug)]
+pub struct Stemmed<T> {
+    pub tokenizer: T,
+    pub language: StemmerLanguage,
+}
+
+impl<T: Tokenizer> Tokenizer for Stemmed<T> {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        let stemmer = self.language.stemmer();
+        self.tokenizer
+            .for_each_token(text, &mut |token| emit(&stemmer.stem(token)));
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let stemmer = self.language.stemmer();
+        self.tokenizer
+            .try_for_each_token(text, &mut |token| emit(&stemmer.stem(token)))
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::Stemmed(
+            Box::new(self.tokenizer.spec()?),
+            self.language,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NaiveBayesBuilder, WhitespaceTokenizer};
+
+    #[test]
+    fn test_stemming() {
+        let stemmed = Stemmed {
+            tokenizer: WhitespaceTokenizer,
+            language: StemmerLanguage::English,
+        };
+        assert_eq!(
+            stemmed.tokenize("loved loves loving"),
+            ["love", "love", "love"]
+        );
+        let rebuilt = stemmed.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("movies"), ["movi"]);
+
+        let documents: Vec<crate::Document> = [("pos", "loved it"), ("neg", "hated it")]
+            .iter()
+            .map(|(class, text)| crate::Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let (model, _) = NaiveBayesBuilder::new()
+            .documents(&documents)
+            .stemming(StemmerLanguage::English)
+            .fit()
+            .unwrap();
+        let unseen = crate::Document {
+            class: String::new(),
+            text: "loving".into(),
+        };
+        assert_eq!(model.predict(&unseen), Some("pos".into()));
+    }
+}
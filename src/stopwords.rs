@@ -0,0 +1,116 @@
+use std::{collections::HashSet, ops::ControlFlow};
+
+use crate::{Tokenizer, TokenizerSpec, Word};
+
+// whitespace-separated lowercase words
+const ENGLISH: &str = "\
+    a about above after again against all am an and any are as at be because been before being
+    below between both but by can could did do does doing down during each few for from further
+    had has have having he her here hers herself him himself his how i if in into is it its itself
+    just me more most my myself no nor not now of off on once only or other our ours ourselves out
+    over own same she should so some such than that the their theirs them themselves then there
+    these they this those through to too under until up very was we were what when where which
+    while who whom why will with would you your yours yourself yourselves";
+
+const FRENCH: &str = "\
+    au aux avec ce ces dans de des du elle en et eux il je la le les leur lui ma mais me mes moi
+    mon ne nos notre nous on ou par pas pour qu que qui sa se ses son sur ta te tes toi ton tu un
+    une vos votre vous est sont été être avoir a ont";
+
+const GERMAN: &str = "\
+    aber alle als also am an auch auf aus bei bin bis da das dass dem den der des die doch du ein
+    eine einem einen einer er es für hat ich ihr im in ist ja kein mit nach nicht noch nur oder
+    sich sie sind so um und uns von vor war was wie wir zu zum zur";
+
+const SPANISH: &str = "\
+    a al algo como con de del el ella en era es esta este fue ha la las le les lo los me mi muy
+    más no nos o para pero por que se si sin su sus te tu un una y ya yo";
+
+/// Languages with a bundled list of function words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StopwordLanguage {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl StopwordLanguage {
+    /// The bundled stopwords, in lowercase.
+    pub fn stopwords(&self) -> HashSet<Word> {
+        let words = match self {
+            StopwordLanguage::English => ENGLISH,
+            StopwordLanguage::French => FRENCH,
+            StopwordLanguage::German => GERMAN,
+            StopwordLanguage::Spanish => SPANISH,
+        };
+        words.split_whitespace().map(str::to_owned).collect()
+    }
+}
+
+/// A tokenizer that drops stopwords, high-frequency function words that
+/// carry little evidence about the class. Stopwords match exactly, so
+/// lowercase lists need lowercased tokens, see [`crate::TextNormalization`].
+#[derive(Clone, Debug)]
+pub struct StopwordFilter<T> {
+    pub tokenizer: T,
+    pub stopwords: HashSet<Word>,
+}
+
+impl<T: Tokenizer> Tokenizer for StopwordFilter<T> {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        self.tokenizer.for_each_token(text, &mut |token| {
+            if !self.stopwords.contains(token) {
+                emit(token);
+            }
+        });
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.tokenizer.try_for_each_token(text, &mut |token| {
+            if self.stopwords.contains(token) {
+                ControlFlow::Continue(())
+            } else {
+                emit(token)
+            }
+        })
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        let mut stopwords: Vec<Word> = self.stopwords.iter().cloned().collect();
+        stopwords.sort_unstable();
+        Some(TokenizerSpec::Stopwords(
+            Box::new(self.tokenizer.spec()?),
+            stopwords,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TextNormalization, TrainOptions, WhitespaceTokenizer};
+
+    #[test]
+    fn test_stopword_filter() {
+        let filter = StopwordFilter {
+            tokenizer: WhitespaceTokenizer,
+            stopwords: StopwordLanguage::English.stopwords(),
+        };
+        assert_eq!(filter.tokenize("the plot of this movie"), ["plot", "movie"]);
+        let rebuilt = filter.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("a good one"), ["good", "one"]);
+
+        // stopwords run after normalization in training options
+        let options = TrainOptions {
+            normalization: TextNormalization::lowercase(),
+            stopwords: ["the".into()].into(),
+            ..Default::default()
+        };
+        assert_eq!(options.effective_tokenizer().tokenize("The End"), ["end"]);
+    }
+}
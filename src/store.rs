@@ -0,0 +1,189 @@
+use crate::{
+    tf_idf::document_weights, Class, Classifier, Document, NaiveBayes, SharedTokenizer, Tokenizer,
+};
+
+/// Read access to the estimated parameters of a multinomial model, so that
+/// they can be kept outside of memory, such as in a memory-mapped file or a
+/// key-value store, and still be scored by [`StoredModel`]. [`NaiveBayes`]
+/// is the in-memory store.
+pub trait ParameterStore: Send + Sync {
+    /// Class names by class id.
+    fn classes(&self) -> &[Class];
+
+    /// Token id of a vocabulary word.
+    fn token(&self, word: &str) -> Option<usize>;
+
+    fn log_prior(&self, class: usize) -> f64;
+
+    /// Log-likelihood of `token` in `class`.
+    fn log_likelihood(&self, class: usize, token: usize) -> f64;
+
+    /// The tokens of `class` with a likelihood of their own, rather than
+    /// the class default, and their log-likelihoods.
+    fn class_features(&self, class: usize) -> Box<dyn Iterator<Item = (usize, f64)> + '_>;
+
+    /// Idf by token id if the model is TF-IDF weighted, see
+    /// [`NaiveBayes::is_tf_idf`]; plain counts by default.
+    fn token_idf(&self) -> Option<&[f64]> {
+        None
+    }
+}
+
+impl ParameterStore for NaiveBayes {
+    fn classes(&self) -> &[Class] {
+        &self.classes
+    }
+
+    fn token(&self, word: &str) -> Option<usize> {
+        self.vocab.get(word).copied()
+    }
+
+    fn log_prior(&self, class: usize) -> f64 {
+        self.log_prior[class]
+    }
+
+    fn log_likelihood(&self, class: usize, token: usize) -> f64 {
+        self.likelihood.get(class, token)
+    }
+
+    fn class_features(&self, class: usize) -> Box<dyn Iterator<Item = (usize, f64)> + '_> {
+        Box::new(
+            (0..self.vocab.len())
+                .filter(move |token| self.likelihood.is_stored(class, *token))
+                .map(move |token| (token, self.likelihood.get(class, token))),
+        )
+    }
+
+    fn token_idf(&self) -> Option<&[f64]> {
+        self.idf.as_deref()
+    }
+}
+
+/// Scores documents from the parameters in a [`ParameterStore`], summing
+/// the log prior and the log-likelihood of every known token, TF-IDF
+/// weighted if the store is, like [`NaiveBayes`] does with the default
+/// [`crate::PredictOptions`].
+pub struct StoredModel<S> {
+    store: S,
+    tokenizer: SharedTokenizer,
+}
+
+impl<S: ParameterStore> StoredModel<S> {
+    /// Scores `store`, splitting documents with `tokenizer`, which must be
+    /// the tokenizer of the stored model.
+    pub fn new(store: S, tokenizer: SharedTokenizer) -> Self {
+        Self { store, tokenizer }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Scores of every class for the tokens of a document.
+    pub fn scores_of_tokens<'a>(&self, tokens: impl IntoIterator<Item = &'a str>) -> Vec<f64> {
+        let classes = self.store.classes().len();
+        let mut scores: Vec<f64> = (0..classes).map(|c| self.store.log_prior(c)).collect();
+        let tokens = tokens.into_iter().filter_map(|word| self.store.token(word));
+        for (token, weight) in document_weights(self.store.token_idf(), tokens) {
+            for (class, score) in scores.iter_mut().enumerate() {
+                *score += weight * self.store.log_likelihood(class, token);
+            }
+        }
+        scores
+    }
+}
+
+impl<S: ParameterStore> Classifier for StoredModel<S> {
+    fn classes(&self) -> &[Class] {
+        self.store.classes()
+    }
+
+    fn scores(&self, document: &Document) -> Vec<f64> {
+        let tokens = self.tokenizer.tokenize(&document.text);
+        self.scores_of_tokens(tokens.iter().map(String::as_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Parameters copied into plain maps, standing in for an external store.
+    struct MapStore {
+        classes: Vec<Class>,
+        tokens: HashMap<String, usize>,
+        log_prior: Vec<f64>,
+        defaults: Vec<f64>,
+        likelihoods: HashMap<(usize, usize), f64>,
+    }
+
+    impl ParameterStore for MapStore {
+        fn classes(&self) -> &[Class] {
+            &self.classes
+        }
+
+        fn token(&self, word: &str) -> Option<usize> {
+            self.tokens.get(word).copied()
+        }
+
+        fn log_prior(&self, class: usize) -> f64 {
+            self.log_prior[class]
+        }
+
+        fn log_likelihood(&self, class: usize, token: usize) -> f64 {
+            self.likelihoods
+                .get(&(class, token))
+                .copied()
+                .unwrap_or(self.defaults[class])
+        }
+
+        fn class_features(&self, class: usize) -> Box<dyn Iterator<Item = (usize, f64)> + '_> {
+            Box::new(
+                self.likelihoods
+                    .iter()
+                    .filter(move |((c, _), _)| *c == class)
+                    .map(|((_, token), likelihood)| (*token, *likelihood)),
+            )
+        }
+    }
+
+    #[test]
+    fn test_stored_model_scores_like_the_model() {
+        let documents: Vec<Document> = [("pos", "good movie"), ("neg", "bad movie plot")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let model = NaiveBayes::new(&documents, classes, crate::vocabulary(&documents));
+
+        let classes = model.classes().len();
+        let store = MapStore {
+            classes: model.classes().to_vec(),
+            tokens: model.vocab.clone(),
+            log_prior: (0..classes).map(|c| model.log_prior(c)).collect(),
+            defaults: (0..classes).map(|c| model.likelihood.default(c)).collect(),
+            likelihoods: (0..classes)
+                .flat_map(|c| model.class_features(c).map(move |(t, l)| ((c, t), l)))
+                .collect(),
+        };
+        // words unseen in a class keep its default, so only 5 of 8 are stored
+        assert_eq!(store.likelihoods.len(), 5);
+        let stored = StoredModel::new(store, model.tokenizer.clone());
+        for text in ["good movie", "bad plot plot", "unknown"] {
+            let document = Document {
+                class: String::new(),
+                text: text.into(),
+            };
+            // the model adds repeated tokens at once, rounding differently
+            let expected = model.scores(&document);
+            for (score, expected) in stored.scores(&document).iter().zip(expected) {
+                assert!((score - expected).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    counts::{vocabulary_with, Counts},
+    progress::{Phase, Progress, Steps},
+    Class, Document, NaiveBayes, SharedTokenizer, Tokenizer, Word,
+};
+
+/// Which words of a corpus make it into the vocabulary, by the number of
+/// documents they occur in.
+#[derive(Clone, Debug)]
+pub struct VocabularySelection {
+    /// Minimum number of documents a word must occur in.
+    pub min_df: usize,
+    /// Largest fraction of the documents a word may occur in, such as 0.9
+    /// to drop the stopwords of a corpus.
+    pub max_df: Option<f64>,
+    /// Keep at most this many words, preferring the most frequent ones.
+    pub max_size: Option<usize>,
+}
+
+impl Default for VocabularySelection {
+    fn default() -> Self {
+        Self {
+            min_df: 1,
+            max_df: None,
+            max_size: None,
+        }
+    }
+}
+
+impl VocabularySelection {
+    /// The words of `documents`, as split by `tokenizer`, that are selected.
+    pub fn select(&self, documents: &[Document], tokenizer: &dyn Tokenizer) -> HashSet<Word> {
+        if self.selects_all() {
+            return vocabulary_with(documents, tokenizer);
+        }
+        let mut document_frequency = HashMap::new();
+        for document in documents {
+            let tokens = tokenizer.tokenize(&document.text);
+            count_document(&mut document_frequency, tokens.iter().map(String::as_str));
+        }
+        self.apply(document_frequency, documents.len())
+            .into_iter()
+            .collect()
+    }
+
+    /// The selected words of `vocab`, by their document frequency in
+    /// `documents` split by `tokenizer`.
+    pub(crate) fn prune(
+        &self,
+        vocab: HashSet<Word>,
+        documents: &[Document],
+        tokenizer: &dyn Tokenizer,
+    ) -> HashSet<Word> {
+        if self.selects_all() {
+            return vocab;
+        }
+        let mut document_frequency: HashMap<Word, usize> =
+            vocab.into_iter().map(|word| (word, 0)).collect();
+        for document in documents {
+            let mut seen = HashSet::new();
+            tokenizer.for_each_token(&document.text, &mut |token| {
+                if let Some(count) = document_frequency.get_mut(token) {
+                    if seen.insert(token.to_owned()) {
+                        *count += 1;
+                    }
+                }
+            });
+        }
+        self.apply(document_frequency, documents.len())
+            .into_iter()
+            .collect()
+    }
+
+    /// Whether every word of a corpus is selected.
+    fn selects_all(&self) -> bool {
+        self.min_df <= 1 && self.max_df.is_none() && self.max_size.is_none()
+    }
+
+    /// The selected words of a corpus of `documents` with
+    /// `document_frequency`, unordered.
+    fn apply(&self, document_frequency: HashMap<Word, usize>, documents: usize) -> Vec<Word> {
+        let max_df = self.max_df.map_or(usize::MAX, |fraction| {
+            (fraction * documents as f64).floor() as usize
+        });
+        let mut words: Vec<(Word, usize)> = document_frequency
+            .into_iter()
+            .filter(|(_, df)| *df >= self.min_df && *df <= max_df)
+            .collect();
+        if let Some(max_size) = self.max_size {
+            words.sort_unstable_by(|(a, a_df), (b, b_df)| b_df.cmp(a_df).then_with(|| a.cmp(b)));
+            words.truncate(max_size);
+        }
+        words.into_iter().map(|(word, _)| word).collect()
+    }
+}
+
+/// Counts every distinct word of a document once.
+fn count_document<'a>(
+    document_frequency: &mut HashMap<Word, usize>,
+    words: impl IntoIterator<Item = &'a str>,
+) {
+    let words: HashSet<&str> = words.into_iter().collect();
+    for word in words {
+        match document_frequency.get_mut(word) {
+            Some(count) => *count += 1,
+            None => {
+                document_frequency.insert(word.to_owned(), 1);
+            }
+        }
+    }
+}
+
+impl NaiveBayes {
+    /// Trains on a corpus that is streamed twice instead of held in memory:
+    /// the first pass computes document frequencies to select the
+    /// vocabulary, the second counts only the selected words. `corpus` must
+    /// yield the same documents every time it is called. Both passes split
+    /// documents with `tokenizer`, which the model keeps.
+    pub fn fit_two_pass<F, I>(
+        corpus: F,
+        classes: HashSet<Class>,
+        selection: &VocabularySelection,
+        tokenizer: impl Tokenizer + 'static,
+    ) -> Self
+    where
+        F: FnMut() -> I,
+        I: IntoIterator<Item = Document>,
+    {
+        Self::fit_two_pass_with_progress(corpus, classes, selection, tokenizer, &mut ())
+    }
+
+    /// Like [`NaiveBayes::fit_two_pass`], reporting progress to `progress`.
+    /// The length of the corpus is unknown during the first pass, which is
+    /// only reported as started and done.
+    pub fn fit_two_pass_with_progress<F, I>(
+        mut corpus: F,
+        classes: HashSet<Class>,
+        selection: &VocabularySelection,
+        tokenizer: impl Tokenizer + 'static,
+        progress: &mut impl Progress,
+    ) -> Self
+    where
+        F: FnMut() -> I,
+        I: IntoIterator<Item = Document>,
+    {
+        let tokenizer = SharedTokenizer::new(tokenizer);
+        progress.report(Phase::DocumentFrequencies, 0.0);
+        let mut total_documents = 0;
+        let mut document_frequency: HashMap<Word, usize> = HashMap::new();
+        for document in corpus() {
+            total_documents += 1;
+            let tokens = tokenizer.tokenize(&document.text);
+            count_document(&mut document_frequency, tokens.iter().map(String::as_str));
+        }
+
+        progress.report(Phase::DocumentFrequencies, 1.0);
+
+        let mut words = selection.apply(document_frequency, total_documents);
+        words.sort_unstable();
+        let vocab: HashMap<Word, usize> = words
+            .into_iter()
+            .enumerate()
+            .map(|(token, word)| (word, token))
+            .collect();
+        let mut classes: Vec<Class> = classes.into_iter().collect();
+        classes.sort_unstable();
+
+        let class_index = Counts::class_index(&classes);
+        let mut steps = Steps::new(progress, Phase::CountingWords, total_documents);
+        let mut counts = Counts::empty(classes.len(), vocab.len());
+        for document in corpus() {
+            counts = counts.add(&document, &class_index, &vocab, &tokenizer);
+            steps.step(1);
+        }
+        steps.finish();
+
+        let mut model = Self::from_counts(vocab, classes, counts, total_documents, progress);
+        model.tokenizer = tokenizer;
+        model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_two_pass_matches_in_memory_training() {
+        let documents: Vec<Document> = [
+            ("pos", "good movie good cast"),
+            ("pos", "good plot"),
+            ("neg", "bad movie"),
+            ("neg", "bad plot bad cast dull"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let selection = VocabularySelection {
+            min_df: 2,
+            max_size: Some(4),
+            ..Default::default()
+        };
+
+        let model = NaiveBayes::fit_two_pass(
+            || documents.clone().into_iter(),
+            classes.clone(),
+            &selection,
+            crate::WhitespaceTokenizer,
+        );
+
+        // "dull" is below min_df, and "plot" loses the tie for the last slot
+        let mut vocab: Vec<&str> = model.vocab.keys().map(|w| w.as_str()).collect();
+        vocab.sort_unstable();
+        assert_eq!(vocab, ["bad", "cast", "good", "movie"]);
+        let upper = |text: &str| -> Vec<String> {
+            text.split_whitespace().map(str::to_uppercase).collect()
+        };
+        let uppercased =
+            NaiveBayes::fit_two_pass(|| documents.clone(), classes.clone(), &selection, upper);
+        assert!(uppercased.vocab.contains_key("GOOD"));
+        let good = Document {
+            class: String::new(),
+            text: "good".into(),
+        };
+        assert_eq!(uppercased.predict(&good), Some("pos".into()));
+
+        let expected = NaiveBayes::new(
+            &documents,
+            classes,
+            vocab.iter().map(|w| w.to_string()).collect(),
+        );
+        assert_eq!(model.counts, expected.counts);
+        assert_eq!(model.total_documents, expected.total_documents);
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{counts::Counts, Document, NaiveBayes};
+
+impl NaiveBayes {
+    /// Replaces the raw counts of the model, trained on `documents`, by
+    /// TF-IDF weighted ones and re-estimates. The idf is the smoothed one of
+    /// [`NaiveBayes::idf`], and is kept for scoring and further training.
+    pub(crate) fn weight_by_tf_idf(&mut self, documents: &[Document]) {
+        let documents_counted = self.total_documents as f64;
+        let idf: Vec<f64> = self
+            .counts
+            .document_frequency
+            .iter()
+            .map(|df| ((1.0 + documents_counted) / (1.0 + *df as f64)).ln() + 1.0)
+            .collect();
+        self.counts =
+            Counts::new_tf_idf(documents, &self.classes, &self.vocab, &self.tokenizer, &idf);
+        self.idf = Some(idf);
+        self.estimate();
+    }
+
+    /// Whether term occurrences are TF-IDF weighted.
+    pub fn is_tf_idf(&self) -> bool {
+        self.idf.is_some()
+    }
+}
+
+/// The token ids of a document with their weights, as scored by
+/// [`NaiveBayes`]: one per occurrence for raw counts, or, given the `idf` of
+/// a TF-IDF weighted model, one per distinct token with the document scaled
+/// to unit length.
+pub(crate) fn document_weights(
+    idf: Option<&[f64]>,
+    tokens: impl IntoIterator<Item = usize>,
+) -> Vec<(usize, f64)> {
+    let Some(idf) = idf else {
+        return tokens.into_iter().map(|token| (token, 1.0)).collect();
+    };
+    let mut tokens: Vec<usize> = tokens.into_iter().collect();
+    tokens.sort_unstable();
+    let mut weights: Vec<(usize, f64)> = tokens
+        .chunk_by(|a, b| a == b)
+        .map(|run| (run[0], run.len() as f64 * idf[run[0]]))
+        .collect();
+    let norm = weights
+        .iter()
+        .map(|(_, weight)| weight.powi(2))
+        .sum::<f64>()
+        .sqrt();
+    for (_, weight) in &mut weights {
+        *weight /= norm;
+    }
+    weights
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Classifier, Document, NaiveBayesBuilder};
+
+    #[test]
+    fn test_tf_idf_discounts_common_words() {
+        let document = |class: &str, text: &str| Document {
+            class: class.into(),
+            text: text.into(),
+        };
+        // "click" is boilerplate of every document, but most frequent in spam
+        let documents = [
+            document("spam", "click click click prize"),
+            document("spam", "click click click money"),
+            document("ham", "click lunch"),
+            document("ham", "click dinner"),
+            document("ham", "click meeting"),
+        ];
+        let fit = |tf_idf| {
+            NaiveBayesBuilder::new()
+                .documents(&documents)
+                .tf_idf(tf_idf)
+                .fit()
+                .unwrap()
+                .0
+                .into_model()
+        };
+        let (raw, weighted) = (fit(false), fit(true));
+        assert!(weighted.is_tf_idf() && !raw.is_tf_idf());
+        let unseen = document("", "click click click click lunch");
+        assert_eq!(raw.predict(&unseen), Some("spam".into()));
+        assert_eq!(weighted.predict(&unseen), Some("ham".into()));
+
+        // every training document adds its unit length to its class
+        let total: f64 = weighted.counts.words.iter().flat_map(|w| w.values()).sum();
+        assert!(total < 5.0 * 2f64.sqrt());
+        let mut updated = weighted.clone();
+        updated.partial_fit(&[document("ham", "click lunch")]);
+        assert!(updated.scores(&unseen)[0] > weighted.scores(&unseen)[0]);
+        assert_eq!(
+            weighted.merge(updated).err(),
+            Some(crate::MergeError::TfIdf)
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use std::{fmt, ops::ControlFlow, sync::Arc};
+
+use crate::{Normalized, StopwordFilter, TextNormalization, Word};
+
+/// Splits text into the words looked up in the vocabulary. A model keeps
+/// its tokenizer, so documents are split the same way at training and
+/// prediction time.
+pub trait Tokenizer: Send + Sync {
+    /// Calls `emit` with every token of `text`, in order.
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str));
+
+    /// Like [`Tokenizer::for_each_token`], stopping at the first token for
+    /// which `emit` breaks. The default still splits all of `text`;
+    /// tokenizers that split lazily override it to stop reading.
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        let mut flow = ControlFlow::Continue(());
+        self.for_each_token(text, &mut |token| {
+            if flow.is_continue() {
+                flow = emit(token);
+            }
+        });
+        flow
+    }
+
+    /// How to rebuild this tokenizer when a saved model is loaded, `None`
+    /// for tokenizers that cannot be saved, such as closures.
+    fn spec(&self) -> Option<TokenizerSpec> {
+        None
+    }
+
+    fn tokenize(&self, text: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        self.for_each_token(text, &mut |token| tokens.push(token.to_owned()));
+        tokens
+    }
+}
+
+/// Splits at whitespace, the default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        text.split_whitespace().for_each(emit);
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        text.split_whitespace().try_for_each(emit)
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::Whitespace)
+    }
+}
+
+/// Takes every match of a regular expression as a token, such as `\w+` to
+/// drop punctuation.
+#[cfg(feature = "regex")]
+#[derive(Clone, Debug)]
+pub struct RegexTokenizer {
+    pattern: regex::Regex,
+}
+
+#[cfg(feature = "regex")]
+impl RegexTokenizer {
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: regex::Regex::new(pattern)?,
+        })
+    }
+}
+
+#[cfg(feature = "regex")]
+impl Tokenizer for RegexTokenizer {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        for token in self.pattern.find_iter(text) {
+            emit(token.as_str());
+        }
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.pattern
+            .find_iter(text)
+            .try_for_each(|token| emit(token.as_str()))
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::Regex(self.pattern.as_str().to_owned()))
+    }
+}
+
+/// Splits at Unicode word boundaries, dropping punctuation, so that text
+/// without spaces around punctuation or in scripts other than Latin is
+/// split sensibly. Runs of Chinese, Japanese, Thai, Lao, Khmer or Burmese,
+/// which are written without spaces between words, become overlapping
+/// character bigrams when `bigrams` is set.
+#[cfg(feature = "unicode")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnicodeTokenizer {
+    pub bigrams: bool,
+}
+
+#[cfg(feature = "unicode")]
+impl Default for UnicodeTokenizer {
+    fn default() -> Self {
+        Self { bigrams: true }
+    }
+}
+
+#[cfg(feature = "unicode")]
+impl Tokenizer for UnicodeTokenizer {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        // start of the current run of unspaced script
+        let mut run: Option<usize> = None;
+        let flush = |run: &mut Option<usize>, end: usize, emit: &mut dyn FnMut(&str)| {
+            if let Some(start) = run.take() {
+                emit_bigrams(&text[start..end], emit);
+            }
+        };
+        for (start, segment) in text.split_word_bound_indices() {
+            if self.bigrams && segment.chars().all(is_unspaced) {
+                run.get_or_insert(start);
+                continue;
+            }
+            flush(&mut run, start, emit);
+            if segment.chars().any(char::is_alphanumeric) {
+                emit(segment);
+            }
+        }
+        flush(&mut run, text.len(), emit);
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        Some(TokenizerSpec::Unicode {
+            bigrams: self.bigrams,
+        })
+    }
+}
+
+/// Overlapping character bigrams of `run`, or the run itself if it is a
+/// single character.
+#[cfg(feature = "unicode")]
+fn emit_bigrams(run: &str, emit: &mut dyn FnMut(&str)) {
+    let starts: Vec<usize> = run.char_indices().map(|(i, _)| i).collect();
+    if starts.len() < 3 {
+        return emit(run);
+    }
+    for pair in starts.windows(3) {
+        emit(&run[pair[0]..pair[2]]);
+    }
+    emit(&run[starts[starts.len() - 2]..]);
+}
+
+/// Whether `c` belongs to a script written without spaces between words.
+#[cfg(feature = "unicode")]
+fn is_unspaced(c: char) -> bool {
+    matches!(c,
+        '\u{0E00}'..='\u{0EFF}' // Thai, Lao
+        | '\u{1000}'..='\u{109F}' // Myanmar
+        | '\u{1780}'..='\u{17FF}' // Khmer
+        | '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{31F0}'..='\u{31FF}'
+        | '\u{3400}'..='\u{4DBF}' // Han
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}')
+}
+
+impl<F: Fn(&str) -> Vec<String> + Send + Sync> Tokenizer for F {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        for token in self(text) {
+            emit(&token);
+        }
+    }
+}
+
+/// The saved form of a built-in tokenizer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TokenizerSpec {
+    Whitespace,
+    /// A [`RegexTokenizer`] and its pattern.
+    #[cfg(feature = "regex")]
+    Regex(String),
+    /// A [`UnicodeTokenizer`].
+    #[cfg(feature = "unicode")]
+    Unicode {
+        bigrams: bool,
+    },
+    /// A tokenizer whose tokens are normalized.
+    Normalized(Box<TokenizerSpec>, TextNormalization),
+    /// A tokenizer dropping the sorted stopwords.
+    Stopwords(Box<TokenizerSpec>, Vec<Word>),
+    /// A [`crate::CharNgramTokenizer`].
+    CharNgrams {
+        min: usize,
+        max: usize,
+    },
+    /// [`crate::WordNgrams`] of a tokenizer, from and to length.
+    WordNgrams(Box<TokenizerSpec>, usize, usize),
+    /// [`crate::FeatureHashing`] of a tokenizer into `2^bits` buckets.
+    Hashed(Box<TokenizerSpec>, u32),
+    /// A tokenizer whose tokens are stemmed.
+    #[cfg(feature = "stemming")]
+    Stemmed(Box<TokenizerSpec>, crate::StemmerLanguage),
+    /// A tokenizer whose tokens follow the conventions of a locale.
+    Localized(Box<TokenizerSpec>, crate::locale::LocaleProfile),
+}
+
+impl TokenizerSpec {
+    pub fn build(&self) -> Result<SharedTokenizer, String> {
+        match self {
+            TokenizerSpec::Whitespace => Ok(SharedTokenizer::new(WhitespaceTokenizer)),
+            #[cfg(feature = "regex")]
+            TokenizerSpec::Regex(pattern) => RegexTokenizer::new(pattern)
+                .map(SharedTokenizer::new)
+                .map_err(|error| error.to_string()),
+            #[cfg(feature = "unicode")]
+            TokenizerSpec::Unicode { bigrams } => {
+                Ok(SharedTokenizer::new(UnicodeTokenizer { bigrams: *bigrams }))
+            }
+            TokenizerSpec::Normalized(tokenizer, normalization) => {
+                if cfg!(not(feature = "unicode")) && normalization.needs_unicode() {
+                    return Err("Unicode normalization needs the `unicode` feature".into());
+                }
+                Ok(SharedTokenizer::new(Normalized {
+                    tokenizer: tokenizer.build()?,
+                    normalization: *normalization,
+                }))
+            }
+            TokenizerSpec::Stopwords(tokenizer, stopwords) => {
+                Ok(SharedTokenizer::new(StopwordFilter {
+                    tokenizer: tokenizer.build()?,
+                    stopwords: stopwords.iter().cloned().collect(),
+                }))
+            }
+            TokenizerSpec::CharNgrams { min, max } => {
+                Ok(SharedTokenizer::new(crate::CharNgramTokenizer {
+                    min: *min,
+                    max: *max,
+                }))
+            }
+            TokenizerSpec::WordNgrams(tokenizer, min, max) => {
+                Ok(SharedTokenizer::new(crate::WordNgrams {
+                    tokenizer: tokenizer.build()?,
+                    min: *min,
+                    max: *max,
+                }))
+            }
+            TokenizerSpec::Hashed(tokenizer, bits) => {
+                Ok(SharedTokenizer::new(crate::FeatureHashing {
+                    tokenizer: tokenizer.build()?,
+                    bits: *bits,
+                }))
+            }
+            #[cfg(feature = "stemming")]
+            TokenizerSpec::Stemmed(tokenizer, language) => {
+                Ok(SharedTokenizer::new(crate::Stemmed {
+                    tokenizer: tokenizer.build()?,
+                    language: *language,
+                }))
+            }
+            TokenizerSpec::Localized(tokenizer, profile) => {
+                Ok(SharedTokenizer::new(crate::locale::Localized {
+                    tokenizer: tokenizer.build()?,
+                    profile: profile.clone(),
+                }))
+            }
+        }
+    }
+}
+
+/// A tokenizer shared between a model, its copies and its training options.
+#[derive(Clone)]
+pub struct SharedTokenizer(Arc<dyn Tokenizer>);
+
+impl SharedTokenizer {
+    pub fn new(tokenizer: impl Tokenizer + 'static) -> Self {
+        Self(Arc::new(tokenizer))
+    }
+
+    /// Tells tokenizers without a [`TokenizerSpec`] apart, by identity.
+    pub(crate) fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Hashes consistently with [`SharedTokenizer::ptr_eq`].
+    pub(crate) fn address(&self) -> usize {
+        Arc::as_ptr(&self.0) as *const () as usize
+    }
+}
+
+impl Default for SharedTokenizer {
+    fn default() -> Self {
+        Self::new(WhitespaceTokenizer)
+    }
+}
+
+impl fmt::Debug for SharedTokenizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.spec() {
+            Some(spec) => write!(f, "{:?}", spec),
+            None => write!(f, "Custom"),
+        }
+    }
+}
+
+impl Tokenizer for SharedTokenizer {
+    fn for_each_token(&self, text: &str, emit: &mut dyn FnMut(&str)) {
+        self.0.for_each_token(text, emit)
+    }
+
+    fn try_for_each_token(
+        &self,
+        text: &str,
+        emit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        self.0.try_for_each_token(text, emit)
+    }
+
+    fn spec(&self) -> Option<TokenizerSpec> {
+        self.0.spec()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SharedTokenizer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.spec() {
+            Some(spec) => spec.serialize(serializer),
+            None => Err(serde::ser::Error::custom(
+                "a custom tokenizer cannot be saved",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SharedTokenizer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TokenizerSpec::deserialize(deserializer)?
+            .build()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenizers() {
+        assert_eq!(
+            WhitespaceTokenizer.tokenize(" good\tmovie "),
+            ["good", "movie"]
+        );
+        let commas = |text: &str| -> Vec<String> { text.split(',').map(str::to_owned).collect() };
+        assert_eq!(commas.tokenize("a,b"), ["a", "b"]);
+        assert_eq!(SharedTokenizer::new(commas).spec(), None);
+        assert_eq!(
+            SharedTokenizer::default().spec(),
+            Some(TokenizerSpec::Whitespace)
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_tokenizer() {
+        let tokenizer = RegexTokenizer::new(r"\w+").unwrap();
+        assert_eq!(tokenizer.tokenize("Good, movie!"), ["Good", "movie"]);
+        let rebuilt = tokenizer.spec().unwrap().build().unwrap();
+        assert_eq!(rebuilt.tokenize("a-b"), ["a", "b"]);
+        assert!(RegexTokenizer::new("(").is_err());
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_unicode_tokenizer() {
+        let tokenizer = UnicodeTokenizer::default();
+        assert_eq!(
+            tokenizer.tokenize("Don't panic, it's fine."),
+            ["Don't", "panic", "it's", "fine"]
+        );
+        // Chinese and Thai become bigrams, the Latin word stays whole
+        assert_eq!(
+            tokenizer.tokenize("我爱北京 ok ภาษาไทย"),
+            [
+                "我爱", "爱北", "北京", "ok", "ภา", "าษ", "ษา", "าไ", "ไท", "ทย"
+            ]
+        );
+        assert_eq!(tokenizer.tokenize("猫"), ["猫"]);
+        let whole = UnicodeTokenizer { bigrams: false };
+        assert_eq!(whole.tokenize("東京タワー"), ["東", "京", "タワー"]);
+    }
+}
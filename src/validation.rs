@@ -0,0 +1,657 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{
+    audit::{AuditOptions, TrainingSummary},
+    ngrams::ngram_vocabulary,
+    resolve_conflicts, Class, ConflictPolicy, Document, LabelMap, NaiveBayes, Normalized,
+    SharedTokenizer, StopwordFilter, TextNormalization, Tokenizer, VocabularySelection, Word,
+    WordNgrams,
+};
+
+/// Why a model could not be trained.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrainError {
+    /// No training documents at all.
+    EmptyCorpus,
+    EmptyVocabulary,
+    /// No classes to train, possibly after dropping the empty ones.
+    NoClasses,
+    /// A class without training documents, which would get a prior of zero.
+    EmptyClass(Class),
+    /// Document `document` is labelled with a class outside the class set.
+    UnknownClass {
+        document: usize,
+        class: Class,
+    },
+    /// A class that is already part of the model.
+    DuplicateClass(Class),
+    /// A smoothing alpha that is negative or not finite.
+    InvalidAlpha,
+    /// An n-gram range starting at 0 or ending before its start.
+    InvalidNgramRange,
+    /// Feature hashing into 0 bits or more than [`crate::MAX_HASH_BITS`].
+    InvalidHashBits,
+}
+
+impl fmt::Display for TrainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrainError::EmptyCorpus => write!(f, "there are no training documents"),
+            TrainError::EmptyVocabulary => write!(f, "the vocabulary is empty"),
+            TrainError::NoClasses => write!(f, "there are no classes to train"),
+            TrainError::EmptyClass(class) => {
+                write!(f, "class {:?} has no training documents", class)
+            }
+            TrainError::UnknownClass { document, class } => {
+                write!(f, "document {} has unknown class {:?}", document, class)
+            }
+            TrainError::DuplicateClass(class) => write!(f, "class {:?} already exists", class),
+            TrainError::InvalidAlpha => write!(f, "smoothing alpha must be finite and at least 0"),
+            TrainError::InvalidHashBits => write!(
+                f,
+                "feature hashing needs 1 to {} bits",
+                crate::MAX_HASH_BITS
+            ),
+            TrainError::InvalidNgramRange => {
+                write!(
+                    f,
+                    "n-gram range must start at 1 or more and not end before it"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TrainError {}
+
+/// Something suspicious that did not stop training.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrainWarning {
+    DroppedEmptyClass(Class),
+    /// Documents without a single vocabulary word, by index.
+    EmptyDocuments(Vec<usize>),
+    /// A class with fewer documents than [`WarningThresholds::min_class_documents`].
+    FewDocuments {
+        class: Class,
+        documents: usize,
+    },
+    /// The largest class outnumbers the smallest by more than
+    /// [`WarningThresholds::max_class_imbalance`], as `(class, documents)`.
+    ClassImbalance {
+        largest: (Class, usize),
+        smallest: (Class, usize),
+    },
+    /// Number of vocabulary words occurring in a single training document.
+    SingletonTokens(usize),
+    /// Number of distinct texts labelled with more than one class, resolved
+    /// by [`TrainOptions::conflicts`].
+    ConflictingLabels(usize),
+}
+
+impl fmt::Display for TrainWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrainWarning::DroppedEmptyClass(class) => {
+                write!(f, "dropped class {:?} without training documents", class)
+            }
+            TrainWarning::EmptyDocuments(documents) => {
+                write!(f, "{} documents have no vocabulary words", documents.len())
+            }
+            TrainWarning::FewDocuments { class, documents } => {
+                write!(f, "class {:?} has only {} documents", class, documents)
+            }
+            TrainWarning::ClassImbalance { largest, smallest } => write!(
+                f,
+                "class {:?} has {} documents but class {:?} only {}",
+                largest.0, largest.1, smallest.0, smallest.1
+            ),
+            TrainWarning::SingletonTokens(tokens) => {
+                write!(f, "{} vocabulary words occur in a single document", tokens)
+            }
+            TrainWarning::ConflictingLabels(texts) => {
+                write!(f, "{} texts have conflicting labels", texts)
+            }
+        }
+    }
+}
+
+/// When [`NaiveBayes::try_new`] warns about the training data.
+#[derive(Clone, Debug)]
+pub struct WarningThresholds {
+    pub min_class_documents: usize,
+    /// Largest allowed ratio of the documents of the largest and the smallest
+    /// class.
+    pub max_class_imbalance: f64,
+}
+
+impl Default for WarningThresholds {
+    fn default() -> Self {
+        Self {
+            min_class_documents: 5,
+            max_class_imbalance: 10.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TrainOptions {
+    /// Drop classes without training documents, with a warning, instead of
+    /// failing with [`TrainError::EmptyClass`].
+    pub drop_empty_classes: bool,
+    /// Lidstone smoothing pseudo-count, add-one smoothing when unset. See
+    /// [`NaiveBayes::set_alpha`].
+    pub alpha: Option<f64>,
+    /// Keep a [`TrainingSummary`] with the model.
+    pub audit: Option<AuditOptions>,
+    /// How texts with conflicting labels are treated. Later checks and
+    /// warnings see the documents after resolution.
+    pub conflicts: ConflictPolicy,
+    pub warnings: WarningThresholds,
+    /// Splits documents into words, at whitespace by default. The model
+    /// keeps it for prediction.
+    pub tokenizer: SharedTokenizer,
+    /// Applied to every token and to every vocabulary word, and kept with
+    /// the tokenizer.
+    pub normalization: TextNormalization,
+    /// Tokens dropped after normalization, and removed from the vocabulary.
+    pub stopwords: HashSet<Word>,
+    /// Renames and merges applied to the labels of the documents and to the
+    /// classes before anything else.
+    pub labels: LabelMap,
+    /// Stems every token and vocabulary word after the stopwords are
+    /// dropped.
+    #[cfg(feature = "stemming")]
+    pub stemming: Option<crate::StemmerLanguage>,
+    /// Prunes the vocabulary by document frequency in the training
+    /// documents, after preprocessing.
+    pub vocabulary_selection: VocabularySelection,
+    /// The shortest and longest word n-grams to count, see
+    /// [`crate::WordNgrams`]. Unigrams only when unset.
+    pub ngram_range: Option<(usize, usize)>,
+    /// Weight the occurrences of every token by its inverse document
+    /// frequency in the training documents, with every document scaled to
+    /// unit length, when training and scoring alike.
+    pub tf_idf: bool,
+    /// The pool training runs in.
+    #[cfg(feature = "parallel")]
+    pub concurrency: crate::Concurrency,
+}
+
+impl TrainOptions {
+    /// The tokenizer followed by the normalization, the stopword filter,
+    /// the stemmer and the n-gram generator, as kept by the trained model.
+    pub(crate) fn effective_tokenizer(&self) -> SharedTokenizer {
+        match self.ngram_range {
+            Some((min, max)) if (min, max) != (1, 1) => SharedTokenizer::new(WordNgrams {
+                tokenizer: self.word_tokenizer(),
+                min,
+                max,
+            }),
+            _ => self.word_tokenizer(),
+        }
+    }
+
+    /// [`TrainOptions::effective_tokenizer`] without n-grams.
+    pub(crate) fn word_tokenizer(&self) -> SharedTokenizer {
+        let mut tokenizer = self.tokenizer.clone();
+        if !self.normalization.is_identity() {
+            tokenizer = SharedTokenizer::new(Normalized {
+                tokenizer,
+                normalization: self.normalization,
+            });
+        }
+        if !self.stopwords.is_empty() {
+            tokenizer = SharedTokenizer::new(StopwordFilter {
+                tokenizer,
+                stopwords: self.stopwords.clone(),
+            });
+        }
+        #[cfg(feature = "stemming")]
+        if let Some(language) = self.stemming {
+            tokenizer = SharedTokenizer::new(crate::Stemmed {
+                tokenizer,
+                language,
+            });
+        }
+        tokenizer
+    }
+}
+
+impl NaiveBayes {
+    /// Trains a model after validating the input with the default
+    /// [`TrainOptions`], discarding the warnings.
+    pub fn fit(
+        documents: &[Document],
+        classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+    ) -> Result<Self, TrainError> {
+        Self::try_new(documents, classes, vocab, &TrainOptions::default()).map(|(model, _)| model)
+    }
+
+    /// Trains on `documents` alone: every label is a class, and every word
+    /// is in the vocabulary.
+    pub fn fit_corpus(documents: &[Document]) -> Result<Self, TrainError> {
+        Self::fit_corpus_with(documents, &VocabularySelection::default())
+    }
+
+    /// Like [`NaiveBayes::fit_corpus`], with the vocabulary limited by
+    /// `selection`, such as to the most frequent words.
+    pub fn fit_corpus_with(
+        documents: &[Document],
+        selection: &VocabularySelection,
+    ) -> Result<Self, TrainError> {
+        let classes = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab = crate::vocabulary(documents);
+        let options = TrainOptions {
+            vocabulary_selection: selection.clone(),
+            ..Default::default()
+        };
+        Self::try_new(documents, classes, vocab, &options).map(|(model, _)| model)
+    }
+
+    /// Like [`NaiveBayes::new`], but validates the input first instead of
+    /// producing infinite priors for classes without documents.
+    pub fn try_new(
+        documents: &[Document],
+        mut classes: HashSet<Class>,
+        vocab: HashSet<Word>,
+        options: &TrainOptions,
+    ) -> Result<(Self, Vec<TrainWarning>), TrainError> {
+        let relabelled;
+        let documents = if options.labels.is_empty() {
+            documents
+        } else {
+            classes = options.labels.map_classes(&classes);
+            relabelled = options.labels.map_documents(documents);
+            relabelled.as_slice()
+        };
+        if documents.is_empty() {
+            return Err(TrainError::EmptyCorpus);
+        }
+        if vocab.is_empty() {
+            return Err(TrainError::EmptyVocabulary);
+        }
+        if let Some((document, d)) = documents
+            .iter()
+            .enumerate()
+            .find(|(_, d)| !classes.contains(&d.class))
+        {
+            return Err(TrainError::UnknownClass {
+                document,
+                class: d.class.clone(),
+            });
+        }
+        let (documents, conflicts) = resolve_conflicts(documents, options.conflicts);
+        let documents = documents.as_slice();
+
+        let seen: HashSet<&str> = documents.iter().map(|d| d.class.as_str()).collect();
+        let mut empty: Vec<Class> = classes
+            .iter()
+            .filter(|class| !seen.contains(class.as_str()))
+            .cloned()
+            .collect();
+        empty.sort_unstable();
+        let mut warnings = Vec::new();
+        if conflicts > 0 {
+            warnings.push(TrainWarning::ConflictingLabels(conflicts));
+        }
+        if let Some(class) = empty.first() {
+            if !options.drop_empty_classes {
+                return Err(TrainError::EmptyClass(class.clone()));
+            }
+            for class in empty {
+                classes.remove(&class);
+                warnings.push(TrainWarning::DroppedEmptyClass(class));
+            }
+        }
+        if classes.is_empty() {
+            return Err(TrainError::NoClasses);
+        }
+        if options
+            .alpha
+            .is_some_and(|alpha| !alpha.is_finite() || alpha < 0.0)
+        {
+            return Err(TrainError::InvalidAlpha);
+        }
+        if options
+            .ngram_range
+            .is_some_and(|(min, max)| min == 0 || max < min)
+        {
+            return Err(TrainError::InvalidNgramRange);
+        }
+
+        let vocab: HashSet<Word> = if options.normalization.is_identity() {
+            vocab
+        } else {
+            vocab
+                .iter()
+                .map(|word| options.normalization.apply(word).into_owned())
+                .collect()
+        };
+        let vocab: HashSet<Word> = vocab
+            .into_iter()
+            .filter(|word| !options.stopwords.contains(word))
+            .collect();
+        #[cfg(feature = "stemming")]
+        let vocab = match options.stemming {
+            Some(language) => vocab.iter().map(|word| language.stem(word)).collect(),
+            None => vocab,
+        };
+        let vocab = match options.ngram_range {
+            Some((min, max)) if (min, max) != (1, 1) => {
+                ngram_vocabulary(documents, &vocab, &options.word_tokenizer(), min, max)
+            }
+            _ => vocab,
+        };
+        let tokenizer = options.effective_tokenizer();
+        let vocab = options
+            .vocabulary_selection
+            .prune(vocab, documents, &tokenizer);
+        if vocab.is_empty() {
+            return Err(TrainError::EmptyVocabulary);
+        }
+        let train = || Self::train(documents, classes, vocab, tokenizer, &mut ());
+        #[cfg(feature = "parallel")]
+        let mut model = options.concurrency.install(train);
+        #[cfg(not(feature = "parallel"))]
+        let mut model = train();
+        if options.tf_idf {
+            model.weight_by_tf_idf(documents);
+        }
+        if let Some(alpha) = options.alpha {
+            model.set_alpha(alpha);
+        }
+        if let Some(audit) = &options.audit {
+            let mut summary = TrainingSummary::new(&model.classes, audit);
+            summary.record(documents, &model.tokenizer);
+            model.summary = Some(summary);
+        }
+        warnings.extend(model.anomalies(documents, &options.warnings));
+        Ok((model, warnings))
+    }
+
+    fn anomalies(
+        &self,
+        documents: &[Document],
+        thresholds: &WarningThresholds,
+    ) -> Vec<TrainWarning> {
+        let mut warnings = Vec::new();
+        let empty: Vec<usize> = documents
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| {
+                let mut known = false;
+                self.tokenizer
+                    .for_each_token(&d.text, &mut |word| known |= self.vocab.contains_key(word));
+                !known
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if !empty.is_empty() {
+            warnings.push(TrainWarning::EmptyDocuments(empty));
+        }
+
+        let sizes = self.classes.iter().zip(
+            self.counts
+                .documents
+                .iter()
+                .map(|documents| *documents as usize),
+        );
+        for (class, documents) in sizes.clone() {
+            if documents < thresholds.min_class_documents {
+                warnings.push(TrainWarning::FewDocuments {
+                    class: class.clone(),
+                    documents,
+                });
+            }
+        }
+        let largest = sizes.clone().rev().max_by_key(|(_, documents)| *documents);
+        let smallest = sizes.min_by_key(|(_, documents)| *documents);
+        if let (Some(largest), Some(smallest)) = (largest, smallest) {
+            if largest.1 as f64 > smallest.1 as f64 * thresholds.max_class_imbalance {
+                warnings.push(TrainWarning::ClassImbalance {
+                    largest: (largest.0.clone(), largest.1),
+                    smallest: (smallest.0.clone(), smallest.1),
+                });
+            }
+        }
+
+        let singletons = self
+            .counts
+            .document_frequency
+            .iter()
+            .filter(|df| **df == 1)
+            .count();
+        if singletons > 0 {
+            warnings.push(TrainWarning::SingletonTokens(singletons));
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_new_validates_classes() {
+        let documents: Vec<Document> = [("pos", "good"), ("neg", "bad")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes: HashSet<Class> = ["pos", "neg", "meh"]
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let vocab: HashSet<Word> = ["good", "bad"].iter().map(|w| w.to_string()).collect();
+        let options = TrainOptions::default();
+
+        assert_eq!(
+            NaiveBayes::fit(&[], classes.clone(), vocab.clone()).err(),
+            Some(TrainError::EmptyCorpus)
+        );
+        assert_eq!(
+            NaiveBayes::try_new(&documents, classes.clone(), HashSet::new(), &options).err(),
+            Some(TrainError::EmptyVocabulary)
+        );
+        let negative = TrainOptions {
+            alpha: Some(-1.0),
+            drop_empty_classes: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            NaiveBayes::try_new(&documents, classes.clone(), vocab.clone(), &negative).err(),
+            Some(TrainError::InvalidAlpha)
+        );
+        assert_eq!(
+            NaiveBayes::try_new(&documents, classes.clone(), vocab.clone(), &options).err(),
+            Some(TrainError::EmptyClass("meh".into()))
+        );
+        let mut without_neg = classes.clone();
+        without_neg.remove("neg");
+        assert_eq!(
+            NaiveBayes::try_new(&documents, without_neg, vocab.clone(), &options).err(),
+            Some(TrainError::UnknownClass {
+                document: 1,
+                class: "neg".into()
+            })
+        );
+
+        let options = TrainOptions {
+            drop_empty_classes: true,
+            alpha: Some(0.5),
+            audit: Some(AuditOptions::default()),
+            ..Default::default()
+        };
+        let (model, warnings) = NaiveBayes::try_new(&documents, classes, vocab, &options).unwrap();
+        assert_eq!(
+            warnings,
+            [
+                TrainWarning::DroppedEmptyClass("meh".into()),
+                TrainWarning::FewDocuments {
+                    class: "neg".into(),
+                    documents: 1
+                },
+                TrainWarning::FewDocuments {
+                    class: "pos".into(),
+                    documents: 1
+                },
+                TrainWarning::SingletonTokens(2),
+            ]
+        );
+        assert_eq!(model.classes(), ["neg", "pos"]);
+        assert!(model.log_prior.iter().all(|prior| prior.is_finite()));
+        assert_eq!(model.alpha(), 0.5);
+        let summary = model.training_summary().unwrap();
+        assert_eq!(summary.class("pos").unwrap().samples[0].text, "good");
+    }
+
+    #[test]
+    fn test_try_new_warns_about_anomalies() {
+        let documents: Vec<Document> = (0..12)
+            .map(|i| Document {
+                class: if i == 0 { "rare" } else { "common" }.into(),
+                text: match i {
+                    0 => "x y".into(),
+                    1 => "".into(),
+                    2 => "unknown".into(),
+                    _ => format!("x w{}", i),
+                },
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let vocab: HashSet<Word> = documents
+            .iter()
+            .flat_map(|d| d.words())
+            .filter(|w| *w != "unknown")
+            .map(|w| w.to_owned())
+            .collect();
+        let options = TrainOptions {
+            warnings: WarningThresholds {
+                min_class_documents: 2,
+                max_class_imbalance: 5.0,
+            },
+            ..Default::default()
+        };
+
+        let (_, warnings) = NaiveBayes::try_new(&documents, classes, vocab, &options).unwrap();
+        assert_eq!(
+            warnings,
+            [
+                TrainWarning::EmptyDocuments(vec![1, 2]),
+                TrainWarning::FewDocuments {
+                    class: "rare".into(),
+                    documents: 1
+                },
+                TrainWarning::ClassImbalance {
+                    largest: ("common".into(), 11),
+                    smallest: ("rare".into(), 1)
+                },
+                // "y" and w3 to w11
+                TrainWarning::SingletonTokens(10),
+            ]
+        );
+        assert_eq!(
+            warnings[2].to_string(),
+            "class \"common\" has 11 documents but class \"rare\" only 1"
+        );
+    }
+
+    #[test]
+    fn test_try_new_normalizes_tokens() {
+        let documents: Vec<Document> = [("pos", "Great GREAT movie"), ("neg", "Awful movie")]
+            .iter()
+            .map(|(class, text)| Document {
+                class: class.to_string(),
+                text: text.to_string(),
+            })
+            .collect();
+        let classes: HashSet<Class> = documents.iter().map(|d| d.class.clone()).collect();
+        let options = TrainOptions {
+            normalization: TextNormalization::lowercase(),
+            ..Default::default()
+        };
+        let vocab = crate::vocabulary(&documents);
+        let (model, _) = NaiveBayes::try_new(&documents, classes, vocab, &options).unwrap();
+
+        let mut words: Vec<&str> = model.vocab.keys().map(String::as_str).collect();
+        words.sort_unstable();
+        assert_eq!(words, ["awful", "great", "movie"]);
+        assert_eq!(model.counts.words[1][&model.vocab["great"]], 2.0);
+        assert_eq!(model.tokenize("AWFUL Movie"), ["awful", "movie"]);
+        let document = Document {
+            class: String::new(),
+            text: "gReAt".into(),
+        };
+        assert_eq!(model.predict(&document), Some("pos".into()));
+    }
+
+    #[test]
+    fn test_fit_corpus_builds_the_vocabulary() {
+        let documents: Vec<Document> = [
+            ("pos", "good movie"),
+            ("pos", "good plot"),
+            ("neg", "bad movie"),
+        ]
+        .iter()
+        .map(|(class, text)| Document {
+            class: class.to_string(),
+            text: text.to_string(),
+        })
+        .collect();
+        let model = NaiveBayes::fit_corpus(&documents).unwrap();
+        assert_eq!(model.classes(), ["neg", "pos"]);
+        assert_eq!(model.vocab.len(), 4);
+
+        let selection = VocabularySelection {
+            max_size: Some(2),
+            ..Default::default()
+        };
+        let model = NaiveBayes::fit_corpus_with(&documents, &selection).unwrap();
+        let mut words: Vec<&str> = model.vocab.keys().map(String::as_str).collect();
+        words.sort_unstable();
+        assert_eq!(words, ["good", "movie"]);
+        assert_eq!(
+            NaiveBayes::fit_corpus(&[]).err(),
+            Some(TrainError::EmptyCorpus)
+        );
+    }
+
+    #[test]
+    fn test_vocabulary_pruning_by_document_frequency() {
+        let documents: Vec<Document> = (0..10)
+            .map(|i| Document {
+                class: format!("c{}", i % 2),
+                text: format!("the w{} x{} y{}", i, i % 3, i % 5),
+            })
+            .collect();
+        let fit = |builder: crate::NaiveBayesBuilder| {
+            let (fitted, _) = builder.documents(&documents).fit().unwrap();
+            let mut words: Vec<String> = fitted.model().vocab.keys().cloned().collect();
+            words.sort_unstable();
+            (words, fitted.into_model().heap_bytes())
+        };
+        let (all, all_bytes) = fit(crate::NaiveBayesBuilder::new());
+        assert_eq!(all.len(), 19);
+
+        // "the" is in every document, the w words in one each
+        let (pruned, pruned_bytes) = fit(crate::NaiveBayesBuilder::new().min_df(2).max_df(0.9));
+        assert_eq!(pruned, ["x0", "x1", "x2", "y0", "y1", "y2", "y3", "y4"]);
+        assert!(pruned_bytes < all_bytes);
+
+        let (top, _) = fit(crate::NaiveBayesBuilder::new()
+            .max_df(0.9)
+            .max_vocabulary(3));
+        assert_eq!(top, ["x0", "x1", "x2"]);
+
+        let error = crate::NaiveBayesBuilder::new()
+            .documents(&documents)
+            .min_df(20)
+            .fit()
+            .err();
+        assert_eq!(error, Some(TrainError::EmptyVocabulary));
+    }
+}